@@ -0,0 +1,15 @@
+/// JSON Schema of the config files
+use atelier_data::templates::Config;
+use std::{env, fs};
+
+fn main() {
+    // Written to the given file, or printed
+    let schema = Config::json_schema();
+    match env::args().nth(1) {
+        Some(file_route) => {
+            fs::write(&file_route, schema + "\n").expect("Failed to write the schema");
+            println!("Schema written to: {}", file_route);
+        }
+        None => println!("{}", schema),
+    }
+}
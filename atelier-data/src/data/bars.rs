@@ -0,0 +1,297 @@
+/// Bar aggregation of trade tapes and midprice series
+use crate::{
+    data::{Column, Dataset},
+    orders::OrderSide,
+    trades::Trade,
+    views::BookView,
+};
+
+// ----------------------------------------------------------------------- BAR KIND -- //
+// ----------------------------------------------------------------------- -------- -- //
+
+/// Rule that closes a bar.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BarKind {
+    /// Fixed time interval, in the units of the timestamps. Bars are aligned
+    /// to multiples of the interval, intervals without ticks have no bar.
+    Time(u64),
+    /// Fixed number of ticks.
+    Tick(usize),
+    /// Traded quantity reaching the threshold.
+    Volume(f64),
+    /// Traded notional (price times quantity) reaching the threshold.
+    Dollar(f64),
+    /// Absolute sum of the tick signs reaching the threshold.
+    TickImbalance(f64),
+    /// Absolute sum of the signed quantities reaching the threshold.
+    VolumeImbalance(f64),
+}
+
+impl BarKind {
+    fn validate(&self) -> Result<(), String> {
+        let valid = match *self {
+            BarKind::Time(interval) => interval > 0,
+            BarKind::Tick(n_ticks) => n_ticks > 0,
+            BarKind::Volume(threshold)
+            | BarKind::Dollar(threshold)
+            | BarKind::TickImbalance(threshold)
+            | BarKind::VolumeImbalance(threshold) => threshold > 0.0,
+        };
+        if !valid {
+            return Err(format!("{:?} threshold must be greater than 0", self));
+        }
+        Ok(())
+    }
+
+    fn needs_volume(&self) -> bool {
+        matches!(
+            self,
+            BarKind::Volume(_) | BarKind::Dollar(_) | BarKind::VolumeImbalance(_)
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------- BAR -- //
+// ---------------------------------------------------------------------------- --- -- //
+
+/// OHLCV bar.
+///
+/// `open_ts` and `close_ts` are the timestamps of the first and last ticks
+/// of the bar. `vwap` is the mean price when the bar has no volume, as in
+/// midprice bars. Imbalances are the sums of the tick signs, +1 for buys
+/// (or up ticks) and -1 for sells (or down ticks), and of the signed
+/// quantities.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Bar {
+    pub open_ts: u64,
+    pub close_ts: u64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+    pub vwap: f64,
+    pub n_trades: usize,
+    pub tick_imbalance: f64,
+    pub volume_imbalance: f64,
+}
+
+/// Price, quantity and sign of a single trade or snapshot.
+struct Tick {
+    ts: u64,
+    price: f64,
+    quantity: f64,
+    sign: f64,
+}
+
+/// Bar being built, with the sums needed for the VWAP.
+struct OpenBar {
+    bar: Bar,
+    notional: f64,
+    price_sum: f64,
+}
+
+impl OpenBar {
+    fn new(tick: &Tick) -> Self {
+        OpenBar {
+            bar: Bar {
+                open_ts: tick.ts,
+                close_ts: tick.ts,
+                open: tick.price,
+                high: tick.price,
+                low: tick.price,
+                close: tick.price,
+                volume: 0.0,
+                vwap: tick.price,
+                n_trades: 0,
+                tick_imbalance: 0.0,
+                volume_imbalance: 0.0,
+            },
+            notional: 0.0,
+            price_sum: 0.0,
+        }
+    }
+
+    fn add(&mut self, tick: &Tick) {
+        let bar = &mut self.bar;
+        bar.close_ts = tick.ts;
+        bar.high = bar.high.max(tick.price);
+        bar.low = bar.low.min(tick.price);
+        bar.close = tick.price;
+        bar.volume += tick.quantity;
+        bar.n_trades += 1;
+        bar.tick_imbalance += tick.sign;
+        bar.volume_imbalance += tick.sign * tick.quantity;
+        self.notional += tick.price * tick.quantity;
+        self.price_sum += tick.price;
+    }
+
+    fn is_complete(&self, kind: &BarKind) -> bool {
+        let bar = &self.bar;
+        match *kind {
+            BarKind::Time(_) => false,
+            BarKind::Tick(n_ticks) => bar.n_trades >= n_ticks,
+            BarKind::Volume(threshold) => bar.volume >= threshold,
+            BarKind::Dollar(threshold) => self.notional >= threshold,
+            BarKind::TickImbalance(threshold) => bar.tick_imbalance.abs() >= threshold,
+            BarKind::VolumeImbalance(threshold) => {
+                bar.volume_imbalance.abs() >= threshold
+            }
+        }
+    }
+
+    fn close(mut self) -> Bar {
+        self.bar.vwap = if self.bar.volume > 0.0 {
+            self.notional / self.bar.volume
+        } else {
+            self.price_sum / self.bar.n_trades as f64
+        };
+        self.bar
+    }
+}
+
+fn aggregate<I: Iterator<Item = Tick>>(ticks: I, kind: &BarKind) -> Vec<Bar> {
+    let mut bars = Vec::new();
+    let mut open: Option<OpenBar> = None;
+
+    for tick in ticks {
+        if let (BarKind::Time(interval), Some(current)) = (kind, open.as_ref()) {
+            if tick.ts / interval != current.bar.open_ts / interval {
+                bars.extend(open.take().map(OpenBar::close));
+            }
+        }
+
+        let current = open.get_or_insert_with(|| OpenBar::new(&tick));
+        current.add(&tick);
+
+        if current.is_complete(kind) {
+            bars.extend(open.take().map(OpenBar::close));
+        }
+    }
+
+    // The last bar is kept even when its threshold was not reached
+    bars.extend(open.map(OpenBar::close));
+    bars
+}
+
+/// Builds bars from a trade tape.
+///
+/// Tick signs are given by the aggressor side of the trades.
+///
+/// # Parameters
+///
+/// - `trades`: Trades sorted by timestamp.
+/// - `kind`: Rule that closes each bar.
+pub fn bars_from_trades(trades: &[Trade], kind: BarKind) -> Result<Vec<Bar>, String> {
+    kind.validate()?;
+    if !trades.is_sorted_by_key(|trade| trade.trade_ts) {
+        return Err("trades are not sorted by timestamp".to_string());
+    }
+
+    let ticks = trades.iter().map(|trade| Tick {
+        ts: trade.trade_ts,
+        price: trade.price,
+        quantity: trade.quantity,
+        sign: match trade.side {
+            OrderSide::Bids => 1.0,
+            OrderSide::Asks => -1.0,
+        },
+    });
+
+    Ok(aggregate(ticks, &kind))
+}
+
+/// Builds bars from the midprice of a sequence of order books.
+///
+/// Each snapshot with both sides is a tick without quantity, so volume,
+/// dollar and volume imbalance bars are not available. Tick signs follow
+/// the tick rule: the sign of the midprice change, or the previous sign
+/// when the midprice does not change.
+///
+/// # Parameters
+///
+/// - `books`: Order books sorted by timestamp.
+/// - `kind`: Rule that closes each bar.
+pub fn bars_from_midprice<B: BookView>(
+    books: &[B],
+    kind: BarKind,
+) -> Result<Vec<Bar>, String> {
+    kind.validate()?;
+    if kind.needs_volume() {
+        return Err(format!("{:?} bars need traded volume", kind));
+    }
+    if !books.is_sorted_by_key(|book| book.timestamp()) {
+        return Err("order books are not sorted by timestamp".to_string());
+    }
+
+    let mut previous: Option<(f64, f64)> = None;
+    let ticks = books.iter().filter_map(|book| {
+        let price = book.midprice()?;
+        let sign = match previous {
+            Some((last, _)) if price > last => 1.0,
+            Some((last, _)) if price < last => -1.0,
+            Some((_, sign)) => sign,
+            None => 0.0,
+        };
+        previous = Some((price, sign));
+
+        Some(Tick {
+            ts: book.timestamp(),
+            price,
+            quantity: 0.0,
+            sign,
+        })
+    });
+
+    Ok(aggregate(ticks, &kind))
+}
+
+// --------------------------------------------------------------------- TO DATASET -- //
+// --------------------------------------------------------------------- ---------- -- //
+
+/// Names of the feature columns of `bars_to_dataset`.
+pub const BAR_FEATURES: [&str; 10] = [
+    "open",
+    "high",
+    "low",
+    "close",
+    "volume",
+    "vwap",
+    "n_trades",
+    "tick_imbalance",
+    "volume_imbalance",
+    "duration",
+];
+
+/// Dataset with one row per bar, indexed by the close timestamp.
+///
+/// The features are the `BAR_FEATURES` columns, `duration` being the time
+/// from the first to the last tick. The target, `bar_return`, is the return
+/// from the open to the close of the same bar, use `align_horizon` to
+/// predict the ones of the next bars.
+pub fn bars_to_dataset(bars: &[Bar]) -> Result<Dataset, String> {
+    let values = |value: fn(&Bar) -> f64| bars.iter().map(value).collect::<Vec<f64>>();
+
+    let columns = vec![
+        Column::new(BAR_FEATURES[0], values(|bar| bar.open)),
+        Column::new(BAR_FEATURES[1], values(|bar| bar.high)),
+        Column::new(BAR_FEATURES[2], values(|bar| bar.low)),
+        Column::new(BAR_FEATURES[3], values(|bar| bar.close)),
+        Column::new(BAR_FEATURES[4], values(|bar| bar.volume)),
+        Column::new(BAR_FEATURES[5], values(|bar| bar.vwap)),
+        Column::new(BAR_FEATURES[6], values(|bar| bar.n_trades as f64)),
+        Column::new(BAR_FEATURES[7], values(|bar| bar.tick_imbalance)),
+        Column::new(BAR_FEATURES[8], values(|bar| bar.volume_imbalance)),
+        Column::new(
+            BAR_FEATURES[9],
+            values(|bar| (bar.close_ts - bar.open_ts) as f64),
+        ),
+    ];
+
+    Dataset::new()
+        .index(bars.iter().map(|bar| bar.close_ts).collect())
+        .columns(columns)
+        .target(values(|bar| bar.close / bar.open - 1.0))
+        .target_name("bar_return")
+        .build()
+}
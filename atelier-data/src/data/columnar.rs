@@ -0,0 +1,949 @@
+/// Apache Arrow / Parquet I/O for datasets and order books
+use crate::{
+    data::{Column, Dataset},
+    levels::Level,
+    orderbooks::Orderbook,
+    orders::OrderSide,
+    trades::Trade,
+};
+use arrow::{
+    array::{
+        Array, ArrayRef, Float64Array, Float64Builder, StringArray, StringBuilder,
+        UInt32Array, UInt32Builder, UInt64Array, UInt64Builder,
+    },
+    datatypes::{DataType, Field, Schema, SchemaRef},
+    record_batch::RecordBatch,
+};
+use parquet::{
+    arrow::{
+        arrow_reader::{ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder},
+        ArrowWriter, ProjectionMask,
+    },
+    file::properties::WriterProperties,
+};
+use std::{
+    collections::HashMap, collections::VecDeque, error::Error, fs::File, sync::Arc,
+};
+
+/// Default number of rows per row group when writing, and per batch when
+/// reading.
+pub const DEFAULT_ROW_GROUP_SIZE: usize = 64 * 1024;
+
+const TARGETS_KEY: &str = "atelier:targets";
+const SELECTED_TARGET_KEY: &str = "atelier:selected_target";
+const LOOKBACK_KEY: &str = "atelier:lookback";
+const HORIZON_KEY: &str = "atelier:horizon";
+const LAYOUT_KEY: &str = "atelier:layout";
+
+fn column<'a, T: 'static>(batch: &'a RecordBatch, name: &str) -> Result<&'a T, String> {
+    batch
+        .column_by_name(name)
+        .ok_or(format!("Missing column {}", name))?
+        .as_any()
+        .downcast_ref::<T>()
+        .ok_or(format!("Column {} has an unexpected type", name))
+}
+
+fn writer_properties(row_group_size: usize) -> Result<WriterProperties, String> {
+    if row_group_size == 0 {
+        return Err("row_group_size must be greater than 0".to_string());
+    }
+    Ok(WriterProperties::builder()
+        .set_max_row_group_size(row_group_size)
+        .build())
+}
+
+/// Opens a parquet file, keeping only the root columns accepted by `keep`.
+fn open_projected<F: Fn(&str) -> bool>(
+    file_route: &str,
+    batch_size: usize,
+    keep: F,
+) -> Result<ParquetRecordBatchReader, Box<dyn Error>> {
+    if batch_size == 0 {
+        return Err("batch_size must be greater than 0".into());
+    }
+
+    let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(file_route)?)?;
+
+    let indices: Vec<usize> = builder
+        .schema()
+        .fields()
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| keep(field.name()))
+        .map(|(i, _)| i)
+        .collect();
+    let mask = ProjectionMask::roots(builder.parquet_schema(), indices);
+
+    Ok(builder
+        .with_projection(mask)
+        .with_batch_size(batch_size)
+        .build()?)
+}
+
+// ---------------------------------------------------------------- DATASET PARQUET -- //
+// ---------------------------------------------------------------- --------------- -- //
+
+/// Writes a `Dataset` to a parquet file.
+///
+/// The index is written as a `u64` column named `index`, features and
+/// targets as `f64` columns with their names. Which columns are targets, the
+/// selected one, the lookback and the horizon are kept in the file metadata,
+/// so `Dataset::from_parquet` gets back the same Dataset.
+///
+/// # Parameters
+///
+/// - `data`: The Dataset to write.
+/// - `file_route`: Route of the parquet file to create.
+/// - `row_group_size`: Maximum number of rows per row group.
+pub fn write_to_parquet(
+    data: &Dataset,
+    file_route: &str,
+    row_group_size: usize,
+) -> Result<(), Box<dyn Error>> {
+    let target_names: Vec<&str> = data.target_names();
+    let metadata = HashMap::from([
+        (
+            TARGETS_KEY.to_string(),
+            serde_json::to_string(&target_names)?,
+        ),
+        (
+            SELECTED_TARGET_KEY.to_string(),
+            data.target().name.to_string(),
+        ),
+        (LOOKBACK_KEY.to_string(), data.lookback.to_string()),
+        (HORIZON_KEY.to_string(), data.horizon.to_string()),
+    ]);
+
+    let mut fields = vec![Field::new("index", DataType::UInt64, false)];
+    fields.extend(
+        data.features
+            .iter()
+            .chain(data.targets.iter())
+            .map(|c| Field::new(&c.name, DataType::Float64, false)),
+    );
+    let schema = Arc::new(Schema::new(fields).with_metadata(metadata));
+
+    let index: Vec<u64> = (0..data.len())
+        .map(|i| *data.index.get(i).unwrap_or(&(i as u64)))
+        .collect();
+
+    let mut arrays: Vec<ArrayRef> = vec![Arc::new(UInt64Array::from(index))];
+    arrays.extend(
+        data.features
+            .iter()
+            .chain(data.targets.iter())
+            .map(|c| Arc::new(Float64Array::from(c.values.clone())) as ArrayRef),
+    );
+
+    let batch = RecordBatch::try_new(Arc::clone(&schema), arrays)?;
+    let properties = writer_properties(row_group_size)?;
+    let mut writer =
+        ArrowWriter::try_new(File::create(file_route)?, schema, Some(properties))?;
+    writer.write(&batch)?;
+    writer.close()?;
+
+    Ok(())
+}
+
+/// Streams a parquet file written by `write_to_parquet` as consecutive
+/// Datasets of at most `batch_size` rows, only one of them in memory at a
+/// time.
+pub struct DatasetChunks {
+    reader: ParquetRecordBatchReader,
+    feature_names: Vec<String>,
+    target_names: Vec<String>,
+    selected_target: String,
+    lookback: usize,
+    horizon: usize,
+}
+
+impl DatasetChunks {
+    /// Opens a parquet Dataset.
+    ///
+    /// # Parameters
+    ///
+    /// - `file_route`: Route of the parquet file.
+    /// - `features`: Names of the features to read, all of them if `None`.
+    ///   The index and the targets are always read.
+    /// - `batch_size`: Maximum number of rows per chunk.
+    pub fn open(
+        file_route: &str,
+        features: Option<&[&str]>,
+        batch_size: usize,
+    ) -> Result<Self, Box<dyn Error>> {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(file_route)?)?;
+        let schema: SchemaRef = Arc::clone(builder.schema());
+        drop(builder);
+
+        let metadata = schema.metadata();
+        let target_names: Vec<String> = serde_json::from_str(
+            metadata
+                .get(TARGETS_KEY)
+                .ok_or("Not an atelier Dataset, targets metadata is missing")?,
+        )?;
+        let selected_target = metadata
+            .get(SELECTED_TARGET_KEY)
+            .cloned()
+            .unwrap_or(target_names.first().cloned().unwrap_or_default());
+        let lookback = metadata.get(LOOKBACK_KEY).map_or(Ok(0), |v| v.parse())?;
+        let horizon = metadata.get(HORIZON_KEY).map_or(Ok(0), |v| v.parse())?;
+
+        let available: Vec<String> = schema
+            .fields()
+            .iter()
+            .map(|f| f.name().to_string())
+            .filter(|name| name != "index" && !target_names.contains(name))
+            .collect();
+
+        let feature_names: Vec<String> = match features {
+            None => available,
+            Some(features) => {
+                for name in features {
+                    if !available.iter().any(|a| a == name) {
+                        return Err(format!(
+                            "Unknown feature {} in {}",
+                            name, file_route
+                        )
+                        .into());
+                    }
+                }
+                // Keep the order of the file
+                available
+                    .into_iter()
+                    .filter(|a| features.contains(&a.as_str()))
+                    .collect()
+            }
+        };
+
+        let reader = open_projected(file_route, batch_size, |name| {
+            name == "index"
+                || target_names.iter().any(|t| t == name)
+                || feature_names.iter().any(|f| f == name)
+        })?;
+
+        Ok(DatasetChunks {
+            reader,
+            feature_names,
+            target_names,
+            selected_target,
+            lookback,
+            horizon,
+        })
+    }
+
+    fn to_dataset(&self, batch: &RecordBatch) -> Result<Dataset, Box<dyn Error>> {
+        let f64_column = |name: &String| -> Result<Column, String> {
+            let values = column::<Float64Array>(batch, name)?;
+            Ok(Column::new(name, values.values().to_vec()))
+        };
+
+        let mut dataset = Dataset::new()
+            .index(column::<UInt64Array>(batch, "index")?.values().to_vec())
+            .columns(
+                self.feature_names
+                    .iter()
+                    .map(f64_column)
+                    .collect::<Result<_, _>>()?,
+            )
+            .targets(
+                self.target_names
+                    .iter()
+                    .map(f64_column)
+                    .collect::<Result<_, _>>()?,
+            )
+            .build()?;
+
+        dataset.set_target(&self.selected_target)?;
+        dataset.lookback = self.lookback;
+        dataset.horizon = self.horizon;
+
+        Ok(dataset)
+    }
+}
+
+impl Iterator for DatasetChunks {
+    type Item = Result<Dataset, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let batch = self.reader.next()?;
+        Some(
+            batch
+                .map_err(|e| e.into())
+                .and_then(|b| self.to_dataset(&b)),
+        )
+    }
+}
+
+impl Dataset {
+    /// Loads a Dataset written with `write_to_parquet`.
+    ///
+    /// # Parameters
+    ///
+    /// - `file_route`: Route of the parquet file.
+    /// - `features`: Names of the features to read, all of them if `None`.
+    ///   The other feature columns are not even decoded.
+    pub fn from_parquet(
+        file_route: &str,
+        features: Option<&[&str]>,
+    ) -> Result<Dataset, Box<dyn Error>> {
+        let mut chunks =
+            DatasetChunks::open(file_route, features, DEFAULT_ROW_GROUP_SIZE)?;
+
+        let mut dataset = match chunks.next() {
+            Some(chunk) => chunk?,
+            // Empty file, still with its columns
+            None => {
+                let empty = |name: &String| Column::new(name, Vec::new());
+                let mut dataset = Dataset::new()
+                    .index(Vec::new())
+                    .columns(chunks.feature_names.iter().map(empty).collect())
+                    .targets(chunks.target_names.iter().map(empty).collect())
+                    .build()?;
+                dataset.set_target(&chunks.selected_target)?;
+                dataset.lookback = chunks.lookback;
+                dataset.horizon = chunks.horizon;
+                return Ok(dataset);
+            }
+        };
+
+        for chunk in chunks {
+            let chunk = chunk?;
+            dataset.index.extend(chunk.index);
+            for (column, other) in dataset.features.iter_mut().zip(chunk.features) {
+                column.values.extend(other.values);
+            }
+            for (column, other) in dataset.targets.iter_mut().zip(chunk.targets) {
+                column.values.extend(other.values);
+            }
+        }
+
+        Ok(dataset)
+    }
+}
+
+// -------------------------------------------------------------- ORDERBOOK PARQUET -- //
+// -------------------------------------------------------------- ----------------- -- //
+
+/// How order book snapshots are flattened into rows.
+///
+/// Only levels (price and volume) are kept, the individual orders of each
+/// level are not written.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BookLayout {
+    /// One row per level, with columns `orderbook_id`, `orderbook_ts`,
+    /// `symbol`, `snapshot` (position of the snapshot in the file), `side`
+    /// ("bids" or "asks"), `level` (0 is the top of the book), `level_id`,
+    /// `price` and `volume`. A snapshot without levels is kept as a single
+    /// row with null level columns.
+    Levels,
+    /// One row per snapshot with the top `depth` levels of each side, as
+    /// `bid_price_1..depth`, `bid_volume_1..depth`, `ask_price_1..depth` and
+    /// `ask_volume_1..depth` columns, null where the side has less levels.
+    /// Level ids are not kept, levels are read back with their position.
+    Wide(usize),
+}
+
+impl BookLayout {
+    fn schema(&self) -> Schema {
+        let mut fields = vec![
+            Field::new("orderbook_id", DataType::UInt32, false),
+            Field::new("orderbook_ts", DataType::UInt64, false),
+            Field::new("symbol", DataType::Utf8, false),
+        ];
+
+        let layout = match self {
+            BookLayout::Levels => {
+                fields.extend([
+                    Field::new("snapshot", DataType::UInt64, false),
+                    Field::new("side", DataType::Utf8, true),
+                    Field::new("level", DataType::UInt32, true),
+                    Field::new("level_id", DataType::UInt32, true),
+                    Field::new("price", DataType::Float64, true),
+                    Field::new("volume", DataType::Float64, true),
+                ]);
+                "levels".to_string()
+            }
+            BookLayout::Wide(depth) => {
+                for name in wide_names(*depth) {
+                    fields.push(Field::new(name, DataType::Float64, true));
+                }
+                format!("wide:{}", depth)
+            }
+        };
+
+        Schema::new(fields)
+            .with_metadata(HashMap::from([(LAYOUT_KEY.to_string(), layout)]))
+    }
+
+    fn from_metadata(metadata: &HashMap<String, String>) -> Result<Self, String> {
+        let layout = metadata
+            .get(LAYOUT_KEY)
+            .ok_or("Not an atelier order book file, layout metadata is missing")?;
+
+        match layout.split_once(':') {
+            None if layout == "levels" => Ok(BookLayout::Levels),
+            Some(("wide", depth)) => depth
+                .parse()
+                .map(BookLayout::Wide)
+                .map_err(|_| format!("Invalid layout {}", layout)),
+            _ => Err(format!("Invalid layout {}", layout)),
+        }
+    }
+}
+
+/// Column names of the wide layout, bid prices and volumes first.
+fn wide_names(depth: usize) -> Vec<String> {
+    ["bid_price", "bid_volume", "ask_price", "ask_volume"]
+        .iter()
+        .flat_map(|prefix| (1..=depth).map(move |i| format!("{}_{}", prefix, i)))
+        .collect()
+}
+
+/// Writes order book snapshots to a parquet file, one row group at a time.
+///
+/// Snapshots are buffered until `row_group_size` rows are reached, so the
+/// whole history never needs to be in memory. `close` must be called to
+/// write the footer of the file.
+pub struct OrderbookParquetWriter {
+    writer: ArrowWriter<File>,
+    schema: SchemaRef,
+    layout: BookLayout,
+    row_group_size: usize,
+    columns: BookColumns,
+    n_rows: usize,
+}
+
+impl OrderbookParquetWriter {
+    pub fn create(
+        file_route: &str,
+        layout: BookLayout,
+        row_group_size: usize,
+    ) -> Result<Self, Box<dyn Error>> {
+        if layout == BookLayout::Wide(0) {
+            return Err("wide layout depth must be greater than 0".into());
+        }
+
+        let schema = Arc::new(layout.schema());
+        let properties = writer_properties(row_group_size)?;
+        let writer = ArrowWriter::try_new(
+            File::create(file_route)?,
+            Arc::clone(&schema),
+            Some(properties),
+        )?;
+
+        Ok(OrderbookParquetWriter {
+            writer,
+            schema,
+            layout,
+            row_group_size,
+            columns: BookColumns::new(layout),
+            n_rows: 0,
+        })
+    }
+
+    pub fn write(&mut self, orderbook: &Orderbook) -> Result<(), Box<dyn Error>> {
+        self.n_rows += match self.layout {
+            BookLayout::Levels => (orderbook.bids.len() + orderbook.asks.len()).max(1),
+            BookLayout::Wide(_) => 1,
+        };
+        self.columns.append(orderbook, self.layout);
+
+        if self.n_rows >= self.row_group_size {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Writes the buffered snapshots.
+    pub fn flush(&mut self) -> Result<(), Box<dyn Error>> {
+        if self.n_rows == 0 {
+            return Ok(());
+        }
+
+        let batch =
+            RecordBatch::try_new(Arc::clone(&self.schema), self.columns.finish())?;
+        self.writer.write(&batch)?;
+
+        self.n_rows = 0;
+        Ok(())
+    }
+
+    pub fn close(mut self) -> Result<(), Box<dyn Error>> {
+        self.flush()?;
+        self.writer.close()?;
+        Ok(())
+    }
+}
+
+/// Columns of the buffered snapshots, only the prices and volumes of their
+/// levels are kept until the row group is written.
+struct BookColumns {
+    ids: UInt32Builder,
+    timestamps: UInt64Builder,
+    symbols: StringBuilder,
+    // Levels layout
+    snapshots: UInt64Builder,
+    // Number of snapshots appended so far, including the written ones
+    n_snapshots: u64,
+    sides: StringBuilder,
+    positions: UInt32Builder,
+    level_ids: UInt32Builder,
+    prices: Float64Builder,
+    volumes: Float64Builder,
+    // Wide layout, in the order of `wide_names`
+    wide: Vec<Float64Builder>,
+}
+
+impl BookColumns {
+    fn new(layout: BookLayout) -> Self {
+        let n_wide = match layout {
+            BookLayout::Levels => 0,
+            BookLayout::Wide(depth) => 4 * depth,
+        };
+
+        BookColumns {
+            ids: UInt32Builder::new(),
+            timestamps: UInt64Builder::new(),
+            symbols: StringBuilder::new(),
+            snapshots: UInt64Builder::new(),
+            n_snapshots: 0,
+            sides: StringBuilder::new(),
+            positions: UInt32Builder::new(),
+            level_ids: UInt32Builder::new(),
+            prices: Float64Builder::new(),
+            volumes: Float64Builder::new(),
+            wide: (0..n_wide).map(|_| Float64Builder::new()).collect(),
+        }
+    }
+
+    fn append(&mut self, ob: &Orderbook, layout: BookLayout) {
+        match layout {
+            BookLayout::Levels => {
+                let sided = ob
+                    .bids
+                    .iter()
+                    .enumerate()
+                    .map(|level| ("bids", level))
+                    .chain(ob.asks.iter().enumerate().map(|level| ("asks", level)));
+
+                let n_rows = (ob.bids.len() + ob.asks.len()).max(1);
+                for _ in 0..n_rows {
+                    self.ids.append_value(ob.orderbook_id);
+                    self.timestamps.append_value(ob.orderbook_ts);
+                    self.symbols.append_value(&ob.symbol);
+                    self.snapshots.append_value(self.n_snapshots);
+                }
+
+                for (side, (position, level)) in sided {
+                    self.sides.append_value(side);
+                    self.positions.append_value(position as u32);
+                    self.level_ids.append_value(level.level_id);
+                    self.prices.append_value(level.price);
+                    self.volumes.append_value(level.volume);
+                }
+
+                // Placeholder row, so the snapshot is read back
+                if ob.bids.is_empty() && ob.asks.is_empty() {
+                    self.sides.append_null();
+                    self.positions.append_null();
+                    self.level_ids.append_null();
+                    self.prices.append_null();
+                    self.volumes.append_null();
+                }
+                self.n_snapshots += 1;
+            }
+            BookLayout::Wide(depth) => {
+                self.ids.append_value(ob.orderbook_id);
+                self.timestamps.append_value(ob.orderbook_ts);
+                self.symbols.append_value(&ob.symbol);
+
+                // Same order as `wide_names`
+                let columns = [
+                    (&ob.bids, true),
+                    (&ob.bids, false),
+                    (&ob.asks, true),
+                    (&ob.asks, false),
+                ];
+                for (j, (levels, is_price)) in columns.into_iter().enumerate() {
+                    for i in 0..depth {
+                        self.wide[j * depth + i].append_option(levels.get(i).map(
+                            |level| if is_price { level.price } else { level.volume },
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Arrays of the buffered rows, leaving the builders empty.
+    fn finish(&mut self) -> Vec<ArrayRef> {
+        let mut arrays: Vec<ArrayRef> = vec![
+            Arc::new(self.ids.finish()),
+            Arc::new(self.timestamps.finish()),
+            Arc::new(self.symbols.finish()),
+        ];
+
+        if self.wide.is_empty() {
+            arrays.extend([
+                Arc::new(self.snapshots.finish()) as ArrayRef,
+                Arc::new(self.sides.finish()),
+                Arc::new(self.positions.finish()),
+                Arc::new(self.level_ids.finish()),
+                Arc::new(self.prices.finish()),
+                Arc::new(self.volumes.finish()),
+            ]);
+        } else {
+            arrays.extend(
+                self.wide
+                    .iter_mut()
+                    .map(|builder| Arc::new(builder.finish()) as ArrayRef),
+            );
+        }
+        arrays
+    }
+}
+
+/// Writes order book snapshots to a parquet file with the given layout.
+pub fn write_orderbooks_to_parquet(
+    orderbooks: &[Orderbook],
+    file_route: &str,
+    layout: BookLayout,
+) -> Result<(), Box<dyn Error>> {
+    let mut writer =
+        OrderbookParquetWriter::create(file_route, layout, DEFAULT_ROW_GROUP_SIZE)?;
+    for orderbook in orderbooks {
+        writer.write(orderbook)?;
+    }
+    writer.close()
+}
+
+/// Streams the snapshots of a parquet file written by
+/// `OrderbookParquetWriter`, decoding one batch of rows at a time.
+///
+/// Consecutive rows of the levels layout with the same `snapshot` belong
+/// to the same snapshot. Levels are read without their orders. With a `depth`, only the top
+/// `depth` levels of each side are read, in the wide layout the other
+/// columns are not even decoded.
+pub struct OrderbookParquetReader {
+    reader: ParquetRecordBatchReader,
+    layout: BookLayout,
+    depth: Option<usize>,
+    ready: VecDeque<Orderbook>,
+    // Levels layout, snapshot whose rows can continue in the next batch,
+    // along with its `snapshot` number
+    pending: Option<(u64, Orderbook)>,
+}
+
+impl OrderbookParquetReader {
+    pub fn open(
+        file_route: &str,
+        depth: Option<usize>,
+        batch_size: usize,
+    ) -> Result<Self, Box<dyn Error>> {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(file_route)?)?;
+        let layout = BookLayout::from_metadata(builder.schema().metadata())?;
+        drop(builder);
+
+        let projected: Option<Vec<String>> = match (layout, depth) {
+            (BookLayout::Wide(stored), Some(depth)) => {
+                Some(wide_names(depth.min(stored)))
+            }
+            _ => None,
+        };
+
+        let reader = open_projected(file_route, batch_size, |name| match &projected {
+            Some(names) => {
+                ["orderbook_id", "orderbook_ts", "symbol"].contains(&name)
+                    || names.iter().any(|n| n == name)
+            }
+            None => true,
+        })?;
+
+        Ok(OrderbookParquetReader {
+            reader,
+            layout,
+            depth,
+            ready: VecDeque::new(),
+            pending: None,
+        })
+    }
+
+    fn read_levels(&mut self, batch: &RecordBatch) -> Result<(), Box<dyn Error>> {
+        let ids = column::<UInt32Array>(batch, "orderbook_id")?;
+        let timestamps = column::<UInt64Array>(batch, "orderbook_ts")?;
+        let symbols = column::<StringArray>(batch, "symbol")?;
+        let snapshots = column::<UInt64Array>(batch, "snapshot")?;
+        let sides = column::<StringArray>(batch, "side")?;
+        let positions = column::<UInt32Array>(batch, "level")?;
+        let level_ids = column::<UInt32Array>(batch, "level_id")?;
+        let prices = column::<Float64Array>(batch, "price")?;
+        let volumes = column::<Float64Array>(batch, "volume")?;
+
+        for row in 0..batch.num_rows() {
+            let snapshot = snapshots.value(row);
+
+            let same_book =
+                matches!(&self.pending, Some((pending, _)) if *pending == snapshot);
+            if !same_book {
+                if let Some((_, ob)) = self.pending.take() {
+                    self.ready.push_back(ob);
+                }
+                self.pending = Some((
+                    snapshot,
+                    Orderbook::new(
+                        ids.value(row),
+                        timestamps.value(row),
+                        symbols.value(row).to_string(),
+                        Vec::new(),
+                        Vec::new(),
+                    ),
+                ));
+            }
+
+            // Placeholder row of a snapshot without levels
+            if sides.is_null(row) {
+                continue;
+            }
+
+            if self
+                .depth
+                .is_some_and(|depth| positions.value(row) as usize >= depth)
+            {
+                continue;
+            }
+
+            let side = match sides.value(row) {
+                "bids" => OrderSide::Bids,
+                "asks" => OrderSide::Asks,
+                other => {
+                    return Err(format!("Invalid side {} at row {}", other, row).into())
+                }
+            };
+            let level = Level::new(
+                level_ids.value(row),
+                side,
+                prices.value(row),
+                volumes.value(row),
+                Vec::new(),
+            );
+
+            // pending is always set above
+            if let Some((_, ob)) = self.pending.as_mut() {
+                match side {
+                    OrderSide::Bids => ob.bids.push(level),
+                    OrderSide::Asks => ob.asks.push(level),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read_wide(
+        &mut self,
+        batch: &RecordBatch,
+        stored: usize,
+    ) -> Result<(), Box<dyn Error>> {
+        let depth = self.depth.unwrap_or(stored).min(stored);
+
+        let ids = column::<UInt32Array>(batch, "orderbook_id")?;
+        let timestamps = column::<UInt64Array>(batch, "orderbook_ts")?;
+        let symbols = column::<StringArray>(batch, "symbol")?;
+
+        let level_columns = |prefix: &str| -> Result<Vec<&Float64Array>, String> {
+            (1..=depth)
+                .map(|i| column::<Float64Array>(batch, &format!("{}_{}", prefix, i)))
+                .collect()
+        };
+        let bid_prices = level_columns("bid_price")?;
+        let bid_volumes = level_columns("bid_volume")?;
+        let ask_prices = level_columns("ask_price")?;
+        let ask_volumes = level_columns("ask_volume")?;
+
+        let levels = |side: OrderSide,
+                      prices: &[&Float64Array],
+                      volumes: &[&Float64Array],
+                      row: usize|
+         -> Vec<Level> {
+            prices
+                .iter()
+                .zip(volumes.iter())
+                .enumerate()
+                .take_while(|(_, (price, volume))| {
+                    !price.is_null(row) && !volume.is_null(row)
+                })
+                .map(|(i, (price, volume))| {
+                    Level::new(
+                        i as u32,
+                        side,
+                        price.value(row),
+                        volume.value(row),
+                        Vec::new(),
+                    )
+                })
+                .collect()
+        };
+
+        for row in 0..batch.num_rows() {
+            self.ready.push_back(Orderbook::new(
+                ids.value(row),
+                timestamps.value(row),
+                symbols.value(row).to_string(),
+                levels(OrderSide::Bids, &bid_prices, &bid_volumes, row),
+                levels(OrderSide::Asks, &ask_prices, &ask_volumes, row),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Iterator for OrderbookParquetReader {
+    type Item = Result<Orderbook, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(ob) = self.ready.pop_front() {
+                return Some(Ok(ob));
+            }
+
+            let batch = match self.reader.next() {
+                Some(Ok(batch)) => batch,
+                Some(Err(e)) => return Some(Err(e.into())),
+                // No more rows, the last snapshot is complete
+                None => return self.pending.take().map(|(_, ob)| Ok(ob)),
+            };
+
+            let read = match self.layout {
+                BookLayout::Levels => self.read_levels(&batch),
+                BookLayout::Wide(stored) => self.read_wide(&batch, stored),
+            };
+            if let Err(e) = read {
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+/// Loads all the snapshots of a parquet file written with
+/// `write_orderbooks_to_parquet`, with at most `depth` levels per side.
+pub fn load_orderbooks_from_parquet(
+    file_route: &str,
+    depth: Option<usize>,
+) -> Result<Vec<Orderbook>, Box<dyn Error>> {
+    OrderbookParquetReader::open(file_route, depth, DEFAULT_ROW_GROUP_SIZE)?.collect()
+}
+
+// ----------------------------------------------------------------- TRADES PARQUET -- //
+// ----------------------------------------------------------------- -------------- -- //
+
+fn trades_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("trade_id", DataType::UInt64, false),
+        Field::new("trade_ts", DataType::UInt64, false),
+        Field::new("side", DataType::Utf8, false),
+        Field::new("price", DataType::Float64, false),
+        Field::new("quantity", DataType::Float64, false),
+        Field::new("maker_order_id", DataType::UInt64, true),
+        Field::new("taker_order_id", DataType::UInt64, true),
+    ])
+}
+
+/// Writes trades to a parquet file, one row per trade.
+///
+/// The aggressor side is stored as "bids" or "asks", order ids are null
+/// when they are not known.
+///
+/// # Parameters
+///
+/// - `trades`: Trades to write.
+/// - `file_route`: Route of the parquet file.
+/// - `row_group_size`: Number of rows per row group.
+pub fn write_trades_to_parquet(
+    trades: &[Trade],
+    file_route: &str,
+    row_group_size: usize,
+) -> Result<(), Box<dyn Error>> {
+    let schema = Arc::new(trades_schema());
+    let properties = writer_properties(row_group_size)?;
+    let mut writer = ArrowWriter::try_new(
+        File::create(file_route)?,
+        Arc::clone(&schema),
+        Some(properties),
+    )?;
+
+    for chunk in trades.chunks(row_group_size) {
+        let arrays: Vec<ArrayRef> = vec![
+            Arc::new(UInt64Array::from_iter_values(
+                chunk.iter().map(|t| t.trade_id),
+            )),
+            Arc::new(UInt64Array::from_iter_values(
+                chunk.iter().map(|t| t.trade_ts),
+            )),
+            Arc::new(StringArray::from_iter_values(chunk.iter().map(
+                |t| match t.side {
+                    OrderSide::Bids => "bids",
+                    OrderSide::Asks => "asks",
+                },
+            ))),
+            Arc::new(Float64Array::from_iter_values(
+                chunk.iter().map(|t| t.price),
+            )),
+            Arc::new(Float64Array::from_iter_values(
+                chunk.iter().map(|t| t.quantity),
+            )),
+            Arc::new(UInt64Array::from_iter(
+                chunk.iter().map(|t| t.maker_order_id),
+            )),
+            Arc::new(UInt64Array::from_iter(
+                chunk.iter().map(|t| t.taker_order_id),
+            )),
+        ];
+        writer.write(&RecordBatch::try_new(Arc::clone(&schema), arrays)?)?;
+    }
+
+    writer.close()?;
+    Ok(())
+}
+
+/// Loads the trades of a parquet file written with `write_trades_to_parquet`.
+pub fn load_trades_from_parquet(file_route: &str) -> Result<Vec<Trade>, Box<dyn Error>> {
+    let reader = open_projected(file_route, DEFAULT_ROW_GROUP_SIZE, |_| true)?;
+    let mut trades = Vec::new();
+
+    for batch in reader {
+        let batch = batch?;
+        let ids = column::<UInt64Array>(&batch, "trade_id")?;
+        let timestamps = column::<UInt64Array>(&batch, "trade_ts")?;
+        let sides = column::<StringArray>(&batch, "side")?;
+        let prices = column::<Float64Array>(&batch, "price")?;
+        let quantities = column::<Float64Array>(&batch, "quantity")?;
+        let makers = column::<UInt64Array>(&batch, "maker_order_id")?;
+        let takers = column::<UInt64Array>(&batch, "taker_order_id")?;
+
+        let optional =
+            |ids: &UInt64Array, row: usize| (!ids.is_null(row)).then(|| ids.value(row));
+
+        for row in 0..batch.num_rows() {
+            let side = match sides.value(row) {
+                "bids" => OrderSide::Bids,
+                "asks" => OrderSide::Asks,
+                other => {
+                    return Err(format!("Invalid side {} at row {}", other, row).into())
+                }
+            };
+            trades.push(Trade {
+                trade_id: ids.value(row),
+                trade_ts: timestamps.value(row),
+                side,
+                price: prices.value(row),
+                quantity: quantities.value(row),
+                maker_order_id: optional(makers, row),
+                taker_order_id: optional(takers, row),
+            });
+        }
+    }
+
+    Ok(trades)
+}
@@ -0,0 +1,550 @@
+/// Importers of exchange depth stream captures (snapshot plus diff updates)
+use crate::{
+    data::ndjson::open_reader, events::Price, levels::Level, orderbooks::Orderbook,
+    orders::OrderSide,
+};
+use csv::ReaderBuilder;
+use serde::Deserialize;
+use serde_json::Value;
+use std::{collections::BTreeMap, error::Error, io::BufRead};
+
+// --------------------------------------------------------------------- DEPTH SYNC -- //
+// --------------------------------------------------------------------- ---------- -- //
+
+/// What happened to an update given to `DepthSync::update`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SyncOutcome {
+    /// Applied to the book.
+    Applied,
+    /// Waiting for a snapshot, dropped.
+    Unsynced,
+    /// Already contained in the snapshot (`last_id <= snapshot id`), dropped.
+    Stale,
+    /// Ids do not follow the previous ones, the book is out of sync until
+    /// the next snapshot.
+    Gap,
+}
+
+/// Count of updates by outcome, while importing a capture.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyncReport {
+    pub snapshots: usize,
+    pub applied: usize,
+    pub unsynced: usize,
+    pub stale: usize,
+    pub gaps: usize,
+}
+
+impl SyncReport {
+    fn count(&mut self, outcome: SyncOutcome) {
+        match outcome {
+            SyncOutcome::Applied => self.applied += 1,
+            SyncOutcome::Unsynced => self.unsynced += 1,
+            SyncOutcome::Stale => self.stale += 1,
+            SyncOutcome::Gap => self.gaps += 1,
+        }
+    }
+}
+
+/// L2 book kept in sync from a snapshot and diff updates, following the
+/// Binance rules:
+///
+/// - Updates are dropped until a snapshot is received.
+/// - Updates with `last_id <= lastUpdateId` of the snapshot are stale.
+/// - The first update applied must have `first_id <= lastUpdateId + 1 <=
+///   last_id`, and every next one `first_id == previous last_id + 1`.
+///   Otherwise there is a gap and updates are dropped until the next
+///   snapshot.
+///
+/// Updates give the new total quantity of a price level, 0 removes it.
+/// Updates without ids are applied as they come, once synced.
+#[derive(Debug, Clone, Default)]
+pub struct DepthSync {
+    bids: BTreeMap<Price, f64>,
+    asks: BTreeMap<Price, f64>,
+    synced: bool,
+    snapshot_id: Option<u64>,
+    last_id: Option<u64>,
+}
+
+impl DepthSync {
+    pub fn new() -> Self {
+        DepthSync::default()
+    }
+
+    pub fn is_synced(&self) -> bool {
+        self.synced
+    }
+
+    /// Replaces the whole book.
+    pub fn snapshot(
+        &mut self,
+        last_update_id: Option<u64>,
+        bids: &[(f64, f64)],
+        asks: &[(f64, f64)],
+    ) {
+        let to_map = |levels: &[(f64, f64)]| {
+            levels
+                .iter()
+                .filter(|(_, quantity)| *quantity > 0.0)
+                .map(|(price, quantity)| (Price(*price), *quantity))
+                .collect()
+        };
+
+        self.bids = to_map(bids);
+        self.asks = to_map(asks);
+        self.synced = true;
+        self.snapshot_id = last_update_id;
+        self.last_id = None;
+    }
+
+    /// Applies a diff update, if it follows the sync rules.
+    pub fn update(
+        &mut self,
+        ids: Option<(u64, u64)>,
+        bids: &[(f64, f64)],
+        asks: &[(f64, f64)],
+    ) -> SyncOutcome {
+        if !self.synced {
+            return SyncOutcome::Unsynced;
+        }
+
+        if let Some((first_id, last_id)) = ids {
+            let in_sequence = match (self.last_id, self.snapshot_id) {
+                (Some(previous), _) => first_id == previous + 1,
+                (None, Some(snapshot_id)) => {
+                    if last_id <= snapshot_id {
+                        return SyncOutcome::Stale;
+                    }
+                    first_id <= snapshot_id + 1
+                }
+                (None, None) => true,
+            };
+
+            if !in_sequence {
+                self.synced = false;
+                return SyncOutcome::Gap;
+            }
+            self.last_id = Some(last_id);
+        }
+
+        for (levels, updates) in [(&mut self.bids, bids), (&mut self.asks, asks)] {
+            for (price, quantity) in updates {
+                if *quantity > 0.0 {
+                    levels.insert(Price(*price), *quantity);
+                } else {
+                    levels.remove(&Price(*price));
+                }
+            }
+        }
+
+        SyncOutcome::Applied
+    }
+
+    /// Snapshot of the top `depth` levels of each side, all of them if
+    /// `None`.
+    pub fn to_orderbook(
+        &self,
+        orderbook_id: u32,
+        orderbook_ts: u64,
+        symbol: &str,
+        depth: Option<usize>,
+    ) -> Orderbook {
+        let depth = depth.unwrap_or(usize::MAX);
+        let to_level = |side: OrderSide| {
+            move |(i, (price, quantity)): (usize, (&Price, &f64))| {
+                Level::new(i as u32, side, price.0, *quantity, Vec::new())
+            }
+        };
+
+        Orderbook::new(
+            orderbook_id,
+            orderbook_ts,
+            symbol.to_string(),
+            self.bids
+                .iter()
+                .rev()
+                .take(depth)
+                .enumerate()
+                .map(to_level(OrderSide::Bids))
+                .collect(),
+            self.asks
+                .iter()
+                .take(depth)
+                .enumerate()
+                .map(to_level(OrderSide::Asks))
+                .collect(),
+        )
+    }
+}
+
+// ------------------------------------------------------------------- DEPTH IMPORT -- //
+// ------------------------------------------------------------------- ------------ -- //
+
+/// Order books of one symbol rebuilt from a capture, one after every
+/// snapshot and every applied update.
+#[derive(Debug, Clone)]
+pub struct DepthReplay {
+    pub orderbooks: Vec<Orderbook>,
+    pub report: SyncReport,
+}
+
+/// Offline importer of depth captures for a given symbol.
+#[derive(Debug, Clone)]
+pub struct DepthImporter {
+    symbol: String,
+    depth: Option<usize>,
+}
+
+impl DepthImporter {
+    pub fn new() -> DepthImporterBuilder {
+        DepthImporterBuilder::new()
+    }
+
+    /// Imports Binance-style JSON captures, one message per line.
+    ///
+    /// Files are read in the given order, e.g. a snapshot file and then the
+    /// updates file, and can be gzip or zstd compressed (see
+    /// `ndjson::open_reader`). Lines are either:
+    ///
+    /// - Snapshots, as returned by the REST depth endpoint, with
+    ///   `lastUpdateId`, `bids` and `asks`. Optional `symbol` (or `s`) and
+    ///   time (`E` or `T`) fields are used when present.
+    /// - `depthUpdate` events, with `E` (time), `s` (symbol), `U` and `u`
+    ///   (first and last update ids), `b` and `a` (bids and asks updates).
+    ///   Combined stream messages, wrapped in `{"stream": .., "data": ..}`,
+    ///   are accepted too.
+    ///
+    /// Messages of other symbols are skipped. Binance times are in
+    /// milliseconds, books get them in microseconds.
+    pub fn from_json(&self, file_routes: &[&str]) -> Result<DepthReplay, Box<dyn Error>> {
+        let mut sync = DepthSync::new();
+        let mut replay = DepthReplay {
+            orderbooks: Vec::new(),
+            report: SyncReport::default(),
+        };
+        let mut last_ts = 0;
+
+        for file_route in file_routes {
+            for (i, line) in open_reader(file_route)?.lines().enumerate() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+
+                let mut message: Value = serde_json::from_str(&line)
+                    .map_err(|e| format!("{} line {}: {}", file_route, i + 1, e))?;
+                if let Some(data) = message.get_mut("data") {
+                    message = data.take();
+                }
+
+                let symbol = message
+                    .get("s")
+                    .or(message.get("symbol"))
+                    .and_then(|s| s.as_str());
+                if symbol.is_some_and(|s| !s.eq_ignore_ascii_case(&self.symbol)) {
+                    continue;
+                }
+                if let Some(ts) = message
+                    .get("E")
+                    .or(message.get("T"))
+                    .and_then(|t| t.as_u64())
+                {
+                    last_ts = ts * 1_000;
+                }
+
+                let parsed: Result<(), String> = if message.get("lastUpdateId").is_some()
+                {
+                    serde_json::from_value::<JsonSnapshot>(message)
+                        .map_err(|e| e.to_string())
+                        .and_then(|snapshot| {
+                            sync.snapshot(
+                                Some(snapshot.last_update_id),
+                                &parse_levels(&snapshot.bids)?,
+                                &parse_levels(&snapshot.asks)?,
+                            );
+                            replay.report.snapshots += 1;
+                            self.push(&sync, &mut replay, last_ts);
+                            Ok(())
+                        })
+                } else if message.get("e").and_then(|e| e.as_str()) == Some("depthUpdate")
+                {
+                    serde_json::from_value::<JsonUpdate>(message)
+                        .map_err(|e| e.to_string())
+                        .and_then(|update| {
+                            let outcome = sync.update(
+                                Some((update.first_id, update.last_id)),
+                                &parse_levels(&update.bids)?,
+                                &parse_levels(&update.asks)?,
+                            );
+                            replay.report.count(outcome);
+                            if outcome == SyncOutcome::Applied {
+                                self.push(&sync, &mut replay, last_ts);
+                            }
+                            Ok(())
+                        })
+                } else {
+                    // Other messages, e.g. subscription results
+                    Ok(())
+                };
+
+                parsed.map_err(|e| format!("{} line {}: {}", file_route, i + 1, e))?;
+            }
+        }
+
+        Ok(replay)
+    }
+
+    /// Imports an incremental L2 CSV capture, one price level update per
+    /// row.
+    ///
+    /// Columns are found by name: `timestamp`, `side` (`bid`/`buy` or
+    /// `ask`/`sell`), `price`, `quantity` (or `amount`) and `is_snapshot`
+    /// are required, `symbol`, `first_update_id` and `last_update_id` are
+    /// optional. Consecutive rows with the same timestamp and update ids are
+    /// one message, consecutive snapshot rows are one snapshot. A book is
+    /// produced after each message, with the timestamp of the file.
+    pub fn from_csv(&self, file_route: &str) -> Result<DepthReplay, Box<dyn Error>> {
+        let mut rdr = ReaderBuilder::new()
+            .has_headers(true)
+            .from_reader(open_reader(file_route)?);
+        let header = rdr.headers()?.clone();
+
+        let position =
+            |names: &[&str]| header.iter().position(|h| names.contains(&h.trim()));
+        let required = |names: &[&str]| {
+            position(names).ok_or(format!("Missing column {}", names[0]))
+        };
+
+        let ts_column = required(&["timestamp"])?;
+        let side_column = required(&["side"])?;
+        let price_column = required(&["price"])?;
+        let quantity_column = required(&["quantity", "amount"])?;
+        let snapshot_column = required(&["is_snapshot"])?;
+        let symbol_column = position(&["symbol"]);
+        let first_id_column = position(&["first_update_id"]);
+        let last_id_column = position(&["last_update_id"]);
+
+        let mut sync = DepthSync::new();
+        let mut replay = DepthReplay {
+            orderbooks: Vec::new(),
+            report: SyncReport::default(),
+        };
+        let mut message: Option<CsvMessage> = None;
+
+        for (i, record) in rdr.records().enumerate() {
+            let record = record?;
+            let row = i + 1;
+            let field = |column: usize| record.get(column).unwrap_or("").trim();
+            let invalid = |name: &str, column: usize| {
+                format!("invalid {} {:?} at row {}", name, field(column), row)
+            };
+
+            if symbol_column.is_some_and(|c| !field(c).eq_ignore_ascii_case(&self.symbol))
+            {
+                continue;
+            }
+
+            let parse_id = |column: Option<usize>| -> Result<Option<u64>, String> {
+                match column.map(field) {
+                    None | Some("") => Ok(None),
+                    Some(id) => id
+                        .parse()
+                        .map(Some)
+                        .map_err(|_| invalid("update id", column.unwrap_or(0))),
+                }
+            };
+
+            let key = CsvKey {
+                ts: field(ts_column)
+                    .parse()
+                    .map_err(|_| invalid("timestamp", ts_column))?,
+                is_snapshot: matches!(
+                    field(snapshot_column).to_lowercase().as_str(),
+                    "true" | "1"
+                ),
+                first_id: parse_id(first_id_column)?,
+                last_id: parse_id(last_id_column)?,
+            };
+            let level = (
+                field(price_column)
+                    .parse()
+                    .map_err(|_| invalid("price", price_column))?,
+                field(quantity_column)
+                    .parse()
+                    .map_err(|_| invalid("quantity", quantity_column))?,
+            );
+            let is_bid = match field(side_column).to_lowercase().as_str() {
+                "bid" | "bids" | "buy" => true,
+                "ask" | "asks" | "sell" => false,
+                _ => return Err(invalid("side", side_column).into()),
+            };
+
+            // A new message starts, the previous one is complete
+            let same = message.as_ref().is_some_and(|m| m.key.continues(&key));
+            if !same {
+                if let Some(previous) = message.take() {
+                    self.apply_csv(&mut sync, &mut replay, previous);
+                }
+                message = Some(CsvMessage {
+                    key,
+                    bids: Vec::new(),
+                    asks: Vec::new(),
+                });
+            }
+
+            if let Some(message) = message.as_mut() {
+                if is_bid {
+                    message.bids.push(level);
+                } else {
+                    message.asks.push(level);
+                }
+            }
+        }
+
+        if let Some(last) = message {
+            self.apply_csv(&mut sync, &mut replay, last);
+        }
+
+        Ok(replay)
+    }
+
+    fn apply_csv(
+        &self,
+        sync: &mut DepthSync,
+        replay: &mut DepthReplay,
+        message: CsvMessage,
+    ) {
+        let key = message.key;
+
+        if key.is_snapshot {
+            sync.snapshot(key.last_id, &message.bids, &message.asks);
+            replay.report.snapshots += 1;
+            self.push(sync, replay, key.ts);
+            return;
+        }
+
+        let ids = match (key.first_id, key.last_id) {
+            (Some(first_id), Some(last_id)) => Some((first_id, last_id)),
+            (None, Some(last_id)) => Some((last_id, last_id)),
+            _ => None,
+        };
+        let outcome = sync.update(ids, &message.bids, &message.asks);
+        replay.report.count(outcome);
+        if outcome == SyncOutcome::Applied {
+            self.push(sync, replay, key.ts);
+        }
+    }
+
+    fn push(&self, sync: &DepthSync, replay: &mut DepthReplay, ts: u64) {
+        let orderbook_id = replay.orderbooks.len() as u32 + 1;
+        replay.orderbooks.push(sync.to_orderbook(
+            orderbook_id,
+            ts,
+            &self.symbol,
+            self.depth,
+        ));
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonSnapshot {
+    #[serde(rename = "lastUpdateId")]
+    last_update_id: u64,
+    bids: Vec<(String, String)>,
+    asks: Vec<(String, String)>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonUpdate {
+    #[serde(rename = "U")]
+    first_id: u64,
+    #[serde(rename = "u")]
+    last_id: u64,
+    #[serde(rename = "b")]
+    bids: Vec<(String, String)>,
+    #[serde(rename = "a")]
+    asks: Vec<(String, String)>,
+}
+
+/// Exchanges send prices and quantities as strings, to keep their decimals.
+fn parse_levels(levels: &[(String, String)]) -> Result<Vec<(f64, f64)>, String> {
+    levels
+        .iter()
+        .map(|(price, quantity)| {
+            Ok((
+                price
+                    .parse()
+                    .map_err(|_| format!("invalid price {:?}", price))?,
+                quantity
+                    .parse()
+                    .map_err(|_| format!("invalid quantity {:?}", quantity))?,
+            ))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CsvKey {
+    ts: u64,
+    is_snapshot: bool,
+    first_id: Option<u64>,
+    last_id: Option<u64>,
+}
+
+impl CsvKey {
+    /// Whether a row with `next` key belongs to the same message.
+    fn continues(&self, next: &CsvKey) -> bool {
+        if self.is_snapshot || next.is_snapshot {
+            return self.is_snapshot && next.is_snapshot && self.last_id == next.last_id;
+        }
+        self == next
+    }
+}
+
+#[derive(Debug)]
+struct CsvMessage {
+    key: CsvKey,
+    bids: Vec<(f64, f64)>,
+    asks: Vec<(f64, f64)>,
+}
+
+#[derive(Debug)]
+pub struct DepthImporterBuilder {
+    symbol: Option<String>,
+    depth: Option<usize>,
+}
+
+impl DepthImporterBuilder {
+    pub fn new() -> Self {
+        DepthImporterBuilder {
+            symbol: None,
+            depth: None,
+        }
+    }
+
+    /// Symbol to import, compared ignoring the case.
+    pub fn symbol(mut self, symbol: &str) -> Self {
+        self.symbol = Some(symbol.to_string());
+        self
+    }
+
+    /// Levels per side of the books produced, all of them if not set.
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+
+    pub fn build(self) -> Result<DepthImporter, &'static str> {
+        let symbol = self.symbol.ok_or("Missing symbol")?;
+
+        if self.depth == Some(0) {
+            return Err("depth must be greater than 0");
+        }
+
+        Ok(DepthImporter {
+            symbol,
+            depth: self.depth,
+        })
+    }
+}
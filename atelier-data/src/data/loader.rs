@@ -0,0 +1,274 @@
+/// Mini-batch loading of a Dataset into tensors
+use crate::data::Dataset;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use std::{
+    sync::{mpsc, Arc},
+    thread,
+};
+use tch::{Kind, Tensor};
+
+// -------------------------------------------------------------------- DATA LOADER -- //
+// -------------------------------------------------------------------- ----------- -- //
+
+/// Iterates a `Dataset` in batches of (features, target) tensors.
+///
+/// Only one batch (plus the prefetched ones) is materialized as tensors at a
+/// time, so datasets too large to be converted into a single tensor can
+/// still be used for training.
+///
+/// With `shuffle(window)`, the rows are shuffled within consecutive windows
+/// of `window` rows, a window as large as the dataset is a full shuffle,
+/// smaller ones keep the coarse chronological order of the rows. With
+/// `prefetch(n)`, batches are built in a background thread up to `n`
+/// batches ahead of the consumer. The target is the selected one of the
+/// Dataset, unless another one is given with `target(name)`.
+#[derive(Debug, Clone)]
+pub struct DataLoader {
+    dataset: Arc<Dataset>,
+    target: usize,
+    batch_size: usize,
+    shuffle_window: Option<usize>,
+    drop_last: bool,
+    prefetch: usize,
+    seed: Option<u64>,
+}
+
+impl DataLoader {
+    pub fn new() -> DataLoaderBuilder {
+        DataLoaderBuilder::new()
+    }
+
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// Number of batches per epoch.
+    pub fn len(&self) -> usize {
+        if self.drop_last {
+            self.dataset.len() / self.batch_size
+        } else {
+            self.dataset.len().div_ceil(self.batch_size)
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Row positions of every batch of the given epoch.
+    ///
+    /// With a seed, the order only depends on the seed and the epoch, so
+    /// runs can be reproduced.
+    pub fn batch_rows(&self, epoch: u64) -> Vec<Vec<usize>> {
+        let mut rows: Vec<usize> = (0..self.dataset.len()).collect();
+
+        if let Some(window) = self.shuffle_window {
+            let mut rng = match self.seed {
+                Some(seed) => StdRng::seed_from_u64(seed.wrapping_add(epoch)),
+                None => StdRng::from_rng(&mut rand::rng()),
+            };
+            rows.chunks_mut(window)
+                .for_each(|chunk| chunk.shuffle(&mut rng));
+        }
+
+        rows.chunks(self.batch_size)
+            .filter(|chunk| !self.drop_last || chunk.len() == self.batch_size)
+            .map(|chunk| chunk.to_vec())
+            .collect()
+    }
+
+    /// Batches of the given epoch, as (features, target) tensors of size
+    /// [batch_size, n_features] and [batch_size].
+    pub fn batches(&self, epoch: u64) -> Batches {
+        let batch_rows = self.batch_rows(epoch);
+
+        if self.prefetch == 0 {
+            return Batches {
+                inner: BatchesInner::Lazy {
+                    dataset: Arc::clone(&self.dataset),
+                    target: self.target,
+                    batch_rows: batch_rows.into_iter(),
+                },
+            };
+        }
+
+        let (sender, receiver) = mpsc::sync_channel(self.prefetch);
+        let dataset = Arc::clone(&self.dataset);
+        let target = self.target;
+
+        thread::spawn(move || {
+            for rows in batch_rows {
+                // the consumer dropped the iterator
+                if sender.send(to_tensors(&dataset, target, &rows)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Batches {
+            inner: BatchesInner::Prefetched { receiver },
+        }
+    }
+}
+
+/// Builds the features and target tensors of the given rows.
+fn to_tensors(dataset: &Dataset, target: usize, rows: &[usize]) -> (Tensor, Tensor) {
+    let n_rows = rows.len() as i64;
+    let n_features = dataset.feature_count() as i64;
+
+    let features: Vec<f64> = rows
+        .iter()
+        .flat_map(|row| dataset.features.iter().map(move |c| c.values[*row]))
+        .collect();
+    let target_values: Vec<f64> = rows
+        .iter()
+        .map(|row| dataset.targets[target].values[*row])
+        .collect();
+
+    (
+        Tensor::from_slice(&features)
+            .reshape([n_rows, n_features])
+            .to_kind(Kind::Float),
+        Tensor::from_slice(&target_values)
+            .reshape([n_rows])
+            .to_kind(Kind::Float),
+    )
+}
+
+#[derive(Debug)]
+pub struct DataLoaderBuilder {
+    dataset: Option<Arc<Dataset>>,
+    target: Option<String>,
+    batch_size: Option<usize>,
+    shuffle_window: Option<usize>,
+    drop_last: bool,
+    prefetch: usize,
+    seed: Option<u64>,
+}
+
+impl DataLoaderBuilder {
+    pub fn new() -> Self {
+        DataLoaderBuilder {
+            dataset: None,
+            target: None,
+            batch_size: None,
+            shuffle_window: None,
+            drop_last: false,
+            prefetch: 0,
+            seed: None,
+        }
+    }
+
+    pub fn dataset(mut self, dataset: impl Into<Arc<Dataset>>) -> Self {
+        self.dataset = Some(dataset.into());
+        self
+    }
+
+    /// Name of the target to load, instead of the selected one.
+    pub fn target(mut self, target: &str) -> Self {
+        self.target = Some(target.to_string());
+        self
+    }
+
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = Some(batch_size);
+        self
+    }
+
+    /// Shuffles the rows within consecutive windows of `window` rows.
+    pub fn shuffle(mut self, window: usize) -> Self {
+        self.shuffle_window = Some(window);
+        self
+    }
+
+    /// Skips the last batch when it has less than `batch_size` rows.
+    pub fn drop_last(mut self, drop_last: bool) -> Self {
+        self.drop_last = drop_last;
+        self
+    }
+
+    /// Number of batches built ahead in a background thread, 0 to build
+    /// them on demand.
+    pub fn prefetch(mut self, prefetch: usize) -> Self {
+        self.prefetch = prefetch;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn build(self) -> Result<DataLoader, String> {
+        let dataset = self.dataset.ok_or("Missing dataset")?;
+        let batch_size = self.batch_size.ok_or("Missing batch_size")?;
+
+        if batch_size == 0 {
+            return Err("batch_size must be greater than 0".to_string());
+        }
+        if self.shuffle_window == Some(0) {
+            return Err("shuffle window must be greater than 0".to_string());
+        }
+
+        let target = match self.target {
+            Some(name) => dataset
+                .target_names()
+                .iter()
+                .position(|target| *target == name)
+                .ok_or(format!(
+                    "Unknown target: {}, the ones available are: {:?}",
+                    name,
+                    dataset.target_names()
+                ))?,
+            None => dataset.selected_target,
+        };
+
+        Ok(DataLoader {
+            dataset,
+            target,
+            batch_size,
+            shuffle_window: self.shuffle_window,
+            drop_last: self.drop_last,
+            prefetch: self.prefetch,
+            seed: self.seed,
+        })
+    }
+}
+
+// ------------------------------------------------------------------------ BATCHES -- //
+// ------------------------------------------------------------------------ ------- -- //
+
+/// Iterator over the (features, target) tensors of one epoch.
+#[derive(Debug)]
+pub struct Batches {
+    inner: BatchesInner,
+}
+
+#[derive(Debug)]
+enum BatchesInner {
+    Lazy {
+        dataset: Arc<Dataset>,
+        target: usize,
+        batch_rows: std::vec::IntoIter<Vec<usize>>,
+    },
+    Prefetched {
+        receiver: mpsc::Receiver<(Tensor, Tensor)>,
+    },
+}
+
+impl Iterator for Batches {
+    type Item = (Tensor, Tensor);
+
+    fn next(&mut self) -> Option<(Tensor, Tensor)> {
+        match &mut self.inner {
+            BatchesInner::Lazy {
+                dataset,
+                target,
+                batch_rows,
+            } => batch_rows
+                .next()
+                .map(|rows| to_tensors(dataset, *target, &rows)),
+            BatchesInner::Prefetched { receiver } => receiver.recv().ok(),
+        }
+    }
+}
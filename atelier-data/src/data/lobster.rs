@@ -0,0 +1,345 @@
+/// LOBSTER message and orderbook files importer
+use crate::{
+    events::{EventKind, L3Book, L3Event},
+    orderbooks::Orderbook,
+    orders::OrderSide,
+};
+use csv::{ReaderBuilder, StringRecord};
+use std::error::Error;
+
+// LOBSTER files have no header. Message rows are: time (seconds after
+// midnight, up to nanoseconds), event type (1 to 7), order id, size, price
+// (times the price scale) and direction (1 buy, -1 sell limit order).
+// Orderbook rows have, for every level, the ask price, ask size, bid price
+// and bid size, row k being the state right after message k. Empty levels
+// have a price of -9999999999 (bids) or 9999999999 (asks) and a size of 0.
+
+const EMPTY_ASK: f64 = 9_999_999_999.0;
+const EMPTY_BID: f64 = -9_999_999_999.0;
+
+// --------------------------------------------------------------- LOBSTER IMPORTER -- //
+// --------------------------------------------------------------- ---------------- -- //
+
+/// Importer of a pair of LOBSTER message and orderbook files.
+#[derive(Debug, Clone)]
+pub struct LobsterImporter {
+    symbol: String,
+    levels: usize,
+    price_scale: f64,
+    start_ts: u64,
+}
+
+impl LobsterImporter {
+    pub fn new() -> LobsterImporterBuilder {
+        LobsterImporterBuilder::new()
+    }
+
+    /// Parses the message file into L3 events.
+    ///
+    /// Times are converted to nanoseconds and added to `start_ts`, prices
+    /// are divided by the price scale.
+    pub fn read_messages(
+        &self,
+        file_route: &str,
+    ) -> Result<Vec<L3Event>, Box<dyn Error>> {
+        let mut rdr = ReaderBuilder::new()
+            .has_headers(false)
+            .from_path(file_route)?;
+
+        rdr.records()
+            .enumerate()
+            .map(|(i, record)| self.parse_message(&record?, i + 1).map_err(|e| e.into()))
+            .collect()
+    }
+
+    /// Parses the orderbook file into the (price, size) of the levels of
+    /// each side, empty levels skipped.
+    pub fn read_levels(
+        &self,
+        file_route: &str,
+    ) -> Result<Vec<BookLevels>, Box<dyn Error>> {
+        let mut rdr = ReaderBuilder::new()
+            .has_headers(false)
+            .from_path(file_route)?;
+
+        rdr.records()
+            .enumerate()
+            .map(|(i, record)| self.parse_levels(&record?, i + 1).map_err(|e| e.into()))
+            .collect()
+    }
+
+    /// Rebuilds the order book after every message and checks it against
+    /// the paired orderbook file.
+    ///
+    /// The book is seeded with the levels of the first orderbook row, the
+    /// state after the first message, so the replay starts at the second
+    /// message. Both files must have the same number of rows.
+    pub fn replay(
+        &self,
+        message_route: &str,
+        orderbook_route: &str,
+    ) -> Result<LobsterReplay, Box<dyn Error>> {
+        let events = self.read_messages(message_route)?;
+        let levels = self.read_levels(orderbook_route)?;
+
+        if events.len() != levels.len() {
+            return Err(format!(
+                "{} messages but {} orderbook rows",
+                events.len(),
+                levels.len()
+            )
+            .into());
+        }
+
+        let mut book = L3Book::new();
+        let mut orderbooks = Vec::with_capacity(events.len());
+        let mut report = LobsterReport::default();
+
+        let (first_event, first_levels) = match (events.first(), levels.first()) {
+            (Some(event), Some(levels)) => (event, levels),
+            _ => {
+                return Ok(LobsterReplay {
+                    events,
+                    orderbooks,
+                    report,
+                })
+            }
+        };
+
+        for (price, size) in first_levels.bids.iter() {
+            book.seed_level(OrderSide::Bids, *price, *size, first_event.event_ts);
+        }
+        for (price, size) in first_levels.asks.iter() {
+            book.seed_level(OrderSide::Asks, *price, *size, first_event.event_ts);
+        }
+        orderbooks.push(book.to_orderbook(
+            1,
+            first_event.event_ts,
+            &self.symbol,
+            self.levels,
+        ));
+
+        for (i, (event, expected)) in events.iter().zip(levels.iter()).enumerate().skip(1)
+        {
+            let row = i + 1;
+
+            if let Err(e) = book.apply(event) {
+                report.unmatched_events.push((row, e));
+            }
+
+            report.rows_checked += 1;
+            let rebuilt = BookLevels {
+                bids: book.top_levels(OrderSide::Bids, self.levels),
+                asks: book.top_levels(OrderSide::Asks, self.levels),
+            };
+            if !rebuilt.matches(expected, 0.5 / self.price_scale) {
+                report.mismatched_rows.push(row);
+            }
+
+            orderbooks.push(book.to_orderbook(
+                row as u32,
+                event.event_ts,
+                &self.symbol,
+                self.levels,
+            ));
+        }
+
+        Ok(LobsterReplay {
+            events,
+            orderbooks,
+            report,
+        })
+    }
+
+    fn parse_message(
+        &self,
+        record: &StringRecord,
+        row: usize,
+    ) -> Result<L3Event, String> {
+        let field = |i: usize| record.get(i).map(|f| f.trim()).unwrap_or("");
+        let invalid = |name: &str, i: usize| {
+            format!("invalid {} {:?} at row {}", name, field(i), row)
+        };
+
+        let kind = match field(1) {
+            "1" => EventKind::Submit,
+            "2" => EventKind::Cancel,
+            "3" => EventKind::Delete,
+            "4" => EventKind::Execute,
+            "5" => EventKind::ExecuteHidden,
+            "6" => EventKind::Cross,
+            "7" => EventKind::Halt,
+            _ => return Err(invalid("event type", 1)),
+        };
+        let side = match field(5) {
+            "1" => OrderSide::Bids,
+            "-1" => OrderSide::Asks,
+            _ => return Err(invalid("direction", 5)),
+        };
+
+        Ok(L3Event {
+            event_ts: self.start_ts + parse_time(field(0)).ok_or(invalid("time", 0))?,
+            kind,
+            order_id: field(2).parse().map_err(|_| invalid("order id", 2))?,
+            side,
+            price: field(4).parse::<f64>().map_err(|_| invalid("price", 4))?
+                / self.price_scale,
+            size: field(3).parse().map_err(|_| invalid("size", 3))?,
+        })
+    }
+
+    fn parse_levels(
+        &self,
+        record: &StringRecord,
+        row: usize,
+    ) -> Result<BookLevels, String> {
+        if record.len() < 4 * self.levels {
+            return Err(format!(
+                "row {} has {} columns, {} levels need {}",
+                row,
+                record.len(),
+                self.levels,
+                4 * self.levels
+            ));
+        }
+
+        let value = |i: usize| {
+            record[i]
+                .trim()
+                .parse::<f64>()
+                .map_err(|_| format!("invalid number {:?} at row {}", &record[i], row))
+        };
+
+        let mut levels = BookLevels::default();
+        for level in 0..self.levels {
+            let (ask_price, ask_size) = (value(4 * level)?, value(4 * level + 1)?);
+            let (bid_price, bid_size) = (value(4 * level + 2)?, value(4 * level + 3)?);
+
+            if ask_price != EMPTY_ASK && ask_size > 0.0 {
+                levels.asks.push((ask_price / self.price_scale, ask_size));
+            }
+            if bid_price != EMPTY_BID && bid_size > 0.0 {
+                levels.bids.push((bid_price / self.price_scale, bid_size));
+            }
+        }
+        Ok(levels)
+    }
+}
+
+/// Nanoseconds of a decimal number of seconds, without float rounding.
+fn parse_time(time: &str) -> Option<u64> {
+    let (seconds, fraction) = time.split_once('.').unwrap_or((time, ""));
+    if fraction.len() > 9 || !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let nanos = format!("{:0<9}", fraction).parse::<u64>().ok()?;
+    Some(seconds.parse::<u64>().ok()? * 1_000_000_000 + nanos)
+}
+
+#[derive(Debug)]
+pub struct LobsterImporterBuilder {
+    symbol: Option<String>,
+    levels: Option<usize>,
+    price_scale: f64,
+    start_ts: u64,
+}
+
+impl LobsterImporterBuilder {
+    pub fn new() -> Self {
+        LobsterImporterBuilder {
+            symbol: None,
+            levels: None,
+            price_scale: 10_000.0,
+            start_ts: 0,
+        }
+    }
+
+    pub fn symbol(mut self, symbol: &str) -> Self {
+        self.symbol = Some(symbol.to_string());
+        self
+    }
+
+    /// Number of levels of the orderbook file.
+    pub fn levels(mut self, levels: usize) -> Self {
+        self.levels = Some(levels);
+        self
+    }
+
+    /// Prices in the files are the real ones times this scale, 10_000 by
+    /// default.
+    pub fn price_scale(mut self, price_scale: f64) -> Self {
+        self.price_scale = price_scale;
+        self
+    }
+
+    /// Timestamp, in nanoseconds, of the midnight the message times are
+    /// counted from. 0 by default.
+    pub fn start_ts(mut self, start_ts: u64) -> Self {
+        self.start_ts = start_ts;
+        self
+    }
+
+    pub fn build(self) -> Result<LobsterImporter, &'static str> {
+        let symbol = self.symbol.ok_or("Missing symbol")?;
+        let levels = self.levels.ok_or("Missing levels")?;
+
+        if levels == 0 {
+            return Err("levels must be greater than 0");
+        }
+        if self.price_scale <= 0.0 {
+            return Err("price_scale must be greater than 0");
+        }
+
+        Ok(LobsterImporter {
+            symbol,
+            levels,
+            price_scale: self.price_scale,
+            start_ts: self.start_ts,
+        })
+    }
+}
+
+// ----------------------------------------------------------------- LOBSTER REPLAY -- //
+// ----------------------------------------------------------------- -------------- -- //
+
+/// (price, size) of the levels of both sides, from the top of the book.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BookLevels {
+    pub bids: Vec<(f64, f64)>,
+    pub asks: Vec<(f64, f64)>,
+}
+
+impl BookLevels {
+    fn matches(&self, other: &BookLevels, tolerance: f64) -> bool {
+        let same = |a: &[(f64, f64)], b: &[(f64, f64)]| {
+            a.len() == b.len()
+                && a.iter().zip(b.iter()).all(|(x, y)| {
+                    (x.0 - y.0).abs() <= tolerance && (x.1 - y.1).abs() <= 1e-9
+                })
+        };
+        same(&self.bids, &other.bids) && same(&self.asks, &other.asks)
+    }
+}
+
+/// Result of checking the rebuilt books against the orderbook file. Rows
+/// start at 1.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LobsterReport {
+    pub rows_checked: usize,
+    pub mismatched_rows: Vec<usize>,
+    pub unmatched_events: Vec<(usize, String)>,
+}
+
+impl LobsterReport {
+    pub fn is_consistent(&self) -> bool {
+        self.mismatched_rows.is_empty() && self.unmatched_events.is_empty()
+    }
+}
+
+/// Events of the message file, with the book rebuilt after each of them.
+#[derive(Debug, Clone)]
+pub struct LobsterReplay {
+    pub events: Vec<L3Event>,
+    pub orderbooks: Vec<Orderbook>,
+    pub report: LobsterReport,
+}
@@ -0,0 +1,270 @@
+/// Missing values policies for data ingestion
+use crate::data::Column;
+use std::{collections::HashMap, mem};
+
+// ----------------------------------------------------------------- MISSING POLICY -- //
+// ----------------------------------------------------------------- -------------- -- //
+
+/// What to do with a missing (empty, unparseable or NaN) value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MissingPolicy {
+    /// Fail the whole load.
+    Error,
+    /// Drop the row the value belongs to.
+    DropRow,
+    /// Use the last value seen in the column.
+    ForwardFill,
+    /// Use a fixed value.
+    Constant(f64),
+    /// Use the mean of the values present in the column.
+    Mean,
+}
+
+impl MissingPolicy {
+    /// Whether two policies are the same kind, regardless of their values.
+    pub fn same_kind(&self, other: &MissingPolicy) -> bool {
+        mem::discriminant(self) == mem::discriminant(other)
+    }
+}
+
+/// Missing values policy of every column of a file.
+///
+/// Columns without an explicit policy use the default one, which is
+/// `MissingPolicy::Error` unless changed. The index column only supports
+/// `Error` and `DropRow`.
+#[derive(Debug, Clone)]
+pub struct MissingValues {
+    default: MissingPolicy,
+    columns: HashMap<String, MissingPolicy>,
+}
+
+impl Default for MissingValues {
+    fn default() -> Self {
+        MissingValues {
+            default: MissingPolicy::Error,
+            columns: HashMap::new(),
+        }
+    }
+}
+
+impl MissingValues {
+    pub fn new() -> Self {
+        MissingValues::default()
+    }
+
+    /// Policy for the columns without an explicit one.
+    pub fn default_policy(mut self, policy: MissingPolicy) -> Self {
+        self.default = policy;
+        self
+    }
+
+    /// Policy for the column with the given name.
+    pub fn column(mut self, name: &str, policy: MissingPolicy) -> Self {
+        self.columns.insert(name.to_string(), policy);
+        self
+    }
+
+    pub fn policy(&self, name: &str) -> &MissingPolicy {
+        self.columns.get(name).unwrap_or(&self.default)
+    }
+
+    /// Resolves the missing values of the index and the data columns.
+    ///
+    /// Rows are dropped first, so the fill policies only see the rows that
+    /// are kept. Without an index column, rows are indexed by their
+    /// position in the file. Row numbers in errors start at 1.
+    pub(crate) fn resolve(
+        &self,
+        index: Option<(&str, Vec<Option<u64>>)>,
+        columns: Vec<(String, Vec<Option<f64>>)>,
+        n_rows: usize,
+    ) -> Result<(Vec<u64>, Vec<Column>, MissingReport), String> {
+        let mut report = MissingReport {
+            rows_read: n_rows,
+            rows_dropped: 0,
+            columns: Vec::new(),
+        };
+
+        // --- Rows to drop, errors on the index or on any other column
+        let mut keep = vec![true; n_rows];
+
+        if let Some((name, values)) = &index {
+            let policy = self.policy(name);
+            let mut missing = 0;
+
+            for (row, value) in values.iter().enumerate() {
+                if value.is_none() {
+                    match policy {
+                        MissingPolicy::DropRow => keep[row] = false,
+                        MissingPolicy::Error => {
+                            return Err(missing_error(name, row));
+                        }
+                        _ => {
+                            return Err(format!(
+                            "index column {} only supports Error and DropRow policies",
+                            name
+                        ))
+                        }
+                    }
+                    missing += 1;
+                }
+            }
+
+            report.columns.push(ColumnReport {
+                name: name.to_string(),
+                policy: policy.clone(),
+                cells: missing,
+            });
+        }
+
+        let mut dropping = Vec::with_capacity(columns.len());
+
+        for (name, values) in columns.iter() {
+            let policy = self.policy(name);
+            dropping.push(values.iter().filter(|value| value.is_none()).count());
+
+            for (row, value) in values.iter().enumerate() {
+                if value.is_none() {
+                    match policy {
+                        MissingPolicy::DropRow => keep[row] = false,
+                        MissingPolicy::Error => return Err(missing_error(name, row)),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        report.rows_dropped = keep.iter().filter(|k| !**k).count();
+
+        // --- Fill the values of the rows kept
+        let index = match index {
+            Some((_, values)) => values
+                .into_iter()
+                .zip(keep.iter())
+                .filter_map(|(value, keep)| if *keep { value } else { None })
+                .collect(),
+            None => (0..n_rows as u64)
+                .filter(|row| keep[*row as usize])
+                .collect(),
+        };
+
+        let rows: Vec<usize> = (0..n_rows).filter(|row| keep[*row]).collect();
+        let mut filled = Vec::with_capacity(columns.len());
+
+        for ((name, values), dropping) in columns.into_iter().zip(dropping) {
+            let policy = self.policy(&name);
+            let kept: Vec<Option<f64>> = values
+                .into_iter()
+                .zip(keep.iter())
+                .filter(|(_, keep)| **keep)
+                .map(|(value, _)| value)
+                .collect();
+
+            // Dropped cells are the ones found before dropping the rows
+            let cells = match policy {
+                MissingPolicy::DropRow => dropping,
+                _ => kept.iter().filter(|value| value.is_none()).count(),
+            };
+
+            let values = fill(&name, kept, &rows, policy)?;
+
+            report.columns.push(ColumnReport {
+                name: name.clone(),
+                policy: policy.clone(),
+                cells,
+            });
+            filled.push(Column::new(&name, values));
+        }
+
+        Ok((index, filled, report))
+    }
+}
+
+fn missing_error(name: &str, row: usize) -> String {
+    format!("missing value in column {} at row {}", name, row + 1)
+}
+
+/// Fills the missing values of a column, once the rows have been dropped.
+/// `rows` are the positions in the file of the rows kept.
+fn fill(
+    name: &str,
+    values: Vec<Option<f64>>,
+    rows: &[usize],
+    policy: &MissingPolicy,
+) -> Result<Vec<f64>, String> {
+    match policy {
+        MissingPolicy::Error | MissingPolicy::DropRow => Ok(values
+            .into_iter()
+            .map(|value| value.unwrap_or(f64::NAN))
+            .collect()),
+
+        MissingPolicy::Constant(constant) => Ok(values
+            .into_iter()
+            .map(|value| value.unwrap_or(*constant))
+            .collect()),
+
+        MissingPolicy::Mean => {
+            let present: Vec<f64> = values.iter().flatten().copied().collect();
+            if present.is_empty() && !values.is_empty() {
+                return Err(format!("column {} has no values to compute a mean", name));
+            }
+            let mean = present.iter().sum::<f64>() / present.len() as f64;
+
+            Ok(values
+                .into_iter()
+                .map(|value| value.unwrap_or(mean))
+                .collect())
+        }
+
+        MissingPolicy::ForwardFill => {
+            let mut last: Option<f64> = None;
+            values
+                .into_iter()
+                .enumerate()
+                .map(|(i, value)| {
+                    last = value.or(last);
+                    last.ok_or(format!(
+                        "nothing to forward-fill in column {} at row {}",
+                        name,
+                        rows[i] + 1
+                    ))
+                })
+                .collect()
+        }
+    }
+}
+
+// ----------------------------------------------------------------- MISSING REPORT -- //
+// ----------------------------------------------------------------- -------------- -- //
+
+/// Number of missing cells found in one column, and the policy applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnReport {
+    pub name: String,
+    pub policy: MissingPolicy,
+    pub cells: usize,
+}
+
+/// What the missing values policies did while loading a file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingReport {
+    pub rows_read: usize,
+    pub rows_dropped: usize,
+    pub columns: Vec<ColumnReport>,
+}
+
+impl MissingReport {
+    /// Total cells touched by policies of the same kind as `policy`.
+    pub fn cells(&self, policy: &MissingPolicy) -> usize {
+        self.columns
+            .iter()
+            .filter(|column| column.policy.same_kind(policy))
+            .map(|column| column.cells)
+            .sum()
+    }
+
+    /// Whether no missing value was found at all.
+    pub fn is_clean(&self) -> bool {
+        self.columns.iter().all(|column| column.cells == 0)
+    }
+}
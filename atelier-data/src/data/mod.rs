@@ -1,31 +1,97 @@
 /// Data
-use crate::orderbooks::Orderbook;
+use crate::{
+    data::missing::{MissingReport, MissingValues},
+    orderbooks::Orderbook,
+};
 use csv::{Reader, ReaderBuilder, Writer};
+use serde::{de::DeserializeOwned, Serialize};
 use std::{
     error::Error,
     fs,
     io::{BufReader, Write},
+    path::Path,
 };
 use tch::{Kind, Tensor};
 use toml;
 
-pub enum Transformation {
-    Standarize,
-    Scale,
+pub mod bars;
+pub mod columnar;
+pub mod depth;
+pub mod loader;
+pub mod lobster;
+pub mod missing;
+pub mod ndjson;
+pub mod resample;
+pub mod scalers;
+pub mod snapshots;
+pub mod splits;
+pub mod trades;
+pub mod wide_csv;
+pub mod windows;
+
+// ------------------------------------------------------------------------- COLUMN -- //
+// ------------------------------------------------------------------------- ------ -- //
+
+/// A named column of values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    pub name: String,
+    pub values: Vec<f64>,
+}
+
+impl Column {
+    pub fn new(name: &str, values: Vec<f64>) -> Self {
+        Column {
+            name: name.to_string(),
+            values,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
 }
 
+// ------------------------------------------------------------------------ DATASET -- //
+// ------------------------------------------------------------------------ ------- -- //
+
+/// Named, column-major Dataset.
+///
+/// Each feature is stored as a `Column`, all of them with one value per
+/// row. Rows are identified by the `index`, usually the timestamp of the
+/// `Orderbook` the row was computed from.
+///
+/// There can be several named targets (e.g. the same label over different
+/// horizons), `selected_target` is the one used when building tensors and
+/// samples, the first one unless changed with `set_target`.
+///
+/// `lookback` is the number of past rows each row of features uses (e.g.
+/// through lags or rolling windows), and `horizon` the number of rows ahead
+/// the target is taken from. Both are 0 until the Dataset is built with the
+/// utilities in `data::windows`.
 #[derive(Debug, Clone)]
 pub struct Dataset {
-    pub index: Vec<u32>,
-    pub features: Vec<Vec<f64>>,
-    pub target: Vec<f64>,
+    pub index: Vec<u64>,
+    pub features: Vec<Column>,
+    pub targets: Vec<Column>,
+    pub selected_target: usize,
+    pub lookback: usize,
+    pub horizon: usize,
 }
 
 #[derive(Debug)]
 pub struct DatasetBuilder {
-    index: Option<Vec<u32>>,
+    index: Option<Vec<u64>>,
     features: Option<Vec<Vec<f64>>>,
+    columns: Option<Vec<Column>>,
+    feature_names: Option<Vec<String>>,
     target: Option<Vec<f64>>,
+    target_name: Option<String>,
+    targets: Option<Vec<Column>>,
     auto_index: bool,
 }
 
@@ -34,78 +100,127 @@ impl DatasetBuilder {
         DatasetBuilder {
             index: None,
             features: None,
+            columns: None,
+            feature_names: None,
             target: None,
+            target_name: None,
+            targets: None,
             auto_index: true,
         }
     }
 
-    pub fn index(mut self, index: Vec<u32>) -> Self {
+    pub fn index(mut self, index: Vec<u64>) -> Self {
         self.index = Some(index);
         self
     }
 
+    /// Features given by rows, one vector of feature values per sample, as
+    /// produced by `compute_features`.
     pub fn features(mut self, features: Vec<Vec<f64>>) -> Self {
         self.features = Some(features);
         self
     }
 
+    /// Features given by (already named) columns.
+    pub fn columns(mut self, columns: Vec<Column>) -> Self {
+        self.columns = Some(columns);
+        self
+    }
+
+    /// Names for the features given by rows, `feature_{i}` if not provided.
+    pub fn feature_names(mut self, feature_names: Vec<String>) -> Self {
+        self.feature_names = Some(feature_names);
+        self
+    }
+
     pub fn target(mut self, target: Vec<f64>) -> Self {
         self.target = Some(target);
         self
     }
 
+    /// Name of the target, `target` if not provided.
+    pub fn target_name(mut self, target_name: &str) -> Self {
+        self.target_name = Some(target_name.to_string());
+        self
+    }
+
+    /// Several (already named) targets, the first one is selected.
+    pub fn targets(mut self, targets: Vec<Column>) -> Self {
+        self.targets = Some(targets);
+        self
+    }
+
     pub fn disable_auto_index(mut self) -> Self {
         self.auto_index = false;
         self
     }
 
     pub fn build(self) -> Result<Dataset, String> {
-        let features = self.features.ok_or("Missing features")?;
-        let target = self.target.ok_or("Missing target")?;
+        let targets = match (self.targets, self.target) {
+            (Some(_), Some(_)) => {
+                return Err("target given both as a single one and as targets".to_string())
+            }
+            (Some(targets), None) => targets,
+            (None, Some(target)) => {
+                let target_name = self.target_name.unwrap_or("target".to_string());
+                vec![Column::new(&target_name, target)]
+            }
+            (None, None) => return Err("Missing target".to_string()),
+        };
 
-        // Validate features and target have the same length
+        let n_rows = targets.first().ok_or("Missing target")?.len();
 
-        if features.len() != target.len() {
-            return Err(format!(
-                "features and target length mismatch: {:?} vs {:?}",
-                features.len(),
-                target.len()
-            ));
+        let columns = match (self.columns, self.features) {
+            (Some(_), Some(_)) => {
+                return Err("features given both by rows and by columns".to_string())
+            }
+            (Some(columns), None) => columns,
+            (None, Some(features)) => rows_to_columns(features, self.feature_names)?,
+            (None, None) => return Err("Missing features".to_string()),
+        };
+
+        // Validate all columns have the same length as the first target
+
+        for column in columns.iter().chain(targets.iter()) {
+            if column.len() != n_rows {
+                return Err(format!(
+                    "features and target length mismatch: {:?} has {:?} vs {:?}",
+                    column.name,
+                    column.len(),
+                    n_rows
+                ));
+            }
         }
 
-        // Validate all feature vectors have the same length
+        // Validate feature and target names are unique
 
-        if !features.is_empty() {
-            // First feature is the expectation criteria
-            let expected_feature_len = features[0].len();
+        for (i, column) in columns.iter().enumerate() {
+            if columns[..i].iter().any(|c| c.name == column.name) {
+                return Err(format!("duplicated feature name {:?}", column.name));
+            }
+        }
 
-            for (i, feature_vec) in features.iter().enumerate() {
-                if feature_vec.len() != expected_feature_len {
-                    return Err(format!(
-                        "feature vector at index {:?} has length {:?}, expected {:?}",
-                        i,
-                        feature_vec.len(),
-                        expected_feature_len
-                    ));
-                }
+        for (i, column) in targets.iter().enumerate() {
+            if targets[..i].iter().any(|c| c.name == column.name) {
+                return Err(format!("duplicated target name {:?}", column.name));
             }
         }
 
         // If index is not provided, create one
         let index = match self.index {
             Some(idx) => {
-                if idx.len() != features.len() {
+                if idx.len() != n_rows {
                     return Err(format!(
                         "Index length {:?} doesn't match data length {:?}",
                         idx.len(),
-                        features.len()
+                        n_rows
                     ));
                 }
                 idx
             }
             None => {
                 if self.auto_index {
-                    (0..features.len() as u32).collect()
+                    (0..n_rows as u64).collect()
                 } else {
                     Vec::new()
                 }
@@ -114,117 +229,104 @@ impl DatasetBuilder {
 
         Ok(Dataset {
             index,
-            features,
-            target,
+            features: columns,
+            targets,
+            selected_target: 0,
+            lookback: 0,
+            horizon: 0,
         })
     }
 }
 
-impl Dataset {
-    pub fn new() -> DatasetBuilder {
-        DatasetBuilder::new()
-    }
-
-    pub fn transform(&mut self, transformation: Transformation) -> Vec<(f64, f64)> {
-        let n_features = self.features[0].len();
-        let epsilon = 1e-8;
-
-        match transformation {
-            Transformation::Standarize => {
-                // Step 1: Transpose the data to get feature columns
-                let transposed: Vec<Vec<f64>> = (0..n_features)
-                    .map(|i| self.features.iter().map(|row| row[i]).collect())
-                    .collect();
-
-                // Step 2: Compute mean and std dev for each feature
-                let stats: Vec<(f64, f64)> = transposed
-                    .iter()
-                    .map(|feature_col| {
-                        let n = feature_col.len() as f64;
-                        let mean: f64 = feature_col.iter().sum::<f64>() / n;
-
-                        let variance =
-                            feature_col.iter().map(|x| (x - mean).powi(2)).sum::<f64>()
-                                / n;
+/// Transposes row-major features into named columns.
+fn rows_to_columns(
+    rows: Vec<Vec<f64>>,
+    names: Option<Vec<String>>,
+) -> Result<Vec<Column>, String> {
+    // First row is the expectation criteria
+    let n_features = match (&names, rows.first()) {
+        (Some(names), _) => names.len(),
+        (None, Some(row)) => row.len(),
+        (None, None) => 0,
+    };
 
-                        let std_dev = variance.sqrt().max(epsilon);
+    // Validate all feature vectors have the same length
 
-                        (mean, std_dev)
-                    })
-                    .collect();
+    for (i, feature_vec) in rows.iter().enumerate() {
+        if feature_vec.len() != n_features {
+            return Err(format!(
+                "feature vector at index {:?} has length {:?}, expected {:?}",
+                i,
+                feature_vec.len(),
+                n_features
+            ));
+        }
+    }
 
-                // Step 3: Apply normalization
-                self.features = self
-                    .features
-                    .iter()
-                    .map(|row| {
-                        row.iter()
-                            .enumerate()
-                            .map(|(i, &x)| {
-                                let (mean, std_dev) = &stats[i];
-                                (x - mean) / std_dev
-                            })
-                            .collect()
-                    })
-                    .collect();
-                stats
-            }
+    let names = names
+        .unwrap_or_else(|| (0..n_features).map(|i| format!("feature_{}", i)).collect());
 
-            Transformation::Scale => {
-                // Step 1: Transpose the data to get feature columns
-                let transposed: Vec<Vec<f64>> = (0..n_features)
-                    .map(|i| self.features.iter().map(|row| row[i]).collect())
-                    .collect();
+    Ok(names
+        .iter()
+        .enumerate()
+        .map(|(j, name)| Column::new(name, rows.iter().map(|row| row[j]).collect()))
+        .collect())
+}
 
-                // Step 2: Compute the max for each feature
-                let maxs: Vec<(f64, f64)> = transposed
-                    .iter()
-                    .map(|feature_col| {
-                        let max: f64 = feature_col
-                            .iter()
-                            .cloned()
-                            .fold(f64::NEG_INFINITY, f64::max)
-                            .max(epsilon);
-                        (0.0, max)
-                    })
-                    .collect();
-
-                // Step 3: Divide every element by the max value for all features
-                self.features = self
-                    .features
-                    .iter()
-                    .map(|row| {
-                        row.iter()
-                            .enumerate()
-                            .map(|(i, &x)| {
-                                let (_mean, max) = maxs[i];
-                                x / max
-                            })
-                            .collect()
-                    })
-                    .collect();
-                maxs
-            }
-        }
+impl Dataset {
+    pub fn new() -> DatasetBuilder {
+        DatasetBuilder::new()
     }
 
+    /// Loads a Dataset from a CSV file, failing on any missing value.
+    ///
+    /// # Parameters
+    ///
+    /// - `file_route`: Route of the CSV file.
+    /// - `header`: Whether the first row holds the column names.
+    /// - `column_types`: Type of each column, 0 index, 1 feature, 2 target.
+    /// - `target_column`: Position of the selected target column, which is
+    ///   a target regardless of `column_types`. The last one by default.
     pub fn from_csv(
         file_route: &str,
         header: bool,
         column_types: Option<Vec<u32>>,
         target_column: Option<u32>,
     ) -> Result<Self, Box<dyn Error>> {
-        let file = fs::File::open(file_route)?;
-        let mut rdr = ReaderBuilder::new().has_headers(header).from_reader(file);
+        let (dataset, _) = Self::from_csv_with(
+            file_route,
+            header,
+            column_types,
+            target_column,
+            &MissingValues::default(),
+        )?;
+        Ok(dataset)
+    }
+
+    /// Loads a Dataset from a CSV file, resolving empty, unparseable or NaN
+    /// fields with the given per column `missing` policies. Returns the
+    /// Dataset along with a report of the cells each policy touched.
+    pub fn from_csv_with(
+        file_route: &str,
+        header: bool,
+        column_types: Option<Vec<u32>>,
+        target_column: Option<u32>,
+        missing: &MissingValues,
+    ) -> Result<(Self, MissingReport), Box<dyn Error>> {
+        let mut rdr = ReaderBuilder::new()
+            .has_headers(header)
+            .from_path(file_route)?;
+
+        let records = rdr.records().collect::<Result<Vec<_>, _>>()?;
 
-        // Get column count from header or first row
-        let col_count = if header {
-            rdr.headers()?.len()
+        // Get column names from header, or count from the first row
+        let col_names: Vec<String> = if header {
+            rdr.headers()?.iter().map(|name| name.to_string()).collect()
         } else {
-            let mut records = rdr.records();
-            let first = records.next().ok_or("CSV file is empty")??;
-            first.len()
+            let first = records.first().ok_or("CSV file is empty")?;
+            (0..first.len()).map(|i| format!("column_{}", i)).collect()
         };
+        let col_count = col_names.len();
 
         // Determine column types
         let col_types: Vec<u32> = match column_types {
@@ -241,77 +343,83 @@ impl Dataset {
         };
 
         // Determine target column (Default is the last one)
-        let target_col = target_column.unwrap_or((col_count - 1) as u32);
+        let target_col = target_column.unwrap_or((col_count - 1) as u32) as usize;
 
-        let mut index = Vec::new();
-        let mut features = Vec::new();
-        let mut target = Vec::new();
-
-        let mut rdr = ReaderBuilder::new()
-            .has_headers(header)
-            .from_path(file_route)?;
+        let col_type = |i: usize| {
+            if i == target_col {
+                2
+            } else {
+                col_types.get(i).copied().unwrap_or(1)
+            }
+        };
 
-        for result in rdr.records() {
-            let record = result?;
-            let mut row_features = Vec::new();
-            let mut row_index: Option<u32> = None;
-            let mut row_target: Option<f64> = None;
+        // Raw cells, None when missing
+        let parse = |i: usize| -> Vec<Option<f64>> {
+            records
+                .iter()
+                .map(|record| {
+                    record
+                        .get(i)
+                        .and_then(|field| field.trim().parse::<f64>().ok())
+                        .filter(|value| !value.is_nan())
+                })
+                .collect()
+        };
 
-            for (i, field) in record.iter().enumerate() {
-                let col_type = if i == target_col as usize {
-                    2
-                } else {
-                    col_types.get(i).copied().unwrap_or(1)
-                };
-                match col_type {
-                    0 => {
-                        // Index column
-                        let idx: u32 = field.parse().unwrap_or(0);
-                        row_index = Some(idx);
-                    }
-                    1 => {
-                        // Feature column
-                        let val: f64 = field.parse().unwrap_or(f64::NAN);
-                        row_features.push(val);
-                    }
-                    2 => {
-                        // Target column
-                        let val: f64 = field.parse().unwrap_or(f64::NAN);
-                        row_target = Some(val);
-                    }
-                    _ => {}
-                }
-            }
+        let index = (0..col_count).find(|i| col_type(*i) == 0).map(|i| {
+            let values = records
+                .iter()
+                .map(|record| record.get(i).and_then(|field| field.trim().parse().ok()))
+                .collect();
+            (col_names[i].as_str(), values)
+        });
+
+        // Features first, the targets last
+        let feature_cols: Vec<usize> =
+            (0..col_count).filter(|i| col_type(*i) == 1).collect();
+        let target_cols: Vec<usize> =
+            (0..col_count).filter(|i| col_type(*i) == 2).collect();
+        let selected_target = target_cols
+            .iter()
+            .position(|i| *i == target_col)
+            .ok_or("Missing target column")?;
 
-            // If no index column, use row number
-            index.push(row_index.unwrap_or(index.len() as u32));
-            features.push(row_features);
-            target.push(row_target.unwrap_or(f64::NAN));
-        }
+        let columns = feature_cols
+            .iter()
+            .chain(target_cols.iter())
+            .map(|i| (col_names[*i].clone(), parse(*i)))
+            .collect();
 
-        Ok(Dataset {
-            index,
-            features,
-            target,
-        })
+        let (index, mut features, report) =
+            missing.resolve(index, columns, records.len())?;
+        let targets = features.split_off(feature_cols.len());
+
+        Ok((
+            Dataset {
+                index,
+                features,
+                targets,
+                selected_target,
+                lookback: 0,
+                horizon: 0,
+            },
+            report,
+        ))
     }
 
     pub fn from_vec_to_tensor(self) -> (Tensor, Tensor) {
-        let d_features = self.features;
-        let d_targets = self.target;
+        let num_samples = self.len() as i64;
+        let num_features = self.feature_count() as i64;
 
-        let num_samples = d_features.len() as i64;
-        let num_features = d_features[0].len() as i64;
-
-        // Convert features to 2D tensor
-        let flat_features: Vec<f64> = d_features.into_iter().flatten().clone().collect();
+        // Convert features to 2D tensor, row-major as expected by the models
+        let flat_features: Vec<f64> = (0..self.len()).flat_map(|i| self.row(i)).collect();
         let features_tensor = Tensor::from_slice(&flat_features)
-            .reshape(&[num_samples, num_features])
+            .reshape([num_samples, num_features])
             .to_kind(Kind::Float);
 
-        // Convert targets to 2D tensor
-        let targets_tensor = Tensor::from_slice(&d_targets)
-            .reshape(&[num_samples])
+        // Convert targets to 1D tensor
+        let targets_tensor = Tensor::from_slice(&self.target().values)
+            .reshape([num_samples])
             .to_kind(Kind::Float);
 
         (features_tensor, targets_tensor)
@@ -326,54 +434,135 @@ impl Dataset {
     }
 
     pub fn get_pairs(&self) -> Vec<(Vec<f64>, f64)> {
-        self.features
-            .iter()
-            .zip(self.target.iter())
-            .map(|(features, &target)| (features.clone(), target))
-            .collect()
-    }
-
-    pub fn get_parirs_ref(&self) -> Vec<(&Vec<f64>, f64)> {
-        self.features
-            .iter()
-            .zip(self.target.iter())
-            .map(|(features, &target)| (features, target))
+        (0..self.len())
+            .map(|i| (self.row(i), self.target().values[i]))
             .collect()
     }
 
     pub fn len(&self) -> usize {
-        self.features.len()
+        self.target().len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.features.is_empty()
+        self.target().is_empty()
     }
 
     pub fn feature_count(&self) -> usize {
-        self.features.first().map_or(0, |f| f.len())
+        self.features.len()
+    }
+
+    pub fn feature_names(&self) -> Vec<&str> {
+        self.features.iter().map(|c| c.name.as_str()).collect()
     }
 
-    pub fn get_features(&self) -> &Vec<Vec<f64>> {
+    pub fn get_features(&self) -> &Vec<Column> {
         &self.features
     }
 
     pub fn get_target(&self) -> &Vec<f64> {
-        &self.target
+        &self.target().values
+    }
+
+    /// The selected target.
+    pub fn target(&self) -> &Column {
+        &self.targets[self.selected_target]
     }
 
-    pub fn get_index(&self) -> &Vec<u32> {
+    pub fn target_names(&self) -> Vec<&str> {
+        self.targets.iter().map(|c| c.name.as_str()).collect()
+    }
+
+    /// Selects the target with the given name, used from then on to build
+    /// tensors and samples.
+    pub fn set_target(&mut self, name: &str) -> Result<(), String> {
+        self.selected_target =
+            self.targets
+                .iter()
+                .position(|c| c.name == name)
+                .ok_or(format!(
+                    "Unknown target: {}, the ones available are: {:?}",
+                    name,
+                    self.target_names()
+                ))?;
+        Ok(())
+    }
+
+    pub fn get_index(&self) -> &Vec<u64> {
         &self.index
     }
 
-    pub fn get_sample(&self, idx: usize) -> Option<(&Vec<f64>, f64)> {
+    /// Position of the feature column with the given name.
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.features.iter().position(|c| c.name == name)
+    }
+
+    /// The feature column with the given name.
+    pub fn column(&self, name: &str) -> Option<&Column> {
+        self.features.iter().find(|c| c.name == name)
+    }
+
+    /// New Dataset with only the given feature columns, in the given order.
+    pub fn select(&self, names: &[&str]) -> Result<Dataset, String> {
+        let features = names
+            .iter()
+            .map(|name| {
+                self.column(name).cloned().ok_or(format!(
+                    "Unknown feature: {}, the ones available are: {:?}",
+                    name,
+                    self.feature_names()
+                ))
+            })
+            .collect::<Result<Vec<Column>, String>>()?;
+
+        Ok(Dataset {
+            index: self.index.clone(),
+            features,
+            targets: self.targets.clone(),
+            selected_target: self.selected_target,
+            lookback: self.lookback,
+            horizon: self.horizon,
+        })
+    }
+
+    /// New Dataset without the given feature columns.
+    pub fn drop(&self, names: &[&str]) -> Result<Dataset, String> {
+        if let Some(name) = names.iter().find(|name| self.column(name).is_none()) {
+            return Err(format!(
+                "Unknown feature: {}, the ones available are: {:?}",
+                name,
+                self.feature_names()
+            ));
+        }
+
+        Ok(Dataset {
+            index: self.index.clone(),
+            features: self
+                .features
+                .iter()
+                .filter(|c| !names.contains(&c.name.as_str()))
+                .cloned()
+                .collect(),
+            targets: self.targets.clone(),
+            selected_target: self.selected_target,
+            lookback: self.lookback,
+            horizon: self.horizon,
+        })
+    }
+
+    /// Feature values of the row at position `idx`.
+    pub fn row(&self, idx: usize) -> Vec<f64> {
+        self.features.iter().map(|c| c.values[idx]).collect()
+    }
+
+    pub fn get_sample(&self, idx: usize) -> Option<(Vec<f64>, f64)> {
         if idx < self.len() {
-            Some((&self.features[idx], self.target[idx]))
+            Some((self.row(idx), self.target().values[idx]))
         } else {
             None
         }
     }
 
-    pub fn get_sample_by_index(&self, index_value: u32) -> Option<(&Vec<f64>, f64)> {
+    pub fn get_sample_by_index(&self, index_value: u64) -> Option<(Vec<f64>, f64)> {
         self.index
             .iter()
             .position(|&idx| idx == index_value)
@@ -381,55 +570,57 @@ impl Dataset {
     }
 
     pub fn shift_features(&self) -> Dataset {
-        if self.features.len() < 2 {
+        if self.len() < 2 {
             return Dataset {
                 index: Vec::new(),
-                features: Vec::new(),
-                target: Vec::new(),
+                features: self
+                    .features
+                    .iter()
+                    .map(|c| Column::new(&c.name, Vec::new()))
+                    .collect(),
+                targets: self
+                    .targets
+                    .iter()
+                    .map(|c| Column::new(&c.name, Vec::new()))
+                    .collect(),
+                selected_target: self.selected_target,
+                lookback: self.lookback,
+                horizon: self.horizon,
             };
         }
 
-        // Shift features forward: drop first feature vector
-        let shifted_features = self.features[1..].to_vec();
+        // Shift features forward: drop first feature value of each column
+        let shifted_features = self
+            .features
+            .iter()
+            .map(|c| Column::new(&c.name, c.values[1..].to_vec()))
+            .collect();
 
         // Keep targets but drop the last one to align with shifted features
-        let aligned_targets = self.target[..self.target.len() - 1].to_vec();
+        let aligned_targets = self
+            .targets
+            .iter()
+            .map(|c| Column::new(&c.name, c.values[..self.len() - 1].to_vec()))
+            .collect();
 
-        // Create new index for the aligned data
-        let shifted_index = (0..shifted_features.len() as u32).collect();
+        // The index follows the shifted features
+        let shifted_index = if self.index.len() == self.len() {
+            self.index[1..].to_vec()
+        } else {
+            Vec::new()
+        };
 
         Dataset {
             index: shifted_index,
             features: shifted_features,
-            target: aligned_targets,
+            targets: aligned_targets,
+            selected_target: self.selected_target,
+            lookback: self.lookback,
+            horizon: self.horizon,
         }
     }
 }
 
-/// Data transformation
-pub fn transform(data: &Tensor, operation: Transformation) -> Tensor {
-    // Compensation error for numerical stability
-    let epsilon = 1e-8;
-
-    // Match the selected operation
-    let transformed = match operation {
-        // new_x = (x - mean(x)) / std(x)
-        Transformation::Standarize => {
-            let xs_1 = data - data.mean(Kind::Float);
-            let xs_2 = data.std(true) + epsilon;
-
-            (xs_1 / xs_2).to_kind(Kind::Float)
-        }
-
-        // new_x = x / max(x)
-        Transformation::Scale => {
-            let max_data = data.max();
-            data / max_data
-        }
-    };
-    transformed
-}
-
 /// Truncate decimals on a f64
 pub fn truncate_to_decimal(num: f64, decimal_places: u32) -> f64 {
     let multiplier = 10_f64.powi(decimal_places as i32);
@@ -444,20 +635,69 @@ pub fn load_from_toml(file_route: &str) -> Result<(), Box<dyn Error>> {
 }
 
 /// Load from JSON file
-pub fn load_from_json(file_route: &str) -> Result<Vec<Orderbook>, Box<dyn Error>> {
+///
+/// Works for any book representation, e.g. `Vec<Orderbook>` or
+/// `Vec<L2Orderbook>`, the type is given by the caller.
+pub fn load_from_json<B: DeserializeOwned>(
+    file_route: &str,
+) -> Result<Vec<B>, Box<dyn Error>> {
     let file = fs::File::open(file_route)?;
     let reader = BufReader::new(file);
-    let v_orderbook: Vec<Orderbook> = serde_json::from_reader(reader)?;
+    let v_orderbook: Vec<B> = serde_json::from_reader(reader)?;
     Ok(v_orderbook)
 }
 
 /// Write to JSON file
-pub fn write_to_json(ob_data: &Vec<Orderbook>, file_route: &str) {
+pub fn write_to_json<B: Serialize>(ob_data: &Vec<B>, file_route: &str) {
     let ob_json = serde_json::to_string(&ob_data).unwrap();
     let mut file = fs::File::create(&file_route).unwrap();
     file.write_all(ob_json.as_bytes()).unwrap();
 }
 
+/// Extension giving the format of a file, without its compression suffix
+///
+/// Only `.ndjson` and `.jsonl` files can be compressed, with `.gz`, `.zst`
+/// or `.zstd`, other compressed formats are an error as their loaders read
+/// plain files.
+pub fn file_format(file_route: &str) -> Result<&str, String> {
+    let (route, compressed) = match [".gz", ".zst", ".zstd"]
+        .iter()
+        .find_map(|suffix| file_route.strip_suffix(suffix))
+    {
+        Some(route) => (route, true),
+        None => (file_route, false),
+    };
+
+    let extension = Path::new(route)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+    if compressed && !extension.is_empty() && !matches!(extension, "ndjson" | "jsonl") {
+        return Err(format!(
+            "compressed {} is not supported: {}",
+            extension, file_route
+        ));
+    }
+    Ok(extension)
+}
+
+/// Load order books from a file, with the format given by its extension
+///
+/// - `.json`: a single JSON array, see `load_from_json`.
+/// - `.ndjson` or `.jsonl`, optionally followed by `.gz` or `.zst`: one book
+///   per line, see `ndjson::load_from_ndjson`.
+/// - `.parquet`: flattened levels, see `columnar::load_orderbooks_from_parquet`.
+/// - `.atb`: binary snapshots, see `snapshots::load_from_snapshots`.
+pub fn load_orderbooks(file_route: &str) -> Result<Vec<Orderbook>, Box<dyn Error>> {
+    match file_format(file_route)? {
+        "json" => load_from_json(file_route),
+        "ndjson" | "jsonl" => ndjson::load_from_ndjson(file_route),
+        "parquet" => columnar::load_orderbooks_from_parquet(file_route, None),
+        snapshots::EXTENSION => snapshots::load_from_snapshots(file_route),
+        _ => Err(format!("Unknown order books file format: {}", file_route).into()),
+    }
+}
+
 /// Load from CSV
 pub fn load_from_csv(file_route: &str) -> Result<Vec<Vec<f64>>, Box<dyn Error>> {
     let mut rdr = Reader::from_path(file_route)?;
@@ -480,36 +720,29 @@ pub fn load_from_csv(file_route: &str) -> Result<Vec<Vec<f64>>, Box<dyn Error>>
 pub fn write_to_csv(data: &Dataset, file_route: &str) {
     let mut wtr = Writer::from_path(file_route).unwrap();
 
-    // Write the header
-    // Header should be based on the number of features, not the index
-    if !data.features.is_empty() {
-        let mut header = vec!["index".to_string()];
+    // Write the header: index, feature names, target names
+    let mut header = vec!["index".to_string()];
+    header.extend(data.features.iter().map(|c| c.name.clone()));
+    header.extend(data.targets.iter().map(|c| c.name.clone()));
 
-        // Add feature column names based on the number of features per sample
-        for i in 0..data.features[0].len() {
-            header.push(format!("feature_{}", i));
-        }
-
-        // Add target column
-        header.push("target".to_string());
-
-        wtr.write_record(&header).unwrap();
-    }
+    wtr.write_record(&header).unwrap();
 
     // Write the data rows
-    for i in 0..data.features.len() {
+    for i in 0..data.len() {
         let mut csv_row = Vec::new();
 
         // Add index
-        csv_row.push(data.index[i].to_string());
+        csv_row.push(data.index.get(i).unwrap_or(&(i as u64)).to_string());
 
         // Add all features for this sample
-        for feature_value in &data.features[i] {
-            csv_row.push(feature_value.to_string());
+        for column in &data.features {
+            csv_row.push(column.values[i].to_string());
         }
 
-        // Add target value
-        csv_row.push(data.target[i].to_string());
+        // Add target values
+        for column in &data.targets {
+            csv_row.push(column.values[i].to_string());
+        }
 
         wtr.write_record(&csv_row).unwrap();
     }
@@ -0,0 +1,174 @@
+/// Streaming line-delimited JSON (NDJSON) I/O for order book sequences
+use flate2::{read::MultiGzDecoder, write::GzEncoder};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    error::Error,
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Lines, Write},
+    marker::PhantomData,
+    path::Path,
+};
+
+// -------------------------------------------------------------------- COMPRESSION -- //
+// -------------------------------------------------------------------- ----------- -- //
+
+/// Compression of a file, detected from its extension.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Compression {
+    None,
+    /// `.gz` files.
+    Gzip,
+    /// `.zst` or `.zstd` files.
+    Zstd,
+}
+
+impl Compression {
+    pub fn from_route(file_route: &str) -> Self {
+        match Path::new(file_route).extension().and_then(|e| e.to_str()) {
+            Some("gz") => Compression::Gzip,
+            Some("zst") | Some("zstd") => Compression::Zstd,
+            _ => Compression::None,
+        }
+    }
+}
+
+/// Opens a file for reading, decompressing it according to its extension.
+pub fn open_reader(file_route: &str) -> Result<Box<dyn BufRead>, Box<dyn Error>> {
+    let file = File::open(file_route)?;
+
+    Ok(match Compression::from_route(file_route) {
+        Compression::None => Box::new(BufReader::new(file)),
+        Compression::Gzip => Box::new(BufReader::new(MultiGzDecoder::new(file))),
+        Compression::Zstd => Box::new(BufReader::new(zstd::Decoder::new(file)?)),
+    })
+}
+
+// ----------------------------------------------------------------- NDJSON READER -- //
+// ----------------------------------------------------------------- ------------- -- //
+
+/// Reads one JSON value per line, one at a time.
+///
+/// Works for any book representation (e.g. `Orderbook` or `L2Orderbook`),
+/// the type is given by the caller. Empty lines are skipped, errors tell
+/// the line (starting at 1) they were found in.
+pub struct NdjsonReader<B> {
+    lines: Lines<Box<dyn BufRead>>,
+    line: usize,
+    book: PhantomData<B>,
+}
+
+impl<B: DeserializeOwned> NdjsonReader<B> {
+    /// Opens a, possibly compressed, NDJSON file.
+    pub fn open(file_route: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(NdjsonReader::from_reader(open_reader(file_route)?))
+    }
+
+    pub fn from_reader(reader: Box<dyn BufRead>) -> Self {
+        NdjsonReader {
+            lines: reader.lines(),
+            line: 0,
+            book: PhantomData,
+        }
+    }
+}
+
+impl<B: DeserializeOwned> Iterator for NdjsonReader<B> {
+    type Item = Result<B, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line += 1;
+
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e.into())),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            return Some(serde_json::from_str(&line).map_err(|e| {
+                format!("invalid JSON at line {}: {}", self.line, e).into()
+            }));
+        }
+    }
+}
+
+// ----------------------------------------------------------------- NDJSON WRITER -- //
+// ----------------------------------------------------------------- ------------- -- //
+
+enum Sink {
+    Plain(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+    Zstd(zstd::Encoder<'static, BufWriter<File>>),
+}
+
+impl Sink {
+    fn writer(&mut self) -> &mut dyn Write {
+        match self {
+            Sink::Plain(w) => w,
+            Sink::Gzip(w) => w,
+            Sink::Zstd(w) => w,
+        }
+    }
+}
+
+/// Writes one JSON value per line, compressed according to the extension
+/// of the file.
+///
+/// `finish` must be called once done, to flush the buffers and to write the
+/// end of the compressed stream.
+pub struct NdjsonWriter {
+    sink: Sink,
+}
+
+impl NdjsonWriter {
+    pub fn create(file_route: &str) -> Result<Self, Box<dyn Error>> {
+        let file = BufWriter::new(File::create(file_route)?);
+
+        let sink = match Compression::from_route(file_route) {
+            Compression::None => Sink::Plain(file),
+            Compression::Gzip => {
+                Sink::Gzip(GzEncoder::new(file, flate2::Compression::default()))
+            }
+            Compression::Zstd => Sink::Zstd(zstd::Encoder::new(file, 0)?),
+        };
+
+        Ok(NdjsonWriter { sink })
+    }
+
+    pub fn write<B: Serialize>(&mut self, book: &B) -> Result<(), Box<dyn Error>> {
+        let writer = self.sink.writer();
+        serde_json::to_writer(&mut *writer, book)?;
+        writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    pub fn finish(self) -> Result<(), Box<dyn Error>> {
+        match self.sink {
+            Sink::Plain(mut w) => w.flush()?,
+            Sink::Gzip(w) => w.finish()?.flush()?,
+            Sink::Zstd(w) => w.finish()?.flush()?,
+        }
+        Ok(())
+    }
+}
+
+/// Load from a, possibly compressed, NDJSON file
+pub fn load_from_ndjson<B: DeserializeOwned>(
+    file_route: &str,
+) -> Result<Vec<B>, Box<dyn Error>> {
+    NdjsonReader::open(file_route)?.collect()
+}
+
+/// Write to a, possibly compressed, NDJSON file
+pub fn write_to_ndjson<B: Serialize>(
+    ob_data: &[B],
+    file_route: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut writer = NdjsonWriter::create(file_route)?;
+    for book in ob_data {
+        writer.write(book)?;
+    }
+    writer.finish()
+}
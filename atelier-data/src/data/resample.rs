@@ -0,0 +1,195 @@
+/// Regular time grid resampling and as-of joins
+use crate::{orderbooks::Orderbook, views::BookView};
+
+// ---------------------------------------------------------------------- ASOF JOIN -- //
+// ---------------------------------------------------------------------- --------- -- //
+
+/// Index of the last `right` timestamp at, or before, each `left` one.
+///
+/// None when there is no such timestamp, or when it is older than
+/// `max_staleness` (left minus right timestamp greater than the limit).
+///
+/// # Parameters
+///
+/// - `left`: Timestamps to join to, sorted.
+/// - `right`: Timestamps to take the values from, sorted.
+/// - `max_staleness`: Oldest accepted right value, no limit if None.
+pub fn asof_indices(
+    left: &[u64],
+    right: &[u64],
+    max_staleness: Option<u64>,
+) -> Result<Vec<Option<usize>>, String> {
+    if !left.is_sorted() {
+        return Err("left timestamps are not sorted".to_string());
+    }
+    if !right.is_sorted() {
+        return Err("right timestamps are not sorted".to_string());
+    }
+
+    let mut next = 0;
+    Ok(left
+        .iter()
+        .map(|ts| {
+            while next < right.len() && right[next] <= *ts {
+                next += 1;
+            }
+            next.checked_sub(1)
+                .filter(|i| max_staleness.is_none_or(|limit| ts - right[*i] <= limit))
+        })
+        .collect())
+}
+
+/// Joins each book with the last value of a time series at, or before, its
+/// timestamp, e.g. the last trade or prediction.
+///
+/// # Parameters
+///
+/// - `books`: Order books sorted by timestamp.
+/// - `values`: Values sorted by timestamp.
+/// - `timestamp`: Timestamp of a value.
+/// - `max_staleness`: Oldest accepted value, no limit if None.
+pub fn asof_join<'a, B: BookView, T, F: Fn(&T) -> u64>(
+    books: &[B],
+    values: &'a [T],
+    timestamp: F,
+    max_staleness: Option<u64>,
+) -> Result<Vec<Option<&'a T>>, String> {
+    let left: Vec<u64> = books.iter().map(|book| book.timestamp()).collect();
+    let right: Vec<u64> = values.iter().map(timestamp).collect();
+
+    Ok(asof_indices(&left, &right, max_staleness)?
+        .into_iter()
+        .map(|i| i.map(|i| &values[i]))
+        .collect())
+}
+
+// ---------------------------------------------------------------------- RESAMPLER -- //
+// ---------------------------------------------------------------------- --------- -- //
+
+/// Resampling of a book series to a regular time grid.
+///
+/// Each grid timestamp takes the last book at, or before, it (last
+/// observation carried forward). The grid starts at the first multiple of
+/// `interval` at, or after, the first book and ends at the last book,
+/// unless `start` and `end` are given.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Resampler {
+    interval: u64,
+    start: Option<u64>,
+    end: Option<u64>,
+    max_staleness: Option<u64>,
+}
+
+impl Resampler {
+    pub fn new() -> ResamplerBuilder {
+        ResamplerBuilder::new()
+    }
+
+    /// Grid timestamps for a series of book timestamps.
+    pub fn grid(&self, timestamps: &[u64]) -> Vec<u64> {
+        let (Some(first), Some(last)) = (timestamps.first(), timestamps.last()) else {
+            return Vec::new();
+        };
+
+        let start = self
+            .start
+            .unwrap_or(first.div_ceil(self.interval) * self.interval);
+        let end = self.end.unwrap_or(*last);
+
+        (0..)
+            .map(|i| start + i * self.interval)
+            .take_while(|ts| *ts <= end)
+            .collect()
+    }
+
+    /// Grid timestamps with the index of the book carried to each of them,
+    /// None before the first book or when the last one is too old.
+    pub fn resample_indices<B: BookView>(
+        &self,
+        books: &[B],
+    ) -> Result<Vec<(u64, Option<usize>)>, String> {
+        let timestamps: Vec<u64> = books.iter().map(|book| book.timestamp()).collect();
+        let grid = self.grid(&timestamps);
+        let indices = asof_indices(&grid, &timestamps, self.max_staleness)?;
+
+        Ok(grid.into_iter().zip(indices).collect())
+    }
+
+    /// Books on the grid, with the grid timestamps.
+    ///
+    /// Each book keeps the id of the snapshot it was carried from, grid
+    /// timestamps without a book are left out.
+    pub fn resample(&self, books: &[Orderbook]) -> Result<Vec<Orderbook>, String> {
+        Ok(self
+            .resample_indices(books)?
+            .into_iter()
+            .filter_map(|(ts, i)| {
+                let mut book = books[i?].clone();
+                book.orderbook_ts = ts;
+                Some(book)
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug)]
+pub struct ResamplerBuilder {
+    interval: Option<u64>,
+    start: Option<u64>,
+    end: Option<u64>,
+    max_staleness: Option<u64>,
+}
+
+impl ResamplerBuilder {
+    pub fn new() -> Self {
+        ResamplerBuilder {
+            interval: None,
+            start: None,
+            end: None,
+            max_staleness: None,
+        }
+    }
+
+    /// Time between grid timestamps, in the units of the books (e.g.
+    /// 100_000 for 100 ms with microseconds).
+    pub fn interval(mut self, interval: u64) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    pub fn start(mut self, start: u64) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    pub fn end(mut self, end: u64) -> Self {
+        self.end = Some(end);
+        self
+    }
+
+    /// Oldest book that can be carried forward to a grid timestamp.
+    pub fn max_staleness(mut self, max_staleness: u64) -> Self {
+        self.max_staleness = Some(max_staleness);
+        self
+    }
+
+    pub fn build(self) -> Result<Resampler, &'static str> {
+        let interval = self.interval.ok_or("Missing interval")?;
+
+        if interval == 0 {
+            return Err("interval must be greater than 0");
+        }
+        if let (Some(start), Some(end)) = (self.start, self.end) {
+            if start > end {
+                return Err("start must not be after end");
+            }
+        }
+
+        Ok(Resampler {
+            interval,
+            start: self.start,
+            end: self.end,
+            max_staleness: self.max_staleness,
+        })
+    }
+}
@@ -0,0 +1,400 @@
+/// Fit/transform feature scalers
+use crate::data::{Column, Dataset};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{error::Error, fs};
+use tch::{Kind, Tensor};
+
+// ------------------------------------------------------------------------- SCALER -- //
+// ------------------------------------------------------------------------- ------ -- //
+
+/// Per column scaling of the features of a `Dataset`.
+///
+/// Statistics are computed once with `fit` (e.g. over the training split),
+/// and then applied as many times as needed with `transform` (e.g. over the
+/// test split, or at inference time). Columns are matched by name, so the
+/// order of the features does not need to be the same as when fitted.
+pub trait Scaler {
+    /// Computes the statistics of every feature column of `data`.
+    fn fit(&mut self, data: &Dataset) -> Result<(), String>;
+
+    /// Names of the fitted columns, empty if not fitted yet.
+    fn columns(&self) -> &[String];
+
+    /// Scales a single value of the fitted column at position `column`.
+    fn scale(&self, column: usize, value: f64) -> f64;
+
+    /// Reverts `scale` for a single value.
+    fn unscale(&self, column: usize, value: f64) -> f64;
+
+    fn is_fitted(&self) -> bool {
+        !self.columns().is_empty()
+    }
+
+    /// Scales the fitted columns of `data` in place.
+    fn transform(&self, data: &mut Dataset) -> Result<(), String> {
+        apply(self, data, |i, x| self.scale(i, x))
+    }
+
+    /// Reverts `transform` over the fitted columns of `data` in place.
+    fn inverse_transform(&self, data: &mut Dataset) -> Result<(), String> {
+        apply(self, data, |i, x| self.unscale(i, x))
+    }
+
+    fn fit_transform(&mut self, data: &mut Dataset) -> Result<(), String> {
+        self.fit(data)?;
+        self.transform(data)
+    }
+
+    /// Scales a [n_samples, n_features] tensor, with its columns in the
+    /// same order as the fitted ones.
+    fn transform_tensor(&self, xs: &Tensor) -> Result<Tensor, Box<dyn Error>> {
+        let n_features = self.columns().len();
+        let size = xs.size();
+
+        if size.last().copied() != Some(n_features as i64) {
+            return Err(format!(
+                "tensor of size {:?} doesn't match the {:?} fitted columns",
+                size, n_features
+            )
+            .into());
+        }
+
+        let values: Vec<f64> = Vec::try_from(&xs.to_kind(Kind::Double).reshape([-1]))?;
+        let scaled: Vec<f64> = values
+            .iter()
+            .enumerate()
+            .map(|(i, x)| self.scale(i % n_features, *x))
+            .collect();
+
+        Ok(Tensor::from_slice(&scaled)
+            .reshape(size)
+            .to_kind(Kind::Float))
+    }
+}
+
+/// Applies `f` (column position, value) over the fitted columns of `data`.
+fn apply<S, F>(scaler: &S, data: &mut Dataset, f: F) -> Result<(), String>
+where
+    S: Scaler + ?Sized,
+    F: Fn(usize, f64) -> f64,
+{
+    if !scaler.is_fitted() {
+        return Err("Scaler is not fitted".to_string());
+    }
+
+    let positions = scaler
+        .columns()
+        .iter()
+        .map(|name| {
+            data.column_index(name)
+                .ok_or(format!("Fitted column {} not found in the dataset", name))
+        })
+        .collect::<Result<Vec<usize>, String>>()?;
+
+    for (i, position) in positions.into_iter().enumerate() {
+        let column = &mut data.features[position];
+        column.values.iter_mut().for_each(|x| *x = f(i, *x));
+    }
+
+    Ok(())
+}
+
+/// Writes the fitted state of a scaler to a JSON file.
+pub fn write_scaler<S: Scaler + Serialize>(
+    scaler: &S,
+    file_route: &str,
+) -> Result<(), Box<dyn Error>> {
+    fs::write(file_route, serde_json::to_string_pretty(scaler)?)?;
+    Ok(())
+}
+
+/// Loads a scaler previously saved with `write_scaler`.
+pub fn load_scaler<S: Scaler + DeserializeOwned>(
+    file_route: &str,
+) -> Result<S, Box<dyn Error>> {
+    let json = fs::read_to_string(file_route)?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+/// Non-NaN values of a column, sorted in ascending order.
+fn sorted_values(column: &Column) -> Result<Vec<f64>, String> {
+    let mut values: Vec<f64> = column
+        .values
+        .iter()
+        .copied()
+        .filter(|x| !x.is_nan())
+        .collect();
+
+    if values.is_empty() {
+        return Err(format!("Column {} has no values to fit", column.name));
+    }
+
+    values.sort_by(|a, b| a.total_cmp(b));
+    Ok(values)
+}
+
+/// Linearly interpolated quantile `q` of already sorted values.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+/// Constant columns are left unscaled, instead of dividing by zero.
+fn non_zero(scale: f64) -> f64 {
+    if scale.abs() < 1e-12 {
+        1.0
+    } else {
+        scale
+    }
+}
+
+// ---------------------------------------------------------------- STANDARD SCALER -- //
+// ---------------------------------------------------------------- --------------- -- //
+
+/// new_x = (x - mean(x)) / std(x)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StandardScaler {
+    pub columns: Vec<String>,
+    pub means: Vec<f64>,
+    pub std_devs: Vec<f64>,
+}
+
+impl StandardScaler {
+    pub fn new() -> Self {
+        StandardScaler::default()
+    }
+}
+
+impl Scaler for StandardScaler {
+    fn fit(&mut self, data: &Dataset) -> Result<(), String> {
+        let mut fitted = StandardScaler::new();
+
+        for column in data.features.iter() {
+            let values = sorted_values(column)?;
+            let n = values.len() as f64;
+            let mean = values.iter().sum::<f64>() / n;
+            let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+
+            fitted.columns.push(column.name.clone());
+            fitted.means.push(mean);
+            fitted.std_devs.push(non_zero(variance.sqrt()));
+        }
+
+        *self = fitted;
+        Ok(())
+    }
+
+    fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    fn scale(&self, column: usize, value: f64) -> f64 {
+        (value - self.means[column]) / self.std_devs[column]
+    }
+
+    fn unscale(&self, column: usize, value: f64) -> f64 {
+        value * self.std_devs[column] + self.means[column]
+    }
+}
+
+// ----------------------------------------------------------------- MIN-MAX SCALER -- //
+// ----------------------------------------------------------------- -------------- -- //
+
+/// new_x = (x - min(x)) / (max(x) - min(x))
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MinMaxScaler {
+    pub columns: Vec<String>,
+    pub mins: Vec<f64>,
+    pub ranges: Vec<f64>,
+}
+
+impl MinMaxScaler {
+    pub fn new() -> Self {
+        MinMaxScaler::default()
+    }
+}
+
+impl Scaler for MinMaxScaler {
+    fn fit(&mut self, data: &Dataset) -> Result<(), String> {
+        let mut fitted = MinMaxScaler::new();
+
+        for column in data.features.iter() {
+            let values = sorted_values(column)?;
+            let min = values[0];
+            let max = values[values.len() - 1];
+
+            fitted.columns.push(column.name.clone());
+            fitted.mins.push(min);
+            fitted.ranges.push(non_zero(max - min));
+        }
+
+        *self = fitted;
+        Ok(())
+    }
+
+    fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    fn scale(&self, column: usize, value: f64) -> f64 {
+        (value - self.mins[column]) / self.ranges[column]
+    }
+
+    fn unscale(&self, column: usize, value: f64) -> f64 {
+        value * self.ranges[column] + self.mins[column]
+    }
+}
+
+// ------------------------------------------------------------------ ROBUST SCALER -- //
+// ------------------------------------------------------------------ ------------- -- //
+
+/// new_x = (x - median(x)) / (q75(x) - q25(x))
+///
+/// Less sensitive to outliers than the standard scaler.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RobustScaler {
+    pub columns: Vec<String>,
+    pub medians: Vec<f64>,
+    pub iqrs: Vec<f64>,
+}
+
+impl RobustScaler {
+    pub fn new() -> Self {
+        RobustScaler::default()
+    }
+}
+
+impl Scaler for RobustScaler {
+    fn fit(&mut self, data: &Dataset) -> Result<(), String> {
+        let mut fitted = RobustScaler::new();
+
+        for column in data.features.iter() {
+            let values = sorted_values(column)?;
+
+            fitted.columns.push(column.name.clone());
+            fitted.medians.push(quantile(&values, 0.5));
+            fitted
+                .iqrs
+                .push(non_zero(quantile(&values, 0.75) - quantile(&values, 0.25)));
+        }
+
+        *self = fitted;
+        Ok(())
+    }
+
+    fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    fn scale(&self, column: usize, value: f64) -> f64 {
+        (value - self.medians[column]) / self.iqrs[column]
+    }
+
+    fn unscale(&self, column: usize, value: f64) -> f64 {
+        value * self.iqrs[column] + self.medians[column]
+    }
+}
+
+// ---------------------------------------------------------------- QUANTILE SCALER -- //
+// ---------------------------------------------------------------- --------------- -- //
+
+/// Maps every value to its (interpolated) empirical cumulative probability,
+/// so each column ends up uniformly distributed in [0, 1]. Values outside
+/// of the fitted range are clipped to 0 or 1.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuantileScaler {
+    pub n_quantiles: usize,
+    pub columns: Vec<String>,
+    pub quantiles: Vec<Vec<f64>>,
+}
+
+impl QuantileScaler {
+    /// New scaler that keeps `n_quantiles` (at least 2) evenly spaced
+    /// quantiles per column.
+    pub fn new(n_quantiles: usize) -> Result<Self, &'static str> {
+        if n_quantiles < 2 {
+            return Err("n_quantiles must be at least 2");
+        }
+
+        Ok(QuantileScaler {
+            n_quantiles,
+            columns: Vec::new(),
+            quantiles: Vec::new(),
+        })
+    }
+}
+
+impl Default for QuantileScaler {
+    fn default() -> Self {
+        QuantileScaler {
+            n_quantiles: 100,
+            columns: Vec::new(),
+            quantiles: Vec::new(),
+        }
+    }
+}
+
+impl Scaler for QuantileScaler {
+    fn fit(&mut self, data: &Dataset) -> Result<(), String> {
+        let mut columns = Vec::new();
+        let mut quantiles = Vec::new();
+        let last = (self.n_quantiles - 1) as f64;
+
+        for column in data.features.iter() {
+            let values = sorted_values(column)?;
+
+            columns.push(column.name.clone());
+            quantiles.push(
+                (0..self.n_quantiles)
+                    .map(|i| quantile(&values, i as f64 / last))
+                    .collect(),
+            );
+        }
+
+        self.columns = columns;
+        self.quantiles = quantiles;
+        Ok(())
+    }
+
+    fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    fn scale(&self, column: usize, value: f64) -> f64 {
+        let qs = &self.quantiles[column];
+        let last = qs.len() - 1;
+
+        if value.is_nan() {
+            return value;
+        }
+        if value <= qs[0] {
+            return 0.0;
+        }
+        if value >= qs[last] {
+            return 1.0;
+        }
+
+        // first quantile above the value, qs[i - 1] <= value < qs[i]
+        let i = qs.partition_point(|q| *q <= value);
+        let fraction = (value - qs[i - 1]) / (qs[i] - qs[i - 1]);
+
+        (i as f64 - 1.0 + fraction) / last as f64
+    }
+
+    fn unscale(&self, column: usize, value: f64) -> f64 {
+        let qs = &self.quantiles[column];
+        let last = qs.len() - 1;
+
+        if value.is_nan() {
+            return value;
+        }
+
+        let position = value.clamp(0.0, 1.0) * last as f64;
+        let lower = position.floor() as usize;
+        let upper = position.ceil() as usize;
+
+        qs[lower] + (qs[upper] - qs[lower]) * (position - lower as f64)
+    }
+}
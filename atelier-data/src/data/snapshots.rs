@@ -0,0 +1,537 @@
+/// Compact, versioned binary format for order book snapshots
+use crate::{
+    levels::Level,
+    orderbooks::Orderbook,
+    orders::{Order, OrderSide, OrderType},
+};
+use std::{
+    error::Error,
+    fs::File,
+    io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+};
+
+// ------------------------------------------------------------------------- FORMAT -- //
+// ------------------------------------------------------------------------- ------ -- //
+//
+// All integers and floats are little endian.
+//
+// header   : MAGIC (4 bytes), VERSION (u16), books per block (u32)
+// blocks   : zstd compressed books, one after the other
+// index    : per block, first ts (u64), last ts (u64), offset (u64),
+//            compressed length (u64), number of books (u32)
+// trailer  : index offset (u64), number of blocks (u32), INDEX_MAGIC (4 bytes)
+//
+// A book is encoded as its id (u32), ts (u64), symbol (u16 length + utf8
+// bytes), bids and asks (u32 length + levels). A level as its id (u32),
+// side (u8), price (f64), volume (f64) and orders (u32 length + orders). An
+// order as its id (u64), ts (u64), type (u8), side (u8), and price and
+// amount (u8 presence flag + f64).
+
+const MAGIC: &[u8; 4] = b"ATLB";
+const INDEX_MAGIC: &[u8; 4] = b"ATLI";
+const HEADER_SIZE: u64 = 10;
+const TRAILER_SIZE: i64 = 16;
+
+/// Extension of snapshot files, as recognized by `data::load_orderbooks`.
+pub const EXTENSION: &str = "atb";
+
+/// Version written by `SnapshotWriter`, and the only one read so far.
+pub const FORMAT_VERSION: u16 = 1;
+
+/// Default number of books per compressed block.
+pub const DEFAULT_BLOCK_SIZE: u32 = 1024;
+
+/// Location and time range of one compressed block of books.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockIndex {
+    pub first_ts: u64,
+    pub last_ts: u64,
+    pub offset: u64,
+    pub length: u64,
+    pub n_books: u32,
+}
+
+// ----------------------------------------------------------------------- ENCODING -- //
+// ----------------------------------------------------------------------- -------- -- //
+
+fn encode_side(side: OrderSide) -> u8 {
+    match side {
+        OrderSide::Bids => 0,
+        OrderSide::Asks => 1,
+    }
+}
+
+fn encode_option(buffer: &mut Vec<u8>, value: Option<f64>) {
+    match value {
+        Some(value) => {
+            buffer.push(1);
+            buffer.extend(value.to_le_bytes());
+        }
+        None => buffer.push(0),
+    }
+}
+
+fn encode_book(buffer: &mut Vec<u8>, ob: &Orderbook) -> Result<(), String> {
+    let symbol = ob.symbol.as_bytes();
+    let symbol_len = u16::try_from(symbol.len())
+        .map_err(|_| format!("symbol of book {} is too long", ob.orderbook_ts))?;
+
+    buffer.extend(ob.orderbook_id.to_le_bytes());
+    buffer.extend(ob.orderbook_ts.to_le_bytes());
+    buffer.extend(symbol_len.to_le_bytes());
+    buffer.extend(symbol);
+
+    for levels in [&ob.bids, &ob.asks] {
+        buffer.extend((levels.len() as u32).to_le_bytes());
+
+        for level in levels {
+            buffer.extend(level.level_id.to_le_bytes());
+            buffer.push(encode_side(level.side));
+            buffer.extend(level.price.to_le_bytes());
+            buffer.extend(level.volume.to_le_bytes());
+            buffer.extend((level.orders.len() as u32).to_le_bytes());
+
+            for order in &level.orders {
+                buffer.extend(order.order_id.to_le_bytes());
+                buffer.extend(order.order_ts.to_le_bytes());
+                buffer.push(match order.order_type {
+                    OrderType::Market => 0,
+                    OrderType::Limit => 1,
+                });
+                buffer.push(encode_side(order.side));
+                encode_option(buffer, order.price);
+                encode_option(buffer, order.amount);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Cursor over the decompressed bytes of a block.
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        let bytes = self
+            .bytes
+            .get(self.position..self.position + n)
+            .ok_or("truncated block")?;
+        self.position += n;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn f64(&mut self) -> Result<f64, String> {
+        Ok(f64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn option(&mut self) -> Result<Option<f64>, String> {
+        match self.u8()? {
+            0 => Ok(None),
+            _ => Ok(Some(self.f64()?)),
+        }
+    }
+
+    fn side(&mut self) -> Result<OrderSide, String> {
+        match self.u8()? {
+            0 => Ok(OrderSide::Bids),
+            1 => Ok(OrderSide::Asks),
+            other => Err(format!("invalid side {}", other)),
+        }
+    }
+
+    fn book(&mut self) -> Result<Orderbook, String> {
+        let orderbook_id = self.u32()?;
+        let orderbook_ts = self.u64()?;
+        let symbol_len = self.u16()? as usize;
+        let symbol = String::from_utf8(self.take(symbol_len)?.to_vec())
+            .map_err(|_| "invalid symbol".to_string())?;
+
+        let mut sides = Vec::with_capacity(2);
+        for _ in 0..2 {
+            let n_levels = self.u32()?;
+            let mut levels = Vec::new();
+
+            for _ in 0..n_levels {
+                let level_id = self.u32()?;
+                let side = self.side()?;
+                let price = self.f64()?;
+                let volume = self.f64()?;
+                let n_orders = self.u32()?;
+                let mut orders = Vec::new();
+
+                for _ in 0..n_orders {
+                    orders.push(Order {
+                        order_id: self.u64()?,
+                        order_ts: self.u64()?,
+                        order_type: match self.u8()? {
+                            0 => OrderType::Market,
+                            1 => OrderType::Limit,
+                            other => return Err(format!("invalid order type {}", other)),
+                        },
+                        side: self.side()?,
+                        price: self.option()?,
+                        amount: self.option()?,
+                    });
+                }
+
+                levels.push(Level::new(level_id, side, price, volume, orders));
+            }
+            sides.push(levels);
+        }
+
+        let asks = sides.pop().unwrap_or_default();
+        let bids = sides.pop().unwrap_or_default();
+
+        Ok(Orderbook::new(
+            orderbook_id,
+            orderbook_ts,
+            symbol,
+            bids,
+            asks,
+        ))
+    }
+}
+
+// ---------------------------------------------------------------- SNAPSHOT WRITER -- //
+// ---------------------------------------------------------------- --------------- -- //
+
+/// Writes order book snapshots, in non decreasing `orderbook_ts` order, to
+/// the binary snapshot format.
+///
+/// Books are buffered into blocks of `block_size` books, each one
+/// compressed and written as soon as it is full. `finish` must be called to
+/// write the last block and the timestamp index.
+pub struct SnapshotWriter {
+    file: BufWriter<File>,
+    block_size: u32,
+    level: i32,
+    offset: u64,
+    block: Vec<u8>,
+    block_books: u32,
+    block_first_ts: u64,
+    last_ts: Option<u64>,
+    index: Vec<BlockIndex>,
+}
+
+impl SnapshotWriter {
+    /// Creates a snapshot file.
+    ///
+    /// # Parameters
+    ///
+    /// - `file_route`: Route of the file to create.
+    /// - `block_size`: Number of books per compressed block, larger blocks
+    ///   compress better, smaller ones make seeking cheaper.
+    /// - `level`: zstd compression level, 0 for the default one.
+    pub fn create(
+        file_route: &str,
+        block_size: u32,
+        level: i32,
+    ) -> Result<Self, Box<dyn Error>> {
+        if block_size == 0 {
+            return Err("block_size must be greater than 0".into());
+        }
+
+        let mut file = BufWriter::new(File::create(file_route)?);
+        file.write_all(MAGIC)?;
+        file.write_all(&FORMAT_VERSION.to_le_bytes())?;
+        file.write_all(&block_size.to_le_bytes())?;
+
+        Ok(SnapshotWriter {
+            file,
+            block_size,
+            level,
+            offset: HEADER_SIZE,
+            block: Vec::new(),
+            block_books: 0,
+            block_first_ts: 0,
+            last_ts: None,
+            index: Vec::new(),
+        })
+    }
+
+    pub fn write(&mut self, ob: &Orderbook) -> Result<(), Box<dyn Error>> {
+        if let Some(last_ts) = self.last_ts {
+            if ob.orderbook_ts < last_ts {
+                return Err(format!(
+                    "books must be written in time order, {} comes after {}",
+                    ob.orderbook_ts, last_ts
+                )
+                .into());
+            }
+        }
+
+        if self.block_books == 0 {
+            self.block_first_ts = ob.orderbook_ts;
+        }
+        encode_book(&mut self.block, ob)?;
+        self.block_books += 1;
+        self.last_ts = Some(ob.orderbook_ts);
+
+        if self.block_books == self.block_size {
+            self.write_block()?;
+        }
+        Ok(())
+    }
+
+    fn write_block(&mut self) -> Result<(), Box<dyn Error>> {
+        if self.block_books == 0 {
+            return Ok(());
+        }
+
+        let compressed = zstd::bulk::compress(&self.block, self.level)?;
+        self.file.write_all(&compressed)?;
+
+        self.index.push(BlockIndex {
+            first_ts: self.block_first_ts,
+            last_ts: self.last_ts.unwrap_or(self.block_first_ts),
+            offset: self.offset,
+            length: compressed.len() as u64,
+            n_books: self.block_books,
+        });
+
+        self.offset += compressed.len() as u64;
+        self.block.clear();
+        self.block_books = 0;
+        Ok(())
+    }
+
+    /// Writes the last block and the index.
+    pub fn finish(mut self) -> Result<(), Box<dyn Error>> {
+        self.write_block()?;
+
+        for block in &self.index {
+            self.file.write_all(&block.first_ts.to_le_bytes())?;
+            self.file.write_all(&block.last_ts.to_le_bytes())?;
+            self.file.write_all(&block.offset.to_le_bytes())?;
+            self.file.write_all(&block.length.to_le_bytes())?;
+            self.file.write_all(&block.n_books.to_le_bytes())?;
+        }
+
+        self.file.write_all(&self.offset.to_le_bytes())?;
+        self.file
+            .write_all(&(self.index.len() as u32).to_le_bytes())?;
+        self.file.write_all(INDEX_MAGIC)?;
+        self.file.flush()?;
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------- SNAPSHOT READER -- //
+// ---------------------------------------------------------------- --------------- -- //
+
+/// Random access to a snapshot file through its timestamp index.
+///
+/// Only the index is read when opening, blocks are decompressed one at a
+/// time while iterating.
+pub struct SnapshotReader {
+    file: BufReader<File>,
+    version: u16,
+    block_size: u32,
+    index: Vec<BlockIndex>,
+}
+
+impl SnapshotReader {
+    pub fn open(file_route: &str) -> Result<Self, Box<dyn Error>> {
+        let mut file = BufReader::new(File::open(file_route)?);
+
+        let mut header = [0u8; HEADER_SIZE as usize];
+        file.read_exact(&mut header)
+            .map_err(|_| format!("{} is not a snapshot file", file_route))?;
+        if &header[0..4] != MAGIC {
+            return Err(format!("{} is not a snapshot file", file_route).into());
+        }
+
+        let version = u16::from_le_bytes([header[4], header[5]]);
+        if version != FORMAT_VERSION {
+            return Err(format!(
+                "unsupported snapshot format version {}, expected {}",
+                version, FORMAT_VERSION
+            )
+            .into());
+        }
+        let block_size = u32::from_le_bytes(header[6..10].try_into().unwrap());
+
+        let mut trailer = [0u8; TRAILER_SIZE as usize];
+        file.seek(SeekFrom::End(-TRAILER_SIZE))?;
+        file.read_exact(&mut trailer)?;
+        if &trailer[12..16] != INDEX_MAGIC {
+            return Err(
+                format!("{} has no index, it was not finished", file_route).into()
+            );
+        }
+
+        let index_offset = u64::from_le_bytes(trailer[0..8].try_into().unwrap());
+        let n_blocks = u32::from_le_bytes(trailer[8..12].try_into().unwrap());
+
+        let mut bytes = vec![0u8; n_blocks as usize * 36];
+        file.seek(SeekFrom::Start(index_offset))?;
+        file.read_exact(&mut bytes)?;
+
+        let mut decoder = Decoder {
+            bytes: &bytes,
+            position: 0,
+        };
+        let index = (0..n_blocks)
+            .map(|_| {
+                Ok(BlockIndex {
+                    first_ts: decoder.u64()?,
+                    last_ts: decoder.u64()?,
+                    offset: decoder.u64()?,
+                    length: decoder.u64()?,
+                    n_books: decoder.u32()?,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(SnapshotReader {
+            file,
+            version,
+            block_size,
+            index,
+        })
+    }
+
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    pub fn block_size(&self) -> u32 {
+        self.block_size
+    }
+
+    pub fn index(&self) -> &[BlockIndex] {
+        &self.index
+    }
+
+    /// Number of books in the file.
+    pub fn len(&self) -> usize {
+        self.index.iter().map(|b| b.n_books as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Timestamps of the first and the last books.
+    pub fn time_range(&self) -> Option<(u64, u64)> {
+        Some((self.index.first()?.first_ts, self.index.last()?.last_ts))
+    }
+
+    /// Decompresses and decodes all the books of a block.
+    pub fn read_block(&mut self, block: usize) -> Result<Vec<Orderbook>, Box<dyn Error>> {
+        let entry = *self.index.get(block).ok_or("block out of range")?;
+
+        let mut compressed = vec![0u8; entry.length as usize];
+        self.file.seek(SeekFrom::Start(entry.offset))?;
+        self.file.read_exact(&mut compressed)?;
+
+        let bytes = zstd::stream::decode_all(compressed.as_slice())?;
+        let mut decoder = Decoder {
+            bytes: &bytes,
+            position: 0,
+        };
+
+        Ok((0..entry.n_books)
+            .map(|_| decoder.book())
+            .collect::<Result<_, _>>()?)
+    }
+
+    /// Books with `start_ts <= orderbook_ts < end_ts`, only the blocks that
+    /// overlap the range are read.
+    pub fn range(&mut self, start_ts: u64, end_ts: u64) -> SnapshotRange<'_> {
+        // first block that may hold books at or after start_ts
+        let block = self.index.partition_point(|b| b.last_ts < start_ts);
+
+        SnapshotRange {
+            reader: self,
+            block,
+            books: Vec::new().into_iter(),
+            start_ts,
+            end_ts,
+        }
+    }
+
+    /// Books from the first one with `orderbook_ts >= ts` to the end.
+    pub fn seek(&mut self, ts: u64) -> SnapshotRange<'_> {
+        self.range(ts, u64::MAX)
+    }
+
+    pub fn iter(&mut self) -> SnapshotRange<'_> {
+        self.range(0, u64::MAX)
+    }
+}
+
+/// Iterator over the books of a time range, see `SnapshotReader::range`.
+pub struct SnapshotRange<'a> {
+    reader: &'a mut SnapshotReader,
+    block: usize,
+    books: std::vec::IntoIter<Orderbook>,
+    start_ts: u64,
+    end_ts: u64,
+}
+
+impl Iterator for SnapshotRange<'_> {
+    type Item = Result<Orderbook, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(ob) = self.books.next() {
+                if ob.orderbook_ts < self.start_ts {
+                    continue;
+                }
+                if ob.orderbook_ts >= self.end_ts {
+                    return None;
+                }
+                return Some(Ok(ob));
+            }
+
+            let entry = self.reader.index.get(self.block)?;
+            if entry.first_ts >= self.end_ts {
+                return None;
+            }
+
+            match self.reader.read_block(self.block) {
+                Ok(books) => self.books = books.into_iter(),
+                Err(e) => return Some(Err(e)),
+            }
+            self.block += 1;
+        }
+    }
+}
+
+/// Load all the books of a snapshot file
+pub fn load_from_snapshots(file_route: &str) -> Result<Vec<Orderbook>, Box<dyn Error>> {
+    SnapshotReader::open(file_route)?.iter().collect()
+}
+
+/// Write books, in time order, to a snapshot file
+pub fn write_to_snapshots(
+    ob_data: &[Orderbook],
+    file_route: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut writer = SnapshotWriter::create(file_route, DEFAULT_BLOCK_SIZE, 0)?;
+    for ob in ob_data {
+        writer.write(ob)?;
+    }
+    writer.finish()
+}
@@ -0,0 +1,453 @@
+/// Chronological splits of a Dataset
+use crate::data::{Column, Dataset};
+use std::ops::Range;
+
+// ------------------------------------------------------------------- DATASET VIEW -- //
+// ------------------------------------------------------------------- ------------ -- //
+
+/// A subset of the rows of a `Dataset`, addressed by position.
+///
+/// Views are cheap, they only hold the row positions. Use `to_dataset` to
+/// materialize one, e.g. to feed it to `processes::Singular`.
+#[derive(Debug, Clone)]
+pub struct DatasetView<'a> {
+    dataset: &'a Dataset,
+    rows: Vec<usize>,
+}
+
+impl<'a> DatasetView<'a> {
+    pub fn new(dataset: &'a Dataset, rows: Vec<usize>) -> Result<Self, String> {
+        if let Some(row) = rows.iter().find(|row| **row >= dataset.len()) {
+            return Err(format!(
+                "row {:?} out of range for a dataset of length {:?}",
+                row,
+                dataset.len()
+            ));
+        }
+
+        Ok(DatasetView { dataset, rows })
+    }
+
+    pub fn rows(&self) -> &[usize] {
+        &self.rows
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Index values (timestamps) of the rows in the view.
+    pub fn index(&self) -> Vec<u64> {
+        self.rows
+            .iter()
+            .filter_map(|row| self.dataset.index.get(*row).copied())
+            .collect()
+    }
+
+    pub fn get_sample(&self, idx: usize) -> Option<(Vec<f64>, f64)> {
+        self.dataset.get_sample(*self.rows.get(idx)?)
+    }
+
+    /// Owned `Dataset` with the rows of the view, in order.
+    pub fn to_dataset(&self) -> Dataset {
+        let take = |values: &Vec<f64>| self.rows.iter().map(|row| values[*row]).collect();
+
+        Dataset {
+            index: self.index(),
+            features: self
+                .dataset
+                .features
+                .iter()
+                .map(|c| Column::new(&c.name, take(&c.values)))
+                .collect(),
+            targets: self
+                .dataset
+                .targets
+                .iter()
+                .map(|c| Column::new(&c.name, take(&c.values)))
+                .collect(),
+            selected_target: self.dataset.selected_target,
+            lookback: self.dataset.lookback,
+            horizon: self.dataset.horizon,
+        }
+    }
+}
+
+impl Dataset {
+    /// View over the rows at the given positions.
+    pub fn view(&self, rows: Vec<usize>) -> Result<DatasetView<'_>, String> {
+        DatasetView::new(self, rows)
+    }
+}
+
+// ----------------------------------------------------------------- TRAIN/VAL/TEST -- //
+// ----------------------------------------------------------------- -------------- -- //
+
+/// Row positions of a chronological train, validation and test split.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Split {
+    pub train: Vec<usize>,
+    pub validation: Vec<usize>,
+    pub test: Vec<usize>,
+}
+
+impl Split {
+    /// Chronological split of `n_rows` rows.
+    ///
+    /// The first `train` fraction of the rows goes to training, the next
+    /// `validation` fraction to validation and the rest to test. Rows are
+    /// never shuffled, so every validation row comes after every train row,
+    /// and every test row after every validation row. An `embargo` number
+    /// of rows is dropped in between consecutive sets.
+    ///
+    /// # Parameters
+    ///
+    /// - `n_rows`: Number of rows in the dataset.
+    /// - `train`: Fraction of rows for training, in (0, 1).
+    /// - `validation`: Fraction of rows for validation, in [0, 1 - train).
+    /// - `embargo`: Rows dropped between consecutive sets.
+    pub fn chronological(
+        n_rows: usize,
+        train: f64,
+        validation: f64,
+        embargo: usize,
+    ) -> Result<Self, String> {
+        if !(train > 0.0 && validation >= 0.0 && train + validation < 1.0) {
+            return Err(format!(
+                "invalid fractions, train {:?} validation {:?} must add up to < 1",
+                train, validation
+            ));
+        }
+
+        let train_end = (n_rows as f64 * train).floor() as usize;
+        let validation_end = (n_rows as f64 * (train + validation)).floor() as usize;
+
+        let validation_start = train_end + embargo;
+        let test_start = if validation > 0.0 {
+            validation_end + embargo
+        } else {
+            validation_start
+        };
+
+        let split = Split {
+            train: (0..train_end).collect(),
+            validation: (validation_start..validation_end).collect(),
+            test: (test_start..n_rows).collect(),
+        };
+
+        if split.train.is_empty()
+            || split.test.is_empty()
+            || (validation > 0.0 && split.validation.is_empty())
+        {
+            return Err(format!(
+                "{:?} rows are not enough for a split with embargo {:?}",
+                n_rows, embargo
+            ));
+        }
+
+        Ok(split)
+    }
+
+    pub fn train_view<'a>(
+        &self,
+        dataset: &'a Dataset,
+    ) -> Result<DatasetView<'a>, String> {
+        dataset.view(self.train.clone())
+    }
+
+    pub fn validation_view<'a>(
+        &self,
+        dataset: &'a Dataset,
+    ) -> Result<DatasetView<'a>, String> {
+        dataset.view(self.validation.clone())
+    }
+
+    pub fn test_view<'a>(&self, dataset: &'a Dataset) -> Result<DatasetView<'a>, String> {
+        dataset.view(self.test.clone())
+    }
+}
+
+// --------------------------------------------------------------------------- FOLD -- //
+// --------------------------------------------------------------------------- ---- -- //
+
+/// Row positions of one cross-validation fold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fold {
+    pub train: Vec<usize>,
+    pub test: Vec<usize>,
+}
+
+impl Fold {
+    pub fn train_view<'a>(
+        &self,
+        dataset: &'a Dataset,
+    ) -> Result<DatasetView<'a>, String> {
+        dataset.view(self.train.clone())
+    }
+
+    pub fn test_view<'a>(&self, dataset: &'a Dataset) -> Result<DatasetView<'a>, String> {
+        dataset.view(self.test.clone())
+    }
+}
+
+// ------------------------------------------------------------------- WALK FORWARD -- //
+// ------------------------------------------------------------------- ------------ -- //
+
+/// How the training window of a walk-forward evolves between folds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WindowType {
+    /// Training always starts at the first row and grows with every fold.
+    Expanding,
+    /// Training is a window of fixed size that moves along with the test.
+    Rolling,
+}
+
+/// Walk-forward validation.
+///
+/// The last `n_folds * test_size` rows are split in consecutive test
+/// blocks, each fold trains on rows strictly before its test block, leaving
+/// an `embargo` gap of rows in between.
+#[derive(Debug, Clone)]
+pub struct WalkForward {
+    n_folds: usize,
+    test_size: Option<usize>,
+    train_size: Option<usize>,
+    window: WindowType,
+    embargo: usize,
+}
+
+impl WalkForward {
+    pub fn new() -> WalkForwardBuilder {
+        WalkForwardBuilder::new()
+    }
+
+    /// Folds for a dataset of `n_rows` rows, in chronological order.
+    pub fn folds(&self, n_rows: usize) -> Result<Vec<Fold>, String> {
+        // Without an explicit size, the rows are split in n_folds + 1 blocks
+        let test_size = self.test_size.unwrap_or(n_rows / (self.n_folds + 1));
+
+        if test_size == 0 || self.n_folds * test_size >= n_rows {
+            return Err(format!(
+                "{:?} rows are not enough for {:?} folds of test size {:?}",
+                n_rows, self.n_folds, test_size
+            ));
+        }
+
+        let first_test = n_rows - self.n_folds * test_size;
+
+        (0..self.n_folds)
+            .map(|k| {
+                let test_start = first_test + k * test_size;
+                let train_end = test_start.saturating_sub(self.embargo);
+
+                let train_start = match (self.window, self.train_size) {
+                    (WindowType::Rolling, Some(size)) => train_end.saturating_sub(size),
+                    // Rolling without size uses the rows available for the first fold
+                    (WindowType::Rolling, None) => {
+                        train_end.saturating_sub(first_test.saturating_sub(self.embargo))
+                    }
+                    (WindowType::Expanding, _) => 0,
+                };
+
+                if train_start >= train_end {
+                    return Err(format!(
+                        "fold {:?} has no training rows, embargo {:?} is too large",
+                        k, self.embargo
+                    ));
+                }
+
+                Ok(Fold {
+                    train: (train_start..train_end).collect(),
+                    test: (test_start..test_start + test_size).collect(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+pub struct WalkForwardBuilder {
+    n_folds: Option<usize>,
+    test_size: Option<usize>,
+    train_size: Option<usize>,
+    window: WindowType,
+    embargo: usize,
+}
+
+impl WalkForwardBuilder {
+    pub fn new() -> Self {
+        WalkForwardBuilder {
+            n_folds: None,
+            test_size: None,
+            train_size: None,
+            window: WindowType::Expanding,
+            embargo: 0,
+        }
+    }
+
+    pub fn n_folds(mut self, n_folds: usize) -> Self {
+        self.n_folds = Some(n_folds);
+        self
+    }
+
+    /// Rows in each test block, by default `n_rows / (n_folds + 1)`.
+    pub fn test_size(mut self, test_size: usize) -> Self {
+        self.test_size = Some(test_size);
+        self
+    }
+
+    /// Rows in each training window, only used by `WindowType::Rolling`.
+    pub fn train_size(mut self, train_size: usize) -> Self {
+        self.train_size = Some(train_size);
+        self
+    }
+
+    pub fn window(mut self, window: WindowType) -> Self {
+        self.window = window;
+        self
+    }
+
+    pub fn embargo(mut self, embargo: usize) -> Self {
+        self.embargo = embargo;
+        self
+    }
+
+    pub fn build(self) -> Result<WalkForward, &'static str> {
+        let n_folds = self.n_folds.ok_or("Missing n_folds")?;
+
+        if n_folds == 0 {
+            return Err("n_folds must be greater than 0");
+        }
+        if self.train_size == Some(0) {
+            return Err("train_size must be greater than 0");
+        }
+
+        Ok(WalkForward {
+            n_folds,
+            test_size: self.test_size,
+            train_size: self.train_size,
+            window: self.window,
+            embargo: self.embargo,
+        })
+    }
+}
+
+// ------------------------------------------------------------------ PURGED K-FOLD -- //
+// ------------------------------------------------------------------ ------------- -- //
+
+/// K-fold cross-validation for labels that overlap in time.
+///
+/// The rows are split in `n_folds` consecutive test blocks, every fold
+/// trains on all the other rows except for the ones whose label overlaps
+/// the test block. With labels computed `horizon` rows ahead (the label of
+/// row `t` uses data up to `t + horizon`), training rows within `horizon`
+/// rows before the test block are purged, and so are the ones within
+/// `horizon + embargo` rows after it.
+#[derive(Debug, Clone)]
+pub struct PurgedKFold {
+    n_folds: usize,
+    horizon: usize,
+    embargo: usize,
+}
+
+impl PurgedKFold {
+    pub fn new() -> PurgedKFoldBuilder {
+        PurgedKFoldBuilder::new()
+    }
+
+    /// Folds for a dataset of `n_rows` rows, test blocks in chronological
+    /// order.
+    pub fn folds(&self, n_rows: usize) -> Result<Vec<Fold>, String> {
+        if n_rows < self.n_folds {
+            return Err(format!(
+                "{:?} rows are not enough for {:?} folds",
+                n_rows, self.n_folds
+            ));
+        }
+
+        (0..self.n_folds)
+            .map(|k| {
+                let test = self.test_block(n_rows, k);
+
+                // Rows whose label overlaps the test block, plus the embargo
+                let purged = test.start.saturating_sub(self.horizon)
+                    ..(test.end + self.horizon + self.embargo).min(n_rows);
+
+                let train: Vec<usize> =
+                    (0..n_rows).filter(|i| !purged.contains(i)).collect();
+
+                if train.is_empty() {
+                    return Err(format!(
+                        "fold {:?} has no training rows after purging",
+                        k
+                    ));
+                }
+
+                Ok(Fold {
+                    train,
+                    test: test.collect(),
+                })
+            })
+            .collect()
+    }
+
+    fn test_block(&self, n_rows: usize, k: usize) -> Range<usize> {
+        // The first n_rows % n_folds blocks take one extra row
+        let base = n_rows / self.n_folds;
+        let extra = n_rows % self.n_folds;
+        let start = k * base + k.min(extra);
+        let end = start + base + usize::from(k < extra);
+        start..end
+    }
+}
+
+#[derive(Debug)]
+pub struct PurgedKFoldBuilder {
+    n_folds: Option<usize>,
+    horizon: usize,
+    embargo: usize,
+}
+
+impl PurgedKFoldBuilder {
+    pub fn new() -> Self {
+        PurgedKFoldBuilder {
+            n_folds: None,
+            horizon: 0,
+            embargo: 0,
+        }
+    }
+
+    pub fn n_folds(mut self, n_folds: usize) -> Self {
+        self.n_folds = Some(n_folds);
+        self
+    }
+
+    /// Number of rows ahead each label looks into.
+    pub fn horizon(mut self, horizon: usize) -> Self {
+        self.horizon = horizon;
+        self
+    }
+
+    pub fn embargo(mut self, embargo: usize) -> Self {
+        self.embargo = embargo;
+        self
+    }
+
+    pub fn build(self) -> Result<PurgedKFold, &'static str> {
+        let n_folds = self.n_folds.ok_or("Missing n_folds")?;
+
+        if n_folds < 2 {
+            return Err("n_folds must be at least 2");
+        }
+
+        Ok(PurgedKFold {
+            n_folds,
+            horizon: self.horizon,
+            embargo: self.embargo,
+        })
+    }
+}
@@ -0,0 +1,63 @@
+/// Trade tape I/O
+use crate::{
+    data::{columnar, file_format, load_from_json, ndjson},
+    trades::Trade,
+};
+use csv::{ReaderBuilder, Writer};
+use std::error::Error;
+
+// ---------------------------------------------------------------------------- CSV -- //
+// ---------------------------------------------------------------------------- --- -- //
+
+/// Loads trades from a CSV file.
+///
+/// The header must have the `trade_id`, `trade_ts`, `side` ("Bids" for buyer
+/// initiated trades, "Asks" for seller initiated ones), `price` and
+/// `quantity` columns, `maker_order_id` and `taker_order_id` are optional
+/// and can be left empty.
+pub fn load_trades_from_csv(file_route: &str) -> Result<Vec<Trade>, Box<dyn Error>> {
+    let mut rdr = ReaderBuilder::new()
+        .has_headers(true)
+        .trim(csv::Trim::All)
+        .from_path(file_route)?;
+
+    rdr.deserialize()
+        .enumerate()
+        .map(|(i, result)| {
+            result.map_err(|e| format!("invalid trade at row {}: {}", i + 1, e).into())
+        })
+        .collect()
+}
+
+/// Writes trades to a CSV file, with the columns of `load_trades_from_csv`.
+pub fn write_trades_to_csv(
+    trades: &[Trade],
+    file_route: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut wtr = Writer::from_path(file_route)?;
+    for trade in trades {
+        wtr.serialize(trade)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+// ----------------------------------------------------------------------- DISPATCH -- //
+// ----------------------------------------------------------------------- -------- -- //
+
+/// Load trades from a file, with the format given by its extension
+///
+/// - `.csv`: see `load_trades_from_csv`.
+/// - `.json`: a single JSON array, see `load_from_json`.
+/// - `.ndjson` or `.jsonl`, optionally followed by `.gz` or `.zst`: one trade
+///   per line, see `ndjson::load_from_ndjson`.
+/// - `.parquet`: see `columnar::load_trades_from_parquet`.
+pub fn load_trades(file_route: &str) -> Result<Vec<Trade>, Box<dyn Error>> {
+    match file_format(file_route)? {
+        "csv" => load_trades_from_csv(file_route),
+        "json" => load_from_json(file_route),
+        "ndjson" | "jsonl" => ndjson::load_from_ndjson(file_route),
+        "parquet" => columnar::load_trades_from_parquet(file_route),
+        _ => Err(format!("Unknown trades file format: {}", file_route).into()),
+    }
+}
@@ -0,0 +1,250 @@
+/// Wide-format top-N CSV import/export for order books
+use crate::{levels::Level, orderbooks::Orderbook, orders::OrderSide};
+use csv::{ReaderBuilder, StringRecord, Writer};
+use std::error::Error;
+
+// ---------------------------------------------------------------- WIDE CSV FORMAT -- //
+// ---------------------------------------------------------------- --------------- -- //
+
+/// Column naming and depth of a wide CSV file.
+///
+/// Each row is a snapshot, with a timestamp column followed by the prices
+/// and sizes of the top `depth` levels of each side, e.g. for a depth of 2:
+///
+/// `timestamp, bid_px_1, bid_px_2, bid_sz_1, bid_sz_2, ask_px_1, ask_px_2,
+/// ask_sz_1, ask_sz_2`
+///
+/// Level columns are named `{prefix}{separator}{level}`, with the level
+/// starting at 1 for the top of the book.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WideCsvFormat {
+    pub depth: usize,
+    pub timestamp: String,
+    pub bid_price: String,
+    pub bid_size: String,
+    pub ask_price: String,
+    pub ask_size: String,
+    pub separator: String,
+}
+
+impl WideCsvFormat {
+    /// Format with the top `depth` levels and the default names.
+    pub fn new(depth: usize) -> Self {
+        WideCsvFormat {
+            depth,
+            timestamp: "timestamp".to_string(),
+            bid_price: "bid_px".to_string(),
+            bid_size: "bid_sz".to_string(),
+            ask_price: "ask_px".to_string(),
+            ask_size: "ask_sz".to_string(),
+            separator: "_".to_string(),
+        }
+    }
+
+    pub fn timestamp(mut self, name: &str) -> Self {
+        self.timestamp = name.to_string();
+        self
+    }
+
+    /// Prefixes of the bid prices, bid sizes, ask prices and ask sizes
+    /// columns.
+    pub fn prefixes(
+        mut self,
+        bid_price: &str,
+        bid_size: &str,
+        ask_price: &str,
+        ask_size: &str,
+    ) -> Self {
+        self.bid_price = bid_price.to_string();
+        self.bid_size = bid_size.to_string();
+        self.ask_price = ask_price.to_string();
+        self.ask_size = ask_size.to_string();
+        self
+    }
+
+    pub fn separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    /// Name of the column of `prefix` for `level`, starting at 1.
+    pub fn column(&self, prefix: &str, level: usize) -> String {
+        format!("{}{}{}", prefix, self.separator, level)
+    }
+
+    /// All the column names, in the order they are written.
+    pub fn header(&self) -> Vec<String> {
+        let mut header = vec![self.timestamp.clone()];
+        for prefix in [
+            &self.bid_price,
+            &self.bid_size,
+            &self.ask_price,
+            &self.ask_size,
+        ] {
+            header.extend((1..=self.depth).map(|level| self.column(prefix, level)));
+        }
+        header
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.depth == 0 {
+            return Err("depth must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------- IMPORT -- //
+// ------------------------------------------------------------------------- ------ -- //
+
+/// Positions of the price and size columns of one side, per level.
+fn side_columns(
+    header: &StringRecord,
+    format: &WideCsvFormat,
+    price: &str,
+    size: &str,
+) -> Result<Vec<(usize, usize)>, String> {
+    let position = |name: &str| header.iter().position(|h| h.trim() == name);
+
+    let mut columns = Vec::new();
+    for level in 1..=format.depth {
+        let price_name = format.column(price, level);
+        let size_name = format.column(size, level);
+
+        match (position(&price_name), position(&size_name)) {
+            (Some(p), Some(s)) => columns.push((p, s)),
+            // Files with less levels than the format
+            (None, None) if level > 1 => break,
+            (None, _) => return Err(format!("Missing column {}", price_name)),
+            (_, None) => return Err(format!("Missing column {}", size_name)),
+        }
+    }
+    Ok(columns)
+}
+
+/// Levels of one side of a row, up to the first empty price or size.
+fn read_levels(
+    record: &StringRecord,
+    columns: &[(usize, usize)],
+    side: OrderSide,
+    row: usize,
+) -> Result<Vec<Level>, String> {
+    let mut levels = Vec::new();
+
+    for (level, (price, size)) in columns.iter().enumerate() {
+        let price = record.get(*price).unwrap_or("").trim();
+        let size = record.get(*size).unwrap_or("").trim();
+        if price.is_empty() || size.is_empty() {
+            break;
+        }
+
+        let parse = |value: &str| {
+            value
+                .parse::<f64>()
+                .map_err(|_| format!("invalid number {:?} at row {}", value, row))
+        };
+        levels.push(Level::new(
+            level as u32,
+            side,
+            parse(price)?,
+            parse(size)?,
+            Vec::new(),
+        ));
+    }
+
+    Ok(levels)
+}
+
+/// Loads order books from a wide CSV file.
+///
+/// Columns are found by name, so they can be in any order and the file can
+/// have other columns. A side ends at its first empty price or size, and
+/// files with less levels than `format.depth` are accepted. Levels have no
+/// orders, and books are given their row number (starting at 1) as id.
+///
+/// # Parameters
+///
+/// - `file_route`: Route of the CSV file, with a header.
+/// - `format`: Column naming and depth to read.
+/// - `symbol`: Symbol of the books, not part of the wide layout.
+pub fn load_from_wide_csv(
+    file_route: &str,
+    format: &WideCsvFormat,
+    symbol: &str,
+) -> Result<Vec<Orderbook>, Box<dyn Error>> {
+    format.validate()?;
+
+    let mut rdr = ReaderBuilder::new()
+        .has_headers(true)
+        .from_path(file_route)?;
+    let header = rdr.headers()?.clone();
+
+    let ts_column = header
+        .iter()
+        .position(|h| h.trim() == format.timestamp)
+        .ok_or(format!("Missing column {}", format.timestamp))?;
+    let bid_columns = side_columns(&header, format, &format.bid_price, &format.bid_size)?;
+    let ask_columns = side_columns(&header, format, &format.ask_price, &format.ask_size)?;
+
+    let mut orderbooks = Vec::new();
+
+    for (i, result) in rdr.records().enumerate() {
+        let record = result?;
+        let row = i + 1;
+
+        let ts = record.get(ts_column).unwrap_or("").trim();
+        let ts = ts
+            .parse::<u64>()
+            .map_err(|_| format!("invalid timestamp {:?} at row {}", ts, row))?;
+
+        orderbooks.push(Orderbook::new(
+            row as u32,
+            ts,
+            symbol.to_string(),
+            read_levels(&record, &bid_columns, OrderSide::Bids, row)?,
+            read_levels(&record, &ask_columns, OrderSide::Asks, row)?,
+        ));
+    }
+
+    Ok(orderbooks)
+}
+
+// ------------------------------------------------------------------------- EXPORT -- //
+// ------------------------------------------------------------------------- ------ -- //
+
+/// Writes order books to a wide CSV file.
+///
+/// Only the top `format.depth` levels of each side are written, the cells
+/// of missing levels are left empty.
+pub fn write_to_wide_csv(
+    ob_data: &[Orderbook],
+    file_route: &str,
+    format: &WideCsvFormat,
+) -> Result<(), Box<dyn Error>> {
+    format.validate()?;
+
+    let mut wtr = Writer::from_path(file_route)?;
+    wtr.write_record(format.header())?;
+
+    for ob in ob_data {
+        let mut row = vec![ob.orderbook_ts.to_string()];
+
+        for (levels, is_price) in [
+            (&ob.bids, true),
+            (&ob.bids, false),
+            (&ob.asks, true),
+            (&ob.asks, false),
+        ] {
+            row.extend((0..format.depth).map(|i| match levels.get(i) {
+                Some(level) if is_price => level.price.to_string(),
+                Some(level) => level.volume.to_string(),
+                None => String::new(),
+            }));
+        }
+
+        wtr.write_record(&row)?;
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
@@ -0,0 +1,250 @@
+/// Lagged and windowed features
+use crate::data::{Column, Dataset};
+use tch::{Kind, Tensor};
+
+// ---------------------------------------------------------------------- AGGREGATE -- //
+// ---------------------------------------------------------------------- --------- -- //
+
+/// Aggregate computed over a rolling window of rows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Aggregate {
+    Mean,
+    /// Population standard deviation.
+    Std,
+    Min,
+    Max,
+    /// Exponentially weighted moving average with smoothing `alpha` in
+    /// (0, 1], computed recursively from the first row. The window only
+    /// sets the warm-up rows that are dropped.
+    Ewma(f64),
+}
+
+impl Aggregate {
+    fn label(&self) -> &'static str {
+        match self {
+            Aggregate::Mean => "mean",
+            Aggregate::Std => "std",
+            Aggregate::Min => "min",
+            Aggregate::Max => "max",
+            Aggregate::Ewma(_) => "ewma",
+        }
+    }
+
+    /// Aggregated value for every row from `window - 1` on.
+    fn compute(&self, values: &[f64], window: usize) -> Vec<f64> {
+        match self {
+            Aggregate::Ewma(alpha) => {
+                let mut ewma = values[0];
+                let mut smoothed = Vec::with_capacity(values.len());
+                for value in values {
+                    ewma = alpha * value + (1.0 - alpha) * ewma;
+                    smoothed.push(ewma);
+                }
+                smoothed[window - 1..].to_vec()
+            }
+
+            Aggregate::Mean => values.windows(window).map(mean).collect(),
+
+            Aggregate::Std => values
+                .windows(window)
+                .map(|w| {
+                    let mean = mean(w);
+                    (w.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / w.len() as f64)
+                        .sqrt()
+                })
+                .collect(),
+
+            Aggregate::Min => values
+                .windows(window)
+                .map(|w| w.iter().cloned().fold(f64::INFINITY, f64::min))
+                .collect(),
+
+            Aggregate::Max => values
+                .windows(window)
+                .map(|w| w.iter().cloned().fold(f64::NEG_INFINITY, f64::max))
+                .collect(),
+        }
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+// -------------------------------------------------------------- WINDOWED FEATURES -- //
+// -------------------------------------------------------------- ----------------- -- //
+
+impl Dataset {
+    /// Adds the values of every feature at t-1..t-`n_lags`.
+    ///
+    /// New columns are named `{feature}_lag_{i}`. The first `n_lags` rows
+    /// have no complete history and are dropped, the lookback of the
+    /// Dataset grows by `n_lags`.
+    pub fn lags(&self, n_lags: usize) -> Result<Dataset, String> {
+        if n_lags == 0 || n_lags >= self.len() {
+            return Err(format!(
+                "n_lags must be in [1, {:?}), got {:?}",
+                self.len(),
+                n_lags
+            ));
+        }
+
+        let n_rows = self.len() - n_lags;
+        let mut features: Vec<Column> = self
+            .features
+            .iter()
+            .map(|c| Column::new(&c.name, c.values[n_lags..].to_vec()))
+            .collect();
+
+        for c in self.features.iter() {
+            for lag in 1..=n_lags {
+                let start = n_lags - lag;
+                features.push(Column::new(
+                    &format!("{}_lag_{}", c.name, lag),
+                    c.values[start..start + n_rows].to_vec(),
+                ));
+            }
+        }
+
+        self.with_features(features, n_lags)
+    }
+
+    /// Adds rolling aggregates of every feature over the last `window` rows,
+    /// the current one included.
+    ///
+    /// New columns are named `{feature}_{aggregate}_{window}`. The first
+    /// `window - 1` rows are dropped, the lookback of the Dataset grows by
+    /// the same amount.
+    pub fn rolling(
+        &self,
+        window: usize,
+        aggregates: &[Aggregate],
+    ) -> Result<Dataset, String> {
+        if window == 0 || window > self.len() {
+            return Err(format!(
+                "window must be in [1, {:?}], got {:?}",
+                self.len(),
+                window
+            ));
+        }
+
+        for aggregate in aggregates {
+            if let Aggregate::Ewma(alpha) = aggregate {
+                if alpha.is_nan() || *alpha <= 0.0 || *alpha > 1.0 {
+                    return Err(format!("ewma alpha must be in (0, 1], got {:?}", alpha));
+                }
+            }
+        }
+
+        let warm_up = window - 1;
+        let mut features: Vec<Column> = self
+            .features
+            .iter()
+            .map(|c| Column::new(&c.name, c.values[warm_up..].to_vec()))
+            .collect();
+
+        for c in self.features.iter() {
+            for aggregate in aggregates {
+                features.push(Column::new(
+                    &format!("{}_{}_{}", c.name, aggregate.label(), window),
+                    aggregate.compute(&c.values, window),
+                ));
+            }
+        }
+
+        self.with_features(features, warm_up)
+    }
+
+    /// Aligns the features at t with the targets at t+`horizon`.
+    ///
+    /// The last `horizon` rows have no target and are dropped, the index
+    /// keeps the timestamps of the features. The horizon of the Dataset
+    /// grows by `horizon`.
+    pub fn align_horizon(&self, horizon: usize) -> Result<Dataset, String> {
+        if horizon >= self.len() {
+            return Err(format!(
+                "horizon {:?} must be lower than the {:?} rows",
+                horizon,
+                self.len()
+            ));
+        }
+
+        let n_rows = self.len() - horizon;
+
+        Ok(Dataset {
+            index: self.index.iter().take(n_rows).copied().collect(),
+            features: self
+                .features
+                .iter()
+                .map(|c| Column::new(&c.name, c.values[..n_rows].to_vec()))
+                .collect(),
+            targets: self
+                .targets
+                .iter()
+                .map(|c| Column::new(&c.name, c.values[horizon..].to_vec()))
+                .collect(),
+            selected_target: self.selected_target,
+            lookback: self.lookback,
+            horizon: self.horizon + horizon,
+        })
+    }
+
+    /// Sequences of `window` consecutive rows, as a features tensor of size
+    /// [n_sequences, window, n_features] and a target tensor of size
+    /// [n_sequences] with the target of the last row of each sequence.
+    pub fn to_sequences(&self, window: usize) -> Result<(Tensor, Tensor), String> {
+        if window == 0 || window > self.len() {
+            return Err(format!(
+                "window must be in [1, {:?}], got {:?}",
+                self.len(),
+                window
+            ));
+        }
+
+        let n_sequences = self.len() - window + 1;
+        let n_features = self.feature_count();
+
+        let mut flat_features = Vec::with_capacity(n_sequences * window * n_features);
+        for start in 0..n_sequences {
+            for row in start..start + window {
+                flat_features.extend(self.features.iter().map(|c| c.values[row]));
+            }
+        }
+
+        let features = Tensor::from_slice(&flat_features)
+            .reshape([n_sequences as i64, window as i64, n_features as i64])
+            .to_kind(Kind::Float);
+
+        let targets = Tensor::from_slice(&self.target().values[window - 1..])
+            .reshape([n_sequences as i64])
+            .to_kind(Kind::Float);
+
+        Ok((features, targets))
+    }
+
+    /// Keeps the last rows, as many as in `features`, with the new columns.
+    fn with_features(
+        &self,
+        features: Vec<Column>,
+        dropped: usize,
+    ) -> Result<Dataset, String> {
+        let mut names: Vec<&str> = features.iter().map(|c| c.name.as_str()).collect();
+        names.sort();
+        if let Some(pair) = names.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(format!("duplicated feature name {:?}", pair[0]));
+        }
+
+        Ok(Dataset {
+            index: self.index.iter().skip(dropped).copied().collect(),
+            features,
+            targets: self
+                .targets
+                .iter()
+                .map(|c| Column::new(&c.name, c.values[dropped..].to_vec()))
+                .collect(),
+            selected_target: self.selected_target,
+            lookback: self.lookback + dropped,
+            horizon: self.horizon,
+        })
+    }
+}
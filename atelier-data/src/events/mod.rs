@@ -0,0 +1,254 @@
+use crate::{
+    levels::Level,
+    orderbooks::Orderbook,
+    orders::{Order, OrderSide, OrderType},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+};
+
+// ----------------------------------------------------------------------- L3 EVENT -- //
+// ----------------------------------------------------------------------- -------- -- //
+
+/// What happened to a single order.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EventKind {
+    /// A new limit order rests in the book.
+    Submit,
+    /// Part of the size of a resting order is cancelled.
+    Cancel,
+    /// A resting order is removed, with all its remaining size.
+    Delete,
+    /// Part or all of a visible resting order is executed.
+    Execute,
+    /// A hidden order is executed, the visible book does not change.
+    ExecuteHidden,
+    /// Auction or cross trade, the visible book does not change.
+    Cross,
+    /// Trading halt or resume.
+    Halt,
+}
+
+/// Order level (L3) event.
+///
+/// `side` is the side of the resting order the event applies to, e.g. an
+/// `Execute` on the Asks side is a buy market order hitting the asks.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct L3Event {
+    pub event_ts: u64,
+    pub kind: EventKind,
+    pub order_id: u64,
+    pub side: OrderSide,
+    pub price: f64,
+    pub size: f64,
+}
+
+// ------------------------------------------------------------------------ L3 BOOK -- //
+// ------------------------------------------------------------------------ ------- -- //
+
+/// Price usable as an ordered map key.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct Price(pub(crate) f64);
+
+impl Eq for Price {}
+
+impl PartialOrd for Price {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Price {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// Order resting in an `L3Book`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Resting {
+    order_id: u64,
+    order_ts: u64,
+    size: f64,
+}
+
+/// Order book state rebuilt from `L3Event`s.
+///
+/// Levels keep their orders in time priority. Books usually start with
+/// orders placed before the first event, `seed_level` adds their aggregated
+/// size as an anonymous order (with id 0), events on orders that are not
+/// known are then taken from it.
+#[derive(Debug, Clone, Default)]
+pub struct L3Book {
+    bids: BTreeMap<Price, Vec<Resting>>,
+    asks: BTreeMap<Price, Vec<Resting>>,
+    orders: HashMap<u64, (OrderSide, Price)>,
+}
+
+/// Sizes below this are considered fully consumed.
+const EPSILON: f64 = 1e-9;
+
+impl L3Book {
+    pub fn new() -> Self {
+        L3Book::default()
+    }
+
+    fn side_mut(&mut self, side: OrderSide) -> &mut BTreeMap<Price, Vec<Resting>> {
+        match side {
+            OrderSide::Bids => &mut self.bids,
+            OrderSide::Asks => &mut self.asks,
+        }
+    }
+
+    /// Adds the aggregated size of unknown orders at a price level.
+    pub fn seed_level(&mut self, side: OrderSide, price: f64, size: f64, ts: u64) {
+        self.side_mut(side)
+            .entry(Price(price))
+            .or_default()
+            .push(Resting {
+                order_id: 0,
+                order_ts: ts,
+                size,
+            });
+    }
+
+    /// Number of orders (seeded ones included) in the book.
+    pub fn n_orders(&self) -> usize {
+        self.bids
+            .values()
+            .chain(self.asks.values())
+            .map(|level| level.len())
+            .sum()
+    }
+
+    /// Applies an event to the book.
+    ///
+    /// Returns an error when the event cannot be matched to any resting
+    /// size, e.g. a cancel of an unknown order at a price without seeded
+    /// size, the book is left unchanged then.
+    pub fn apply(&mut self, event: &L3Event) -> Result<(), String> {
+        match event.kind {
+            EventKind::Submit => {
+                if self.orders.contains_key(&event.order_id) {
+                    return Err(format!("order {} already in the book", event.order_id));
+                }
+                self.orders
+                    .insert(event.order_id, (event.side, Price(event.price)));
+                self.side_mut(event.side)
+                    .entry(Price(event.price))
+                    .or_default()
+                    .push(Resting {
+                        order_id: event.order_id,
+                        order_ts: event.event_ts,
+                        size: event.size,
+                    });
+                Ok(())
+            }
+            EventKind::Cancel | EventKind::Execute => self.reduce(event, event.size),
+            EventKind::Delete => self.reduce(event, f64::INFINITY),
+            EventKind::ExecuteHidden | EventKind::Cross | EventKind::Halt => Ok(()),
+        }
+    }
+
+    /// Takes `size` from the order of the event, or from the seeded size at
+    /// its price when the order is unknown.
+    fn reduce(&mut self, event: &L3Event, size: f64) -> Result<(), String> {
+        let (side, price, order_id) = match self.orders.get(&event.order_id) {
+            Some((side, price)) => (*side, *price, event.order_id),
+            None => (event.side, Price(event.price), 0),
+        };
+
+        let levels = match side {
+            OrderSide::Bids => &mut self.bids,
+            OrderSide::Asks => &mut self.asks,
+        };
+        let level = levels.get_mut(&price).ok_or(format!(
+            "no level at {} for order {}",
+            price.0, event.order_id
+        ))?;
+        let position = level
+            .iter()
+            .position(|o| o.order_id == order_id)
+            .ok_or(format!("order {} not found at {}", event.order_id, price.0))?;
+
+        // Deleting an unknown order only removes the size of the event
+        let size = if order_id == 0 && size.is_infinite() {
+            event.size
+        } else {
+            size
+        };
+
+        level[position].size -= size.min(level[position].size);
+        if level[position].size <= EPSILON {
+            level.remove(position);
+            if order_id != 0 {
+                self.orders.remove(&order_id);
+            }
+        }
+        if level.is_empty() {
+            levels.remove(&price);
+        }
+
+        Ok(())
+    }
+
+    /// Aggregated (price, volume) of the top `depth` levels of one side.
+    pub fn top_levels(&self, side: OrderSide, depth: usize) -> Vec<(f64, f64)> {
+        let aggregate = |(price, level): (&Price, &Vec<Resting>)| {
+            (price.0, level.iter().map(|o| o.size).sum())
+        };
+
+        match side {
+            OrderSide::Bids => {
+                self.bids.iter().rev().take(depth).map(aggregate).collect()
+            }
+            OrderSide::Asks => self.asks.iter().take(depth).map(aggregate).collect(),
+        }
+    }
+
+    /// Snapshot of the top `depth` levels of each side, with their orders.
+    pub fn to_orderbook(
+        &self,
+        orderbook_id: u32,
+        orderbook_ts: u64,
+        symbol: &str,
+        depth: usize,
+    ) -> Orderbook {
+        let to_level =
+            |side: OrderSide, i: usize, (price, resting): (&Price, &Vec<Resting>)| {
+                let orders: Vec<Order> = resting
+                    .iter()
+                    .map(|o| Order {
+                        order_id: o.order_id,
+                        order_ts: o.order_ts,
+                        order_type: OrderType::Limit,
+                        side,
+                        price: Some(price.0),
+                        amount: Some(o.size),
+                    })
+                    .collect();
+                let volume = resting.iter().map(|o| o.size).sum();
+                Level::new(i as u32, side, price.0, volume, orders)
+            };
+
+        let bids = self
+            .bids
+            .iter()
+            .rev()
+            .take(depth)
+            .enumerate()
+            .map(|(i, level)| to_level(OrderSide::Bids, i, level))
+            .collect();
+        let asks = self
+            .asks
+            .iter()
+            .take(depth)
+            .enumerate()
+            .map(|(i, level)| to_level(OrderSide::Asks, i, level))
+            .collect();
+
+        Orderbook::new(orderbook_id, orderbook_ts, symbol.to_string(), bids, asks)
+    }
+}
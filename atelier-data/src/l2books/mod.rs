@@ -0,0 +1,250 @@
+use crate::{
+    levels::Level,
+    orderbooks::Orderbook,
+    orders::OrderSide,
+    views::{BookSlice, BookView, LevelView},
+};
+
+use rand::{distr::Uniform, Rng};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// ---------------------------------------------------------------------- L2 LEVELS -- //
+// ---------------------------------------------------------------------- --------- -- //
+
+/// One side of an L2 order book, in a structure-of-arrays layout.
+///
+/// The i-th element of `prices`, `volumes` and `counts` describe the i-th
+/// level, index 0 being the top of the book. No individual orders are kept.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct L2Levels {
+    pub prices: Vec<f64>,
+    pub volumes: Vec<f64>,
+    pub counts: Vec<u32>,
+}
+
+impl L2Levels {
+    pub fn new() -> Self {
+        L2Levels::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        L2Levels {
+            prices: Vec::with_capacity(capacity),
+            volumes: Vec::with_capacity(capacity),
+            counts: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Appends a level at the bottom of this side.
+    pub fn push(&mut self, price: f64, volume: f64, count: u32) {
+        self.prices.push(price);
+        self.volumes.push(volume);
+        self.counts.push(count);
+    }
+
+    pub fn len(&self) -> usize {
+        self.prices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prices.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<LevelView> {
+        Some(LevelView {
+            price: *self.prices.get(index)?,
+            volume: *self.volumes.get(index)?,
+            count: *self.counts.get(index)?,
+        })
+    }
+
+    /// Aggregates L3 levels into their L2 representation.
+    pub fn from_levels(levels: &[Level]) -> Self {
+        let mut l2_levels = L2Levels::with_capacity(levels.len());
+        for level in levels {
+            l2_levels.push(level.price, level.volume, level.orders.len() as u32);
+        }
+        l2_levels
+    }
+}
+
+// ------------------------------------------------------------------- L2 ORDERBOOK -- //
+// ------------------------------------------------------------------- ------------ -- //
+
+/// Compact, L2-only order book.
+///
+/// Holds price, volume and order count per level, without the individual
+/// `Order`s that the L3 `Orderbook` carries in every `Level`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct L2Orderbook {
+    pub orderbook_id: u32,
+    pub orderbook_ts: u64,
+    pub symbol: String,
+    pub bids: L2Levels,
+    pub asks: L2Levels,
+}
+
+impl L2Orderbook {
+    /// Creates a new instance of `L2Orderbook`.
+    ///
+    /// # Parameters
+    ///
+    /// - `orderbook_id`: The unique identifier for the order book.
+    /// - `orderbook_ts`: The timestamp for the order book.
+    /// - `symbol`: The trading symbol for the order book.
+    /// - `bids`: The L2 levels of the buy side.
+    /// - `asks`: The L2 levels of the sell side.
+    pub fn new(
+        orderbook_id: u32,
+        orderbook_ts: u64,
+        symbol: String,
+        bids: L2Levels,
+        asks: L2Levels,
+    ) -> Self {
+        L2Orderbook {
+            orderbook_id,
+            orderbook_ts,
+            symbol,
+            bids,
+            asks,
+        }
+    }
+
+    /// Generates a synthetic L2 order book.
+    ///
+    /// Takes the same parameters as `Orderbook::random`, level volumes are
+    /// sampled as the sum of the random amounts of each (non-materialized)
+    /// order, so no `Order` is allocated.
+    pub fn random(
+        bids_price: f64,
+        bids_levels: Option<(u32, u32)>,
+        bids_orders: Option<(u32, u32)>,
+
+        tick_size: Option<(f64, f64)>,
+
+        asks_price: f64,
+        asks_levels: Option<(u32, u32)>,
+        asks_orders: Option<(u32, u32)>,
+    ) -> Self {
+        let orderbook_ts = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_micros() as u64;
+
+        let bids = Self::random_side(
+            OrderSide::Bids,
+            bids_price,
+            bids_levels,
+            bids_orders,
+            tick_size,
+        );
+        let asks = Self::random_side(
+            OrderSide::Asks,
+            asks_price,
+            asks_levels,
+            asks_orders,
+            tick_size,
+        );
+
+        L2Orderbook {
+            orderbook_id: 1234,
+            orderbook_ts,
+            symbol: String::from("BTCUSDT"),
+            bids,
+            asks,
+        }
+    }
+
+    /// Borrowed view over the first `n_levels` of each side.
+    pub fn slice(&self, n_levels: usize) -> BookSlice<'_> {
+        BookSlice::from_l2(
+            self.orderbook_ts,
+            &self.symbol,
+            &self.bids,
+            &self.asks,
+            n_levels,
+        )
+    }
+
+    fn random_side(
+        side: OrderSide,
+        top_price: f64,
+        levels: Option<(u32, u32)>,
+        orders: Option<(u32, u32)>,
+        tick_size: Option<(f64, f64)>,
+    ) -> L2Levels {
+        let mut rng = rand::rng();
+
+        let (min_levels, max_levels) = levels.expect("Missing levels range");
+        let n_levels = rng.sample(
+            Uniform::new(min_levels, max_levels)
+                .expect("Failed to create random n_levels"),
+        );
+
+        let (min_tick, max_tick) = tick_size.unwrap_or((0.0, 1.0));
+        let ticks = Uniform::new(min_tick, max_tick).expect("Failed to create distr");
+        let amounts = Uniform::new(0.001, 0.100).expect("Failed to create distr");
+
+        let mut l2_levels = L2Levels::with_capacity(n_levels as usize);
+        let mut price = top_price;
+
+        for i in 0..n_levels {
+            if i > 0 {
+                price = match side {
+                    OrderSide::Bids => price - rng.sample(ticks),
+                    OrderSide::Asks => price + rng.sample(ticks),
+                };
+            }
+
+            let count = match orders {
+                Some((min_orders, max_orders)) => {
+                    rng.random_range(min_orders..max_orders)
+                }
+                None => rng.random_range(1..5),
+            };
+
+            let volume: f64 = (0..count).map(|_| rng.sample(amounts)).sum();
+
+            l2_levels.push(price, volume, count);
+        }
+
+        l2_levels
+    }
+}
+
+impl From<&Orderbook> for L2Orderbook {
+    fn from(orderbook: &Orderbook) -> Self {
+        L2Orderbook {
+            orderbook_id: orderbook.orderbook_id,
+            orderbook_ts: orderbook.orderbook_ts,
+            symbol: orderbook.symbol.clone(),
+            bids: L2Levels::from_levels(&orderbook.bids),
+            asks: L2Levels::from_levels(&orderbook.asks),
+        }
+    }
+}
+
+impl BookView for L2Orderbook {
+    fn timestamp(&self) -> u64 {
+        self.orderbook_ts
+    }
+
+    fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    fn depth(&self, side: OrderSide) -> usize {
+        match side {
+            OrderSide::Bids => self.bids.len(),
+            OrderSide::Asks => self.asks.len(),
+        }
+    }
+
+    fn level(&self, side: OrderSide, index: usize) -> Option<LevelView> {
+        match side {
+            OrderSide::Bids => self.bids.get(index),
+            OrderSide::Asks => self.asks.get(index),
+        }
+    }
+}
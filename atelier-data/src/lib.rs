@@ -12,8 +12,23 @@ pub mod data;
 /// Implementation of orders
 pub mod orders;
 
+/// Executed trades and their alignment with Orderbooks.
+pub mod trades;
+
 /// Orders-Price-Volume levels for Orderbooks.
 pub mod levels;
 
+/// Order level (L3) events and the book state rebuilt from them.
+pub mod events;
+
 /// Single thread Orderbook structure.
 pub mod orderbooks;
+
+/// Compact L2-only Orderbook structure.
+pub mod l2books;
+
+/// Single writer, multiple readers shared Orderbook.
+pub mod shared;
+
+/// Common read access over Orderbook representations.
+pub mod views;
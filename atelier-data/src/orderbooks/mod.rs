@@ -1,6 +1,7 @@
 use crate::{
     levels::Level,
     orders::{Order, OrderSide, OrderType},
+    views::{BookView, LevelView},
 };
 
 use atelier_results::errors::{LevelError, OrderError};
@@ -759,3 +760,33 @@ impl Orderbook {
         }
     }
 }
+
+impl BookView for Orderbook {
+    fn timestamp(&self) -> u64 {
+        self.orderbook_ts
+    }
+
+    fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    fn depth(&self, side: OrderSide) -> usize {
+        match side {
+            OrderSide::Bids => self.bids.len(),
+            OrderSide::Asks => self.asks.len(),
+        }
+    }
+
+    fn level(&self, side: OrderSide, index: usize) -> Option<LevelView> {
+        let levels = match side {
+            OrderSide::Bids => &self.bids,
+            OrderSide::Asks => &self.asks,
+        };
+
+        levels.get(index).map(|level| LevelView {
+            price: level.price,
+            volume: level.volume,
+            count: level.orders.len() as u32,
+        })
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{
+    orders::OrderSide,
+    views::{BookView, LevelView},
+};
+
+use arc_swap::ArcSwap;
+use std::{ops::Deref, sync::Arc};
+
+// ------------------------------------------------------------------ BOOK SNAPSHOT -- //
+// ------------------------------------------------------------------ ------------- -- //
+
+/// An immutable, published state of a shared book.
+///
+/// `sequence` starts at 0 with the initial book and is increased by one on
+/// every publication, so readers can tell whether the book changed between
+/// two reads.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookSnapshot<B> {
+    pub sequence: u64,
+    pub book: B,
+}
+
+impl<B> Deref for BookSnapshot<B> {
+    type Target = B;
+
+    fn deref(&self) -> &B {
+        &self.book
+    }
+}
+
+impl<B: BookView> BookView for BookSnapshot<B> {
+    fn timestamp(&self) -> u64 {
+        self.book.timestamp()
+    }
+
+    fn symbol(&self) -> &str {
+        self.book.symbol()
+    }
+
+    fn depth(&self, side: OrderSide) -> usize {
+        self.book.depth(side)
+    }
+
+    fn level(&self, side: OrderSide, index: usize) -> Option<LevelView> {
+        self.book.level(side, index)
+    }
+}
+
+// -------------------------------------------------------------------- SHARED BOOK -- //
+// -------------------------------------------------------------------- ----------- -- //
+
+/// Creates a single writer, multiple readers order book.
+///
+/// The writer mutates a private staging copy of the book and publishes it as
+/// a new immutable snapshot, swapped in atomically. Readers never block the
+/// writer nor each other: a read is a lock-free load of the latest snapshot,
+/// which stays valid (and unchanged) for as long as the reader holds it.
+///
+/// Returns the unique writer and a first reader, more readers are obtained
+/// by cloning a reader or from the writer. Works with any book
+/// representation, e.g. `Orderbook` or `L2Orderbook`.
+pub fn shared_book<B: Clone>(book: B) -> (BookWriter<B>, BookReader<B>) {
+    let current = Arc::new(ArcSwap::from_pointee(BookSnapshot {
+        sequence: 0,
+        book: book.clone(),
+    }));
+
+    let writer = BookWriter {
+        current: Arc::clone(&current),
+        staging: book,
+        sequence: 0,
+    };
+
+    (writer, BookReader { current })
+}
+
+// -------------------------------------------------------------------- BOOK WRITER -- //
+// -------------------------------------------------------------------- ----------- -- //
+
+/// The unique writing end of a shared book.
+///
+/// Not `Clone`, so there is exactly one writer per shared book.
+#[derive(Debug)]
+pub struct BookWriter<B> {
+    current: Arc<ArcSwap<BookSnapshot<B>>>,
+    staging: B,
+    sequence: u64,
+}
+
+impl<B: Clone> BookWriter<B> {
+    /// Mutable access to the staging book, changes are not visible to the
+    /// readers until `publish` is called. Use it to apply a batch of events
+    /// and publish them as a single consistent state.
+    pub fn staging(&mut self) -> &mut B {
+        &mut self.staging
+    }
+
+    /// Publishes the current staging book as the new snapshot, returns its
+    /// sequence number.
+    pub fn publish(&mut self) -> u64 {
+        self.sequence += 1;
+        self.current.store(Arc::new(BookSnapshot {
+            sequence: self.sequence,
+            book: self.staging.clone(),
+        }));
+        self.sequence
+    }
+
+    /// Applies `update` to the staging book and publishes the result.
+    pub fn update<R, F: FnOnce(&mut B) -> R>(&mut self, update: F) -> R {
+        let result = update(&mut self.staging);
+        self.publish();
+        result
+    }
+
+    /// Replaces the staging book and publishes it.
+    pub fn replace(&mut self, book: B) -> u64 {
+        self.staging = book;
+        self.publish()
+    }
+
+    /// Sequence number of the last published snapshot.
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// A new reader of this shared book.
+    pub fn reader(&self) -> BookReader<B> {
+        BookReader {
+            current: Arc::clone(&self.current),
+        }
+    }
+}
+
+// -------------------------------------------------------------------- BOOK READER -- //
+// -------------------------------------------------------------------- ----------- -- //
+
+/// A reading end of a shared book, cheap to clone and send across threads.
+#[derive(Debug)]
+pub struct BookReader<B> {
+    current: Arc<ArcSwap<BookSnapshot<B>>>,
+}
+
+impl<B> Clone for BookReader<B> {
+    fn clone(&self) -> Self {
+        BookReader {
+            current: Arc::clone(&self.current),
+        }
+    }
+}
+
+impl<B> BookReader<B> {
+    /// The latest published snapshot.
+    ///
+    /// The returned snapshot is owned by the caller, it can be kept for any
+    /// amount of time and never changes, even if the writer keeps publishing.
+    pub fn snapshot(&self) -> Arc<BookSnapshot<B>> {
+        self.current.load_full()
+    }
+
+    /// Runs `read` over the latest published snapshot.
+    ///
+    /// Cheaper than `snapshot` for short reads, the snapshot is only borrowed
+    /// for the duration of the closure.
+    pub fn read<R, F: FnOnce(&BookSnapshot<B>) -> R>(&self, read: F) -> R {
+        let guard = self.current.load();
+        read(&guard)
+    }
+
+    /// Sequence number of the latest published snapshot.
+    pub fn sequence(&self) -> u64 {
+        self.current.load().sequence
+    }
+}
@@ -1,16 +1,56 @@
-use serde::Deserialize;
-use std::{error::Error, fs};
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{InstanceType, Schema, SchemaObject},
+    JsonSchema,
+};
+use serde::{Deserialize, Serialize};
+use std::{error::Error, fmt, fs};
+use sweeps::SweepConfig;
 use toml;
 
-#[derive(Debug, Deserialize, Clone)]
+/// Parameter sweeps of experiments
+pub mod sweeps;
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
 pub enum Models {
+    #[serde(alias = "uniform")]
     Uniform,
+    #[serde(alias = "gbm")]
     GBM,
+    #[serde(alias = "hawkes")]
     Hawkes,
+    #[serde(alias = "gd")]
     GD,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+impl JsonSchema for Models {
+    fn schema_name() -> String {
+        "Models".to_string()
+    }
+
+    /// Name of every model along with its lowercase alias, which the
+    /// derived schema would leave out.
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        let names = [
+            ("Uniform", "uniform"),
+            ("GBM", "gbm"),
+            ("Hawkes", "hawkes"),
+            ("GD", "gd"),
+        ];
+        Schema::Object(SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            enum_values: Some(
+                names
+                    .iter()
+                    .flat_map(|(name, alias)| [(*name).into(), (*alias).into()])
+                    .collect(),
+            ),
+            ..Default::default()
+        })
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 pub struct Config {
     pub experiments: Vec<ExpConfig>,
     pub exchanges: Vec<ExchangeConfig>,
@@ -23,15 +63,101 @@ impl Config {
         let config: Config = toml::from_str(&contents)?;
         Ok(config)
     }
+
+    /// Config with the defaults filled in, for it to be written next to the
+    /// outputs of a run.
+    ///
+    /// The parameters of each model are given by name, with the defaults
+    /// of `ModelParams` (e.g. the `dt` of a GBM), instead of by position.
+    /// Models with invalid parameters are kept as they are.
+    pub fn resolved(&self) -> Config {
+        let mut config = self.clone();
+        for model in config.models.iter_mut() {
+            if let Ok(params) = model.model_params() {
+                model.params = Some(params);
+                model.params_labels = None;
+                model.params_values = None;
+            }
+        }
+        config
+    }
+
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string(self)
+    }
+
+    /// JSON Schema of the config files, e.g. for editors to autocomplete
+    /// and check them with a `#:schema config.schema.json` first line.
+    pub fn json_schema() -> String {
+        serde_json::to_string_pretty(&schemars::schema_for!(Config)).unwrap()
+    }
+
+    /// Checks every experiment, exchange and model, returning all the
+    /// problems found instead of stopping at the first one.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut issues = Vec::new();
+
+        for (name, len) in [
+            ("experiments", self.experiments.len()),
+            ("exchanges", self.exchanges.len()),
+            ("models", self.models.len()),
+        ] {
+            if len == 0 {
+                issues.push(ConfigIssue::new(name, "must have at least one entry"));
+            }
+        }
+
+        for (i, experiment) in self.experiments.iter().enumerate() {
+            let path = format!("experiments[{}]", i);
+            let n_issues = issues.len();
+            experiment.check(&path, &mut issues);
+
+            // Sweeps are only expanded once their values are valid
+            if issues.len() == n_issues {
+                if let Err(e) = self.expand_experiment(experiment) {
+                    issues.push(ConfigIssue::new(&field(&path, "sweeps"), &e));
+                }
+            }
+        }
+        for (i, exchange) in self.exchanges.iter().enumerate() {
+            exchange.check(&format!("exchanges[{}]", i), &mut issues);
+        }
+        for (i, model) in self.models.iter().enumerate() {
+            model.check(&format!("models[{}]", i), &mut issues);
+        }
+
+        ConfigError::from_issues(issues)
+    }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+/// Experiment, run once or once per combination of the values of its
+/// sweeps, see `Config::expand_experiment`.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 pub struct ExpConfig {
     pub id: String,
     pub n_progressions: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sweeps: Vec<SweepConfig>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+impl ExpConfig {
+    fn check(&self, path: &str, issues: &mut Vec<ConfigIssue>) {
+        if self.id.trim().is_empty() {
+            issues.push(ConfigIssue::new(&field(path, "id"), "must not be empty"));
+        }
+        if self.n_progressions < 2 {
+            issues.push(ConfigIssue::new(
+                &field(path, "n_progressions"),
+                "must be at least 2",
+            ));
+        }
+        for (i, sweep) in self.sweeps.iter().enumerate() {
+            sweep.check(&format!("{}.sweeps[{}]", path, i), issues);
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 pub struct ExchangeConfig {
     pub id: String,
     pub region: String,
@@ -40,28 +166,229 @@ pub struct ExchangeConfig {
     pub orderbook: Option<OrderbookConfig>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+impl ExchangeConfig {
+    fn check(&self, path: &str, issues: &mut Vec<ConfigIssue>) {
+        if self.id.trim().is_empty() {
+            issues.push(ConfigIssue::new(&field(path, "id"), "must not be empty"));
+        }
+        match &self.orderbook {
+            Some(orderbook) => orderbook.check(&field(path, "orderbook"), issues),
+            None => {
+                issues.push(ConfigIssue::new(&field(path, "orderbook"), "is missing"))
+            }
+        }
+    }
+}
+
+/// Model of a `[[models]]` table.
+///
+/// Parameters are given by name, next to the label:
+///
+/// ```toml
+/// [[models]]
+/// id = "returns_00"
+/// label = "GBM"
+/// mu = 0.01
+/// sigma = 0.001
+/// ```
+///
+/// or, as in older files, with `params_values` read by position, or by
+/// name when `params_labels` has the names of the parameters. Named
+/// parameters are kept in `params`, use `model_params` to get them from
+/// either form.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(try_from = "RawModelConfig", into = "RawModelConfig")]
 pub struct ModelConfig {
     pub id: Option<String>,
     pub label: Option<Models>,
     pub description: Option<String>,
     pub params_labels: Option<Vec<String>>,
     pub params_values: Option<Vec<f64>>,
+    pub params: Option<ModelParams>,
+}
+
+/// Fields of a `[[models]]` table, every other key being a parameter.
+#[derive(Deserialize, Serialize, JsonSchema)]
+struct RawModelConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<Models>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    params_labels: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    params_values: Option<Vec<f64>>,
+    #[serde(flatten)]
+    #[schemars(skip)]
+    params: toml::Table,
+}
+
+impl From<ModelConfig> for RawModelConfig {
+    fn from(model: ModelConfig) -> Self {
+        let params = model
+            .params
+            .iter()
+            .flat_map(ModelParams::values)
+            .map(|(name, value)| (name.to_string(), value.into()))
+            .collect();
+
+        RawModelConfig {
+            id: model.id,
+            label: model.label,
+            description: model.description,
+            params_labels: model.params_labels,
+            params_values: model.params_values,
+            params,
+        }
+    }
+}
+
+impl JsonSchema for ModelConfig {
+    fn schema_name() -> String {
+        "ModelConfig".to_string()
+    }
+
+    /// Fields of `RawModelConfig` along with the name of every parameter.
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let mut schema = RawModelConfig::json_schema(gen).into_object();
+        schema.metadata().description =
+            Some("Model, with its parameters by name or in params_values".to_string());
+
+        for label in [Models::Uniform, Models::GBM, Models::Hawkes, Models::GD] {
+            for name in ModelParams::names(label).0 {
+                schema
+                    .object()
+                    .properties
+                    .insert(name.to_string(), gen.subschema_for::<f64>());
+            }
+        }
+        Schema::Object(schema)
+    }
+}
+
+impl TryFrom<RawModelConfig> for ModelConfig {
+    type Error = String;
+
+    fn try_from(raw: RawModelConfig) -> Result<Self, Self::Error> {
+        let id = raw.id.as_deref().unwrap_or("unnamed");
+        let params = if raw.params.is_empty() {
+            None
+        } else {
+            if raw.params_values.is_some() {
+                return Err(format!(
+                    "model {}: parameters given both by name and in params_values",
+                    id
+                ));
+            }
+            let label = raw
+                .label
+                .ok_or(format!("model {}: named parameters need a label", id))?;
+            Some(
+                ModelParams::from_named(label, raw.params)
+                    .map_err(|e| format!("model {}: {}", id, e))?,
+            )
+        };
+
+        Ok(ModelConfig {
+            id: raw.id,
+            label: raw.label,
+            description: raw.description,
+            params_labels: raw.params_labels,
+            params_values: raw.params_values,
+            params,
+        })
+    }
 }
 
 impl ModelConfig {
     pub fn builder() -> ModelConfigBuilder {
         ModelConfigBuilder::new()
     }
+
+    /// Parameters of the model, the named ones or else the ones of
+    /// `params_values`, see `ModelParams::from_values`.
+    pub fn model_params(&self) -> Result<ModelParams, String> {
+        if let Some(params) = self.params {
+            return Ok(params);
+        }
+        let label = self.label.ok_or("Missing Model's label")?;
+        let values = self
+            .params_values
+            .as_deref()
+            .ok_or("Missing Model's params_values")?;
+        ModelParams::from_values(label, self.params_labels.as_deref(), values)
+    }
+
+    /// Checks the parameters of the model, see `Config::validate`.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut issues = Vec::new();
+        self.check("", &mut issues);
+        ConfigError::from_issues(issues)
+    }
+
+    fn check(&self, path: &str, issues: &mut Vec<ConfigIssue>) {
+        let Some(label) = self.label else {
+            issues.push(ConfigIssue::new(&field(path, "label"), "is missing"));
+            return;
+        };
+
+        if let Some(params) = &self.params {
+            params.check(
+                &|name, message| ConfigIssue::new(&field(path, name), message),
+                issues,
+            );
+            return;
+        }
+
+        let values_path = field(path, "params_values");
+        let Some(values) = &self.params_values else {
+            issues.push(ConfigIssue::new(&values_path, "is missing"));
+            return;
+        };
+
+        if let Some(labels) = &self.params_labels {
+            if labels.len() != values.len() {
+                issues.push(ConfigIssue::new(
+                    &field(path, "params_labels"),
+                    "must have one label per value of params_values",
+                ));
+            }
+        }
+        if values.iter().any(|value| !value.is_finite()) {
+            issues.push(ConfigIssue::new(&values_path, "must be finite numbers"));
+            return;
+        }
+
+        match ModelParams::from_values(label, self.params_labels.as_deref(), values) {
+            Ok(params) => {
+                // Issues point to the position of each value
+                let labels = self.params_labels.as_deref();
+                params.check(
+                    &|name, message| {
+                        let i = ModelParams::position(label, labels, values.len(), name);
+                        ConfigIssue::new(
+                            &format!("{}[{}]", values_path, i),
+                            &format!("{} {}", name, message),
+                        )
+                    },
+                    issues,
+                )
+            }
+            Err(message) => issues.push(ConfigIssue::new(&values_path, &message)),
+        }
+    }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 pub struct ModelConfigBuilder {
     pub id: Option<String>,
     pub label: Option<Models>,
     pub description: Option<String>,
     pub params_labels: Option<Vec<String>>,
     pub params_values: Option<Vec<f64>>,
+    pub params: Option<ModelParams>,
 }
 
 impl ModelConfigBuilder {
@@ -72,6 +399,7 @@ impl ModelConfigBuilder {
             description: None,
             params_labels: None,
             params_values: None,
+            params: None,
         }
     }
 
@@ -100,10 +428,36 @@ impl ModelConfigBuilder {
         self
     }
 
+    /// Named parameters, which also give the label of the model, instead of
+    /// `params_labels` and `params_values`.
+    pub fn params(mut self, params: ModelParams) -> Self {
+        self.params = Some(params);
+        self
+    }
+
     pub fn build(self) -> Result<ModelConfig, &'static str> {
         let id = self.id.ok_or("Missing Model's id")?;
-        let label = self.label.ok_or("Missing Model's label")?;
         let description = self.description.ok_or("Missing Model's description")?;
+
+        if let Some(params) = self.params {
+            if self.label.is_some_and(|label| label != params.label()) {
+                return Err("Model's label does not match its params");
+            }
+            // As when loading, params are not given by name and by position
+            if self.params_labels.is_some() || self.params_values.is_some() {
+                return Err("Model's params given both by name and in params_values");
+            }
+            return Ok(ModelConfig {
+                id: Some(id),
+                label: Some(params.label()),
+                description: Some(description),
+                params_labels: None,
+                params_values: None,
+                params: Some(params),
+            });
+        }
+
+        let label = self.label.ok_or("Missing Model's label")?;
         let params_labels = self.params_labels.ok_or("Missing Model's params_labels")?;
         let params_values = self.params_values.ok_or("Missing Model's params_values")?;
 
@@ -113,11 +467,210 @@ impl ModelConfigBuilder {
             description: Some(description),
             params_labels: Some(params_labels),
             params_values: Some(params_values),
+            params: None,
         })
     }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+// ------------------------------------------------------------------- MODEL PARAMS -- //
+// ------------------------------------------------------------------- ------------ -- //
+
+fn default_dt() -> f64 {
+    0.01
+}
+
+/// Parameters of each model, by name.
+///
+/// `dt`, the time step of the GBM, is 0.01 when not given, and `epsilon`,
+/// the convergence threshold of the GD, is optional.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq)]
+#[serde(tag = "label", deny_unknown_fields)]
+pub enum ModelParams {
+    #[serde(alias = "uniform")]
+    Uniform { lower: f64, upper: f64 },
+    #[serde(alias = "gbm")]
+    GBM {
+        mu: f64,
+        sigma: f64,
+        #[serde(default = "default_dt")]
+        dt: f64,
+    },
+    #[serde(alias = "hawkes")]
+    Hawkes { mu: f64, alpha: f64, beta: f64 },
+    #[serde(alias = "gd")]
+    GD {
+        learning_rate: f64,
+        #[serde(default)]
+        epsilon: Option<f64>,
+    },
+}
+
+impl ModelParams {
+    /// Names of the parameters of a model, in positional order, and how
+    /// many of them are required.
+    pub fn names(label: Models) -> (&'static [&'static str], usize) {
+        match label {
+            Models::Uniform => (&["lower", "upper"], 2),
+            Models::GBM => (&["mu", "sigma", "dt"], 2),
+            Models::Hawkes => (&["mu", "alpha", "beta"], 3),
+            Models::GD => (&["learning_rate", "epsilon"], 1),
+        }
+    }
+
+    pub fn label(&self) -> Models {
+        match self {
+            ModelParams::Uniform { .. } => Models::Uniform,
+            ModelParams::GBM { .. } => Models::GBM,
+            ModelParams::Hawkes { .. } => Models::Hawkes,
+            ModelParams::GD { .. } => Models::GD,
+        }
+    }
+
+    /// Parameters of a model from a table of named values.
+    pub fn from_named(label: Models, mut params: toml::Table) -> Result<Self, String> {
+        params.insert("label".to_string(), format!("{:?}", label).into());
+        toml::Value::Table(params)
+            .try_into()
+            .map_err(|e: toml::de::Error| e.message().to_string())
+    }
+
+    /// Parameters of a model from the legacy `params_values`.
+    ///
+    /// Values are taken by name when `labels` has one name of the model per
+    /// value, and by position otherwise.
+    ///
+    /// # Parameters
+    ///
+    /// - `label`: Model the parameters are for.
+    /// - `labels`: Names of the values, if any.
+    /// - `values`: Values of the parameters.
+    pub fn from_values(
+        label: Models,
+        labels: Option<&[String]>,
+        values: &[f64],
+    ) -> Result<Self, String> {
+        let (names, required) = ModelParams::names(label);
+        if values.len() < required || values.len() > names.len() {
+            let shape = |n: usize| format!("[{}]", names[..n].join(", "));
+            return Err(if required == names.len() {
+                format!("must be {}", shape(required))
+            } else {
+                format!("must be {} or {}", shape(required), shape(names.len()))
+            });
+        }
+
+        let params = names
+            .iter()
+            .filter_map(|name| {
+                let i = ModelParams::position(label, labels, values.len(), name);
+                values
+                    .get(i)
+                    .map(|value| (name.to_string(), (*value).into()))
+            })
+            .collect();
+        ModelParams::from_named(label, params)
+    }
+
+    /// Position in `params_values` of a parameter, see `from_values`.
+    fn position(
+        label: Models,
+        labels: Option<&[String]>,
+        n_values: usize,
+        name: &str,
+    ) -> usize {
+        let names = ModelParams::names(label).0;
+        let by_name = labels.filter(|labels| {
+            labels.len() == n_values && labels.iter().all(|l| names.contains(&l.as_str()))
+        });
+
+        match by_name {
+            Some(labels) => labels.iter().position(|l| l == name),
+            None => names.iter().position(|n| *n == name),
+        }
+        .unwrap_or(n_values)
+    }
+
+    /// Named values, in positional order.
+    pub fn values(&self) -> Vec<(&'static str, f64)> {
+        match *self {
+            ModelParams::Uniform { lower, upper } => {
+                vec![("lower", lower), ("upper", upper)]
+            }
+            ModelParams::GBM { mu, sigma, dt } => {
+                vec![("mu", mu), ("sigma", sigma), ("dt", dt)]
+            }
+            ModelParams::Hawkes { mu, alpha, beta } => {
+                vec![("mu", mu), ("alpha", alpha), ("beta", beta)]
+            }
+            ModelParams::GD {
+                learning_rate,
+                epsilon,
+            } => {
+                let mut values = vec![("learning_rate", learning_rate)];
+                values.extend(epsilon.map(|epsilon| ("epsilon", epsilon)));
+                values
+            }
+        }
+    }
+
+    /// Checks the values, `issue` gives the issue of a parameter.
+    fn check(
+        &self,
+        issue: &dyn Fn(&str, &str) -> ConfigIssue,
+        issues: &mut Vec<ConfigIssue>,
+    ) {
+        let non_finite: Vec<ConfigIssue> = self
+            .values()
+            .into_iter()
+            .filter(|(_, value)| !value.is_finite())
+            .map(|(name, _)| issue(name, "must be a finite number"))
+            .collect();
+        if !non_finite.is_empty() {
+            issues.extend(non_finite);
+            return;
+        }
+
+        match *self {
+            ModelParams::Uniform { lower, upper } => {
+                if upper <= lower {
+                    issues.push(issue("upper", "must exceed lower"));
+                }
+            }
+            ModelParams::GBM { sigma, dt, .. } => {
+                if sigma <= 0.0 {
+                    issues.push(issue("sigma", "must be greater than 0"));
+                }
+                if dt <= 0.0 {
+                    issues.push(issue("dt", "must be greater than 0"));
+                }
+            }
+            ModelParams::Hawkes { mu, alpha, beta } => {
+                if mu <= 0.0 {
+                    issues.push(issue("mu", "must be greater than 0"));
+                }
+                if alpha < 0.0 {
+                    issues.push(issue("alpha", "must not be negative"));
+                }
+                if beta <= alpha {
+                    issues.push(issue("beta", "must exceed alpha"));
+                }
+            }
+            ModelParams::GD {
+                learning_rate,
+                epsilon,
+            } => {
+                if learning_rate <= 0.0 {
+                    issues.push(issue("learning_rate", "must be greater than 0"));
+                }
+                if epsilon.is_some_and(|epsilon| epsilon <= 0.0) {
+                    issues.push(issue("epsilon", "must be greater than 0"));
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 pub struct OrderbookConfig {
     pub bid_price: Option<f64>,
     pub bid_levels: Option<Vec<u32>>,
@@ -133,9 +686,50 @@ impl OrderbookConfig {
     pub fn builder() -> OrderbookConfigBuilder {
         OrderbookConfigBuilder::new()
     }
+
+    /// Checks the prices and [min, max] ranges, see `Config::validate`.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut issues = Vec::new();
+        self.check("", &mut issues);
+        ConfigError::from_issues(issues)
+    }
+
+    fn check(&self, path: &str, issues: &mut Vec<ConfigIssue>) {
+        let mut check_price = |name: &str, price: Option<f64>| match price {
+            None => issues.push(ConfigIssue::new(&field(path, name), "is missing")),
+            Some(price) if !(price > 0.0 && price.is_finite()) => issues.push(
+                ConfigIssue::new(&field(path, name), "must be greater than 0"),
+            ),
+            Some(_) => {}
+        };
+        check_price("bid_price", self.bid_price);
+        check_price("ask_price", self.ask_price);
+
+        if let (Some(bid), Some(ask)) = (self.bid_price, self.ask_price) {
+            if ask <= bid {
+                issues.push(ConfigIssue::new(
+                    &field(path, "ask_price"),
+                    "must exceed bid_price",
+                ));
+            }
+        }
+
+        for (name, range) in [
+            ("bid_levels", &self.bid_levels),
+            ("bid_orders", &self.bid_orders),
+            ("ask_levels", &self.ask_levels),
+            ("ask_orders", &self.ask_orders),
+        ] {
+            let range = range
+                .as_ref()
+                .map(|values| values.iter().map(|v| *v as f64).collect::<Vec<f64>>());
+            check_range(&field(path, name), range.as_deref(), issues);
+        }
+        check_range(&field(path, "ticksize"), self.ticksize.as_deref(), issues);
+    }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 pub struct OrderbookConfigBuilder {
     pub bid_price: Option<f64>,
     pub bid_levels: Option<Vec<u32>>,
@@ -196,6 +790,11 @@ impl OrderbookConfigBuilder {
         self
     }
 
+    pub fn rands(mut self, rands: Vec<f64>) -> Self {
+        self.rands = Some(rands);
+        self
+    }
+
     pub fn build(self) -> Result<OrderbookConfig, &'static str> {
         let bid_price = self.bid_price.ok_or("Missing initial bid price")?;
         let bid_levels = self.bid_levels.ok_or("Missing initial bid levels")?;
@@ -204,7 +803,6 @@ impl OrderbookConfigBuilder {
         let ask_price = self.ask_price.ok_or("Missing initial ask price")?;
         let ask_levels = self.ask_levels.ok_or("Missing initial ask levels")?;
         let ask_orders = self.ask_orders.ok_or("Missing initial ask orders")?;
-        let rands = self.rands.ok_or("Missing initial random numbers")?;
 
         Ok(OrderbookConfig {
             bid_price: Some(bid_price),
@@ -214,7 +812,89 @@ impl OrderbookConfigBuilder {
             ask_price: Some(ask_price),
             ask_levels: Some(ask_levels),
             ask_orders: Some(ask_orders),
-            rands: Some(rands),
+            rands: self.rands,
         })
     }
 }
+
+// --------------------------------------------------------------------- VALIDATION -- //
+// --------------------------------------------------------------------- ---------- -- //
+
+/// Problem found in a configuration, with the TOML path of the value, e.g.
+/// `exchanges[0].orderbook.bid_levels`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigIssue {
+    pub path: String,
+    pub message: String,
+}
+
+impl ConfigIssue {
+    pub fn new(path: &str, message: &str) -> Self {
+        ConfigIssue {
+            path: path.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.path, self.message)
+    }
+}
+
+/// Every problem found when validating a configuration.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigError {
+    pub issues: Vec<ConfigIssue>,
+}
+
+impl ConfigError {
+    fn from_issues(issues: Vec<ConfigIssue>) -> Result<(), ConfigError> {
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError { issues })
+        }
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid configuration, {} problem(s):",
+            self.issues.len()
+        )?;
+        for issue in &self.issues {
+            write!(f, "\n  - {}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ConfigError {}
+
+/// TOML path of a field.
+fn field(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+/// Checks a [min, max] range, with 0 < min < max.
+fn check_range(path: &str, range: Option<&[f64]>, issues: &mut Vec<ConfigIssue>) {
+    match range {
+        None => issues.push(ConfigIssue::new(path, "is missing")),
+        Some([min, max]) if min < max => {
+            if *min <= 0.0 {
+                issues.push(ConfigIssue::new(path, "min must be greater than 0"));
+            }
+        }
+        Some(_) => {
+            issues.push(ConfigIssue::new(path, "must be [min, max] with min < max"))
+        }
+    }
+}
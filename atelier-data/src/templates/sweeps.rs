@@ -0,0 +1,394 @@
+/// Parameter sweeps of experiments
+use crate::templates::{Config, ConfigIssue, ExpConfig, ModelParams};
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+// ------------------------------------------------------------------- SWEEP CONFIG -- //
+// ------------------------------------------------------------------- ------------ -- //
+
+/// Values taken by one parameter of an experiment.
+///
+/// `param` is the path of the parameter, one of:
+///
+/// - `n_progressions`
+/// - `models.<model id>.<parameter name>`, e.g. `models.mod_00.sigma`
+/// - `exchanges.<exchange id>.orderbook.<field>`, e.g.
+///   `exchanges.exc_00.orderbook.ticksize`
+///
+/// and its values are given by exactly one of `values`, `range` or
+/// `log_range`. Sweeps with the same `zip` name move together, and must
+/// have the same number of values, every other sweep is crossed with the
+/// rest.
+///
+/// ```toml
+/// [[experiments.sweeps]]
+/// param = "models.mod_00.sigma"
+/// log_range = { start = 0.001, stop = 0.1, num = 3 }
+///
+/// [[experiments.sweeps]]
+/// param = "exchanges.exc_00.orderbook.bid_levels"
+/// values = [[5, 10], [10, 20]]
+/// zip = "depth"
+/// ```
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+pub struct SweepConfig {
+    pub param: String,
+    #[schemars(with = "Option<Vec<serde_json::Value>>")]
+    pub values: Option<Vec<toml::Value>>,
+    pub range: Option<SweepRange>,
+    pub log_range: Option<SweepLogRange>,
+    pub zip: Option<String>,
+}
+
+/// `start`, `start + step`, ... up to `stop` included.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq)]
+pub struct SweepRange {
+    pub start: f64,
+    pub stop: f64,
+    pub step: f64,
+}
+
+/// `num` values from `start` to `stop`, both included, evenly spaced in
+/// log scale.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq)]
+pub struct SweepLogRange {
+    pub start: f64,
+    pub stop: f64,
+    pub num: usize,
+}
+
+impl SweepConfig {
+    /// Values of the sweep, in order.
+    pub fn sweep_values(&self) -> Result<Vec<toml::Value>, String> {
+        match (&self.values, self.range, self.log_range) {
+            (Some(values), None, None) => Ok(values.clone()),
+            (None, Some(range), None) => {
+                Ok(range.values()?.into_iter().map(Into::into).collect())
+            }
+            (None, None, Some(log_range)) => {
+                Ok(log_range.values().into_iter().map(Into::into).collect())
+            }
+            _ => Err("must have one of values, range or log_range".to_string()),
+        }
+    }
+
+    pub(crate) fn check(&self, path: &str, issues: &mut Vec<ConfigIssue>) {
+        if self.param.trim().is_empty() {
+            issues.push(ConfigIssue::new(
+                &format!("{}.param", path),
+                "must not be empty",
+            ));
+        }
+
+        let n_specs = [
+            self.values.is_some(),
+            self.range.is_some(),
+            self.log_range.is_some(),
+        ]
+        .iter()
+        .filter(|spec| **spec)
+        .count();
+        if n_specs != 1 {
+            issues.push(ConfigIssue::new(
+                path,
+                "must have one of values, range or log_range",
+            ));
+            return;
+        }
+
+        if self.values.as_ref().is_some_and(|values| values.is_empty()) {
+            issues.push(ConfigIssue::new(
+                &format!("{}.values", path),
+                "must not be empty",
+            ));
+        }
+        if let Some(range) = self.range {
+            let path = format!("{}.range", path);
+            if !(range.start.is_finite() && range.stop.is_finite()) {
+                issues.push(ConfigIssue::new(&path, "must be finite numbers"));
+            } else if range.stop < range.start {
+                issues.push(ConfigIssue::new(&path, "stop must not be below start"));
+            }
+            if !(range.step > 0.0 && range.step.is_finite()) {
+                issues.push(ConfigIssue::new(&path, "step must be greater than 0"));
+            }
+        }
+        if let Some(log_range) = self.log_range {
+            let path = format!("{}.log_range", path);
+            let positive = |value: f64| value > 0.0 && value.is_finite();
+            if !(positive(log_range.start) && positive(log_range.stop)) {
+                issues.push(ConfigIssue::new(
+                    &path,
+                    "start and stop must be greater than 0",
+                ));
+            }
+            if log_range.num == 0 {
+                issues.push(ConfigIssue::new(&path, "num must be greater than 0"));
+            }
+        }
+    }
+}
+
+impl SweepRange {
+    /// Values of the range, an error when `start` or `stop` are not finite
+    /// or `step` is not greater than 0.
+    pub fn values(&self) -> Result<Vec<f64>, String> {
+        if !(self.start.is_finite() && self.stop.is_finite()) {
+            return Err("range must be finite numbers".to_string());
+        }
+        if !(self.step > 0.0 && self.step.is_finite()) {
+            return Err("range step must be greater than 0".to_string());
+        }
+
+        // Tolerance for the stop lost to rounding, e.g. 0.1 + 0.2
+        let limit = self.stop + self.step * 1e-9;
+        Ok((0..)
+            .map(|i| self.start + i as f64 * self.step)
+            .take_while(|value| *value <= limit)
+            .collect())
+    }
+}
+
+impl SweepLogRange {
+    pub fn values(&self) -> Vec<f64> {
+        if self.num == 1 {
+            return vec![self.start];
+        }
+        let ratio = self.stop / self.start;
+        (0..self.num)
+            .map(|i| self.start * ratio.powf(i as f64 / (self.num - 1) as f64))
+            .collect()
+    }
+}
+
+// ------------------------------------------------------------ EXPERIMENT INSTANCE -- //
+// ------------------------------------------------------------ ------------------- -- //
+
+/// Concrete run of an experiment, with one value for each swept parameter.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExpInstance {
+    /// `<experiment id>_<index>`, or the experiment id without sweeps.
+    pub id: String,
+    /// Path and value of each swept parameter, in the order of the sweeps.
+    pub values: Vec<(String, toml::Value)>,
+    /// Config with this instance as its only experiment, without sweeps.
+    pub config: Config,
+}
+
+impl Config {
+    /// Runs of every experiment, see `expand_experiment`.
+    pub fn expand(&self) -> Result<Vec<ExpInstance>, String> {
+        let mut instances = Vec::new();
+        for experiment in &self.experiments {
+            instances.extend(self.expand_experiment(experiment)?);
+        }
+        Ok(instances)
+    }
+
+    /// Runs of an experiment, one per combination of the values of its
+    /// sweeps.
+    ///
+    /// Combinations are in the order of the sweeps in the file, the last
+    /// one changing fastest, and numbered from 0 with the same number of
+    /// digits, e.g. `vol_grid_00` to `vol_grid_11`. Ids are stable as long
+    /// as the sweeps are not changed.
+    ///
+    /// # Parameters
+    ///
+    /// - `experiment`: Experiment with the sweeps, and the template of the
+    ///   runs along with the exchanges and models of this config.
+    pub fn expand_experiment(
+        &self,
+        experiment: &ExpConfig,
+    ) -> Result<Vec<ExpInstance>, String> {
+        let axes = sweep_axes(&experiment.sweeps)?;
+        let n_instances: usize = axes.iter().map(|axis| axis.len).product();
+
+        let mut template = self.clone();
+        template.experiments = vec![ExpConfig {
+            sweeps: Vec::new(),
+            ..experiment.clone()
+        }];
+
+        if experiment.sweeps.is_empty() {
+            return Ok(vec![ExpInstance {
+                id: experiment.id.clone(),
+                values: Vec::new(),
+                config: template,
+            }]);
+        }
+
+        let width = (n_instances - 1).max(1).to_string().len();
+        let mut instances = Vec::with_capacity(n_instances);
+
+        for index in 0..n_instances {
+            let id = format!("{}_{:0width$}", experiment.id, index, width = width);
+
+            // Position along each axis, the last axis changing fastest
+            let mut rest = index;
+            let mut positions = vec![0; axes.len()];
+            for (position, axis) in positions.iter_mut().zip(&axes).rev() {
+                *position = rest % axis.len;
+                rest /= axis.len;
+            }
+
+            // Value of each sweep, in the order of the sweeps
+            let mut values: Vec<(usize, &toml::Value)> = axes
+                .iter()
+                .zip(&positions)
+                .flat_map(|(axis, position)| {
+                    axis.sweeps
+                        .iter()
+                        .map(|(i, values)| (*i, &values[*position]))
+                })
+                .collect();
+            values.sort_by_key(|(i, _)| *i);
+
+            let mut config = template.clone();
+            config.experiments[0].id = id.clone();
+            let values = values
+                .into_iter()
+                .map(|(i, value)| {
+                    let param = &experiment.sweeps[i].param;
+                    apply_param(&mut config, param, value)?;
+                    Ok((param.clone(), value.clone()))
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+
+            instances.push(ExpInstance { id, values, config });
+        }
+
+        Ok(instances)
+    }
+}
+
+/// Sweeps that move together, a single one or the ones of a zip, with the
+/// index and values of each of them.
+struct SweepAxis<'a> {
+    zip: Option<&'a str>,
+    sweeps: Vec<(usize, Vec<toml::Value>)>,
+    len: usize,
+}
+
+fn sweep_axes(sweeps: &[SweepConfig]) -> Result<Vec<SweepAxis<'_>>, String> {
+    let mut axes: Vec<SweepAxis> = Vec::new();
+
+    for (i, sweep) in sweeps.iter().enumerate() {
+        let values = sweep
+            .sweep_values()
+            .map_err(|e| format!("sweep of {} {}", sweep.param, e))?;
+        if values.is_empty() {
+            return Err(format!("sweep of {} has no values", sweep.param));
+        }
+
+        let zip = sweep.zip.as_deref();
+        match axes
+            .iter_mut()
+            .find(|axis| zip.is_some() && axis.zip == zip)
+        {
+            Some(axis) => {
+                if values.len() != axis.len {
+                    return Err(format!(
+                        "sweeps zipped as {} must have the same number of values",
+                        zip.unwrap_or_default()
+                    ));
+                }
+                axis.sweeps.push((i, values));
+            }
+            None => axes.push(SweepAxis {
+                zip,
+                len: values.len(),
+                sweeps: vec![(i, values)],
+            }),
+        }
+    }
+
+    Ok(axes)
+}
+
+// ------------------------------------------------------------------- APPLY VALUES -- //
+// ------------------------------------------------------------------- ------------ -- //
+
+fn convert<T: DeserializeOwned>(param: &str, value: &toml::Value) -> Result<T, String> {
+    value
+        .clone()
+        .try_into()
+        .map_err(|e: toml::de::Error| format!("{} {}: {}", param, value, e.message()))
+}
+
+/// Sets a parameter of a config, see `SweepConfig` for the paths.
+fn apply_param(
+    config: &mut Config,
+    param: &str,
+    value: &toml::Value,
+) -> Result<(), String> {
+    let parts: Vec<&str> = param.split('.').collect();
+
+    match parts[..] {
+        ["n_progressions"] => {
+            for experiment in config.experiments.iter_mut() {
+                experiment.n_progressions = convert(param, value)?;
+            }
+        }
+
+        ["models", id, name] => {
+            let model = config
+                .models
+                .iter_mut()
+                .find(|model| model.id.as_deref() == Some(id))
+                .ok_or(format!("{}: no model with id {}", param, id))?;
+            let params = model
+                .model_params()
+                .map_err(|e| format!("{}: {}", param, e))?;
+            if !ModelParams::names(params.label()).0.contains(&name) {
+                return Err(format!(
+                    "{}: {:?} has no parameter {}",
+                    param,
+                    params.label(),
+                    name
+                ));
+            }
+
+            let mut named: toml::Table = params
+                .values()
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value.into()))
+                .collect();
+            named.insert(name.to_string(), value.clone());
+
+            model.params = Some(
+                ModelParams::from_named(params.label(), named)
+                    .map_err(|e| format!("{} {}: {}", param, value, e))?,
+            );
+            model.params_labels = None;
+            model.params_values = None;
+        }
+
+        ["exchanges", id, "orderbook", name] => {
+            let orderbook = config
+                .exchanges
+                .iter_mut()
+                .find(|exchange| exchange.id == id)
+                .ok_or(format!("{}: no exchange with id {}", param, id))?
+                .orderbook
+                .as_mut()
+                .ok_or(format!("{}: exchange {} has no orderbook", param, id))?;
+
+            match name {
+                "bid_price" => orderbook.bid_price = Some(convert(param, value)?),
+                "ask_price" => orderbook.ask_price = Some(convert(param, value)?),
+                "bid_levels" => orderbook.bid_levels = Some(convert(param, value)?),
+                "bid_orders" => orderbook.bid_orders = Some(convert(param, value)?),
+                "ask_levels" => orderbook.ask_levels = Some(convert(param, value)?),
+                "ask_orders" => orderbook.ask_orders = Some(convert(param, value)?),
+                "ticksize" => orderbook.ticksize = Some(convert(param, value)?),
+                "rands" => orderbook.rands = Some(convert(param, value)?),
+                _ => return Err(format!("{}: unknown orderbook field {}", param, name)),
+            }
+        }
+
+        _ => return Err(format!("{}: unknown parameter", param)),
+    }
+
+    Ok(())
+}
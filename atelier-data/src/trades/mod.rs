@@ -0,0 +1,192 @@
+use crate::{data::resample::asof_indices, orders::OrderSide, views::BookView};
+use serde::{Deserialize, Serialize};
+
+// -------------------------------------------------------------------------- TRADE -- //
+// -------------------------------------------------------------------------- ----- -- //
+
+/// Executed trade.
+///
+/// `side` is the side of the aggressor, the incoming order that took
+/// liquidity: `Bids` for a buy lifting the asks, `Asks` for a sell hitting
+/// the bids. Maker and taker order ids are only known in some feeds.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trade {
+    pub trade_id: u64,
+    pub trade_ts: u64,
+    pub side: OrderSide,
+    pub price: f64,
+    pub quantity: f64,
+    #[serde(default)]
+    pub maker_order_id: Option<u64>,
+    #[serde(default)]
+    pub taker_order_id: Option<u64>,
+}
+
+impl Trade {
+    pub fn new(
+        trade_id: u64,
+        trade_ts: u64,
+        side: OrderSide,
+        price: f64,
+        quantity: f64,
+    ) -> Self {
+        Trade {
+            trade_id,
+            trade_ts,
+            side,
+            price,
+            quantity,
+            maker_order_id: None,
+            taker_order_id: None,
+        }
+    }
+
+    /// Sets the ids of the resting (maker) and incoming (taker) orders.
+    pub fn with_order_ids(mut self, maker_order_id: u64, taker_order_id: u64) -> Self {
+        self.maker_order_id = Some(maker_order_id);
+        self.taker_order_id = Some(taker_order_id);
+        self
+    }
+
+    /// Price times quantity.
+    pub fn notional(&self) -> f64 {
+        self.price * self.quantity
+    }
+
+    /// Quantity, positive for buyer initiated trades and negative for seller
+    /// initiated ones.
+    pub fn signed_quantity(&self) -> f64 {
+        match self.side {
+            OrderSide::Bids => self.quantity,
+            OrderSide::Asks => -self.quantity,
+        }
+    }
+}
+
+// --------------------------------------------------------------------- TRADE FLOW -- //
+// --------------------------------------------------------------------- ---------- -- //
+
+/// Executed flow of a group of trades.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct TradeFlow {
+    pub n_trades: usize,
+    pub volume: f64,
+    pub buy_volume: f64,
+    pub sell_volume: f64,
+    pub notional: f64,
+    pub last_price: Option<f64>,
+}
+
+impl TradeFlow {
+    pub fn from_trades(trades: &[Trade]) -> Self {
+        let mut flow = TradeFlow::default();
+
+        for trade in trades {
+            flow.n_trades += 1;
+            flow.volume += trade.quantity;
+            flow.notional += trade.notional();
+            match trade.side {
+                OrderSide::Bids => flow.buy_volume += trade.quantity,
+                OrderSide::Asks => flow.sell_volume += trade.quantity,
+            }
+            flow.last_price = Some(trade.price);
+        }
+
+        flow
+    }
+
+    /// Volume weighted average price, None without volume.
+    pub fn vwap(&self) -> Option<f64> {
+        (self.volume > 0.0).then(|| self.notional / self.volume)
+    }
+
+    /// Buy volume minus sell volume.
+    pub fn net_volume(&self) -> f64 {
+        self.buy_volume - self.sell_volume
+    }
+
+    /// Net volume over total volume, in [-1, 1], 0 without volume.
+    pub fn imbalance(&self) -> f64 {
+        if self.volume > 0.0 {
+            self.net_volume() / self.volume
+        } else {
+            0.0
+        }
+    }
+}
+
+// -------------------------------------------------------------------------- JOINS -- //
+// -------------------------------------------------------------------------- ----- -- //
+
+fn check_sorted<B: BookView>(books: &[B], trades: &[Trade]) -> Result<(), String> {
+    if !books.is_sorted_by_key(|book| book.timestamp()) {
+        return Err("order books are not sorted by timestamp".to_string());
+    }
+    if !trades.is_sorted_by_key(|trade| trade.trade_ts) {
+        return Err("trades are not sorted by timestamp".to_string());
+    }
+    Ok(())
+}
+
+/// Trades executed up to each book, since the previous one.
+///
+/// The trades of book `i` are the ones with a timestamp in
+/// `(ts[i - 1], ts[i]]`, the first book takes every trade up to its
+/// timestamp. Trades after the last book are left out.
+///
+/// # Parameters
+///
+/// - `books`: Order books sorted by timestamp.
+/// - `trades`: Trades sorted by timestamp.
+pub fn trades_per_book<'a, B: BookView>(
+    books: &[B],
+    trades: &'a [Trade],
+) -> Result<Vec<&'a [Trade]>, String> {
+    check_sorted(books, trades)?;
+
+    let mut start = 0;
+    let mut groups = Vec::with_capacity(books.len());
+
+    for book in books {
+        let ts = book.timestamp();
+        let end = start + trades[start..].partition_point(|trade| trade.trade_ts <= ts);
+        groups.push(&trades[start..end]);
+        start = end;
+    }
+
+    Ok(groups)
+}
+
+/// Executed flow up to each book, since the previous one, see
+/// `trades_per_book`.
+pub fn trade_flows<B: BookView>(
+    books: &[B],
+    trades: &[Trade],
+) -> Result<Vec<TradeFlow>, String> {
+    Ok(trades_per_book(books, trades)?
+        .into_iter()
+        .map(TradeFlow::from_trades)
+        .collect())
+}
+
+/// Index of the last book at, or before, the timestamp of each trade.
+///
+/// None for the trades that happened before the first book, or when the
+/// last book is older than `max_staleness`, see `data::resample::asof_indices`.
+///
+/// # Parameters
+///
+/// - `books`: Order books sorted by timestamp.
+/// - `trades`: Trades sorted by timestamp.
+/// - `max_staleness`: Oldest accepted book, no limit if None.
+pub fn books_asof<B: BookView>(
+    books: &[B],
+    trades: &[Trade],
+    max_staleness: Option<u64>,
+) -> Result<Vec<Option<usize>>, String> {
+    check_sorted(books, trades)?;
+
+    let book_ts: Vec<u64> = books.iter().map(|book| book.timestamp()).collect();
+    let trade_ts: Vec<u64> = trades.iter().map(|trade| trade.trade_ts).collect();
+    asof_indices(&trade_ts, &book_ts, max_staleness)
+}
@@ -0,0 +1,307 @@
+use crate::{l2books::L2Levels, orders::OrderSide};
+
+// --------------------------------------------------------------------- LEVEL VIEW -- //
+// --------------------------------------------------------------------- ---------- -- //
+
+/// Aggregated, read-only information of a single price level.
+///
+/// Regardless of how the book stores its levels (L3 `Level` with its orders,
+/// or the L2 structure-of-arrays), a level can always be reduced to its
+/// price, total volume and number of resting orders.
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct LevelView {
+    pub price: f64,
+    pub volume: f64,
+    pub count: u32,
+}
+
+// ---------------------------------------------------------------------- BOOK VIEW -- //
+// ---------------------------------------------------------------------- --------- -- //
+
+/// Common read access for order book representations.
+///
+/// Levels are addressed by side and index, index 0 being the top of the book
+/// on each side (the best bid and the best ask). Only `timestamp`, `symbol`,
+/// `depth` and `level` have to be implemented, everything else is derived
+/// from them.
+pub trait BookView {
+    /// Timestamp of the book snapshot.
+    fn timestamp(&self) -> u64;
+
+    /// Trading symbol of the book.
+    fn symbol(&self) -> &str;
+
+    /// Number of levels on the given side.
+    fn depth(&self, side: OrderSide) -> usize;
+
+    /// The level at `index` on the given side, `None` if out of range.
+    fn level(&self, side: OrderSide, index: usize) -> Option<LevelView>;
+
+    /// Top of the book on the Bids side.
+    fn best_bid(&self) -> Option<LevelView> {
+        self.level(OrderSide::Bids, 0)
+    }
+
+    /// Top of the book on the Asks side.
+    fn best_ask(&self) -> Option<LevelView> {
+        self.level(OrderSide::Asks, 0)
+    }
+
+    /// Iterator over the levels of one side, from the top of the book.
+    fn levels(&self, side: OrderSide) -> LevelsIter<'_, Self> {
+        LevelsIter {
+            book: self,
+            side,
+            index: 0,
+        }
+    }
+
+    /// Midprice between the best bid and the best ask.
+    fn midprice(&self) -> Option<f64> {
+        Some((self.best_bid()?.price + self.best_ask()?.price) / 2.0)
+    }
+
+    /// Difference between the best ask and the best bid.
+    fn spread(&self) -> Option<f64> {
+        Some(self.best_ask()?.price - self.best_bid()?.price)
+    }
+
+    /// Total volume of the first `n_levels` levels of one side.
+    fn volume_depth(&self, side: OrderSide, n_levels: usize) -> f64 {
+        self.levels(side)
+            .take(n_levels)
+            .map(|level| level.volume)
+            .sum()
+    }
+
+    /// Total volume of the levels of one side priced at, or better than,
+    /// `price_limit`. For the Bids it is every level with a price greater or
+    /// equal to the limit, for the Asks lower or equal.
+    fn volume_within(&self, side: OrderSide, price_limit: f64) -> f64 {
+        self.levels(side)
+            .filter(|level| match side {
+                OrderSide::Bids => level.price >= price_limit,
+                OrderSide::Asks => level.price <= price_limit,
+            })
+            .map(|level| level.volume)
+            .sum()
+    }
+
+    /// Number of levels of one side priced at, or better than, `price_limit`.
+    fn depth_within(&self, side: OrderSide, price_limit: f64) -> usize {
+        self.levels(side)
+            .filter(|level| match side {
+                OrderSide::Bids => level.price >= price_limit,
+                OrderSide::Asks => level.price <= price_limit,
+            })
+            .count()
+    }
+}
+
+impl<B: BookView + ?Sized> BookView for &B {
+    fn timestamp(&self) -> u64 {
+        (**self).timestamp()
+    }
+
+    fn symbol(&self) -> &str {
+        (**self).symbol()
+    }
+
+    fn depth(&self, side: OrderSide) -> usize {
+        (**self).depth(side)
+    }
+
+    fn level(&self, side: OrderSide, index: usize) -> Option<LevelView> {
+        (**self).level(side, index)
+    }
+}
+
+// -------------------------------------------------------------------- LEVELS ITER -- //
+// -------------------------------------------------------------------- ----------- -- //
+
+/// Iterator over the levels of one side of a `BookView`.
+#[derive(Debug)]
+pub struct LevelsIter<'a, B: ?Sized> {
+    book: &'a B,
+    side: OrderSide,
+    index: usize,
+}
+
+impl<B: BookView + ?Sized> Iterator for LevelsIter<'_, B> {
+    type Item = LevelView;
+
+    fn next(&mut self) -> Option<LevelView> {
+        let level = self.book.level(self.side, self.index)?;
+        self.index += 1;
+        Some(level)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.book.depth(self.side).saturating_sub(self.index);
+        (remaining, Some(remaining))
+    }
+}
+
+impl<B: BookView + ?Sized> ExactSizeIterator for LevelsIter<'_, B> {}
+
+// ---------------------------------------------------------------- AGGREGATED VIEW -- //
+// ---------------------------------------------------------------- --------------- -- //
+
+/// A coarser view of a book, with its levels grouped into price buckets.
+///
+/// Every level is assigned to the bucket of width `bucket_size` that
+/// contains it, bids are rounded down and asks rounded up, so the buckets
+/// never cross. Volumes and counts are summed within a bucket, its price is
+/// the lower boundary of the bucket for bids and the upper one for asks.
+#[derive(Debug, Clone)]
+pub struct AggregatedView<'a, B: ?Sized> {
+    book: &'a B,
+    bucket_size: f64,
+    bids: L2Levels,
+    asks: L2Levels,
+}
+
+impl<'a, B: BookView + ?Sized> AggregatedView<'a, B> {
+    pub fn new(book: &'a B, bucket_size: f64) -> Result<Self, String> {
+        if bucket_size.is_nan() || bucket_size <= 0.0 {
+            return Err(format!(
+                "bucket_size must be positive, got {:?}",
+                bucket_size
+            ));
+        }
+
+        Ok(AggregatedView {
+            book,
+            bucket_size,
+            bids: Self::aggregate(book, OrderSide::Bids, bucket_size),
+            asks: Self::aggregate(book, OrderSide::Asks, bucket_size),
+        })
+    }
+
+    pub fn bucket_size(&self) -> f64 {
+        self.bucket_size
+    }
+
+    fn aggregate(book: &B, side: OrderSide, bucket_size: f64) -> L2Levels {
+        let mut buckets = L2Levels::new();
+
+        for level in book.levels(side) {
+            let bucket_price = match side {
+                OrderSide::Bids => (level.price / bucket_size).floor() * bucket_size,
+                OrderSide::Asks => (level.price / bucket_size).ceil() * bucket_size,
+            };
+
+            // levels come sorted from the top, so only the last bucket can match
+            match buckets.prices.last() {
+                Some(last_price) if *last_price == bucket_price => {
+                    let last = buckets.len() - 1;
+                    buckets.volumes[last] += level.volume;
+                    buckets.counts[last] += level.count;
+                }
+                _ => buckets.push(bucket_price, level.volume, level.count),
+            }
+        }
+
+        buckets
+    }
+}
+
+impl<B: BookView + ?Sized> BookView for AggregatedView<'_, B> {
+    fn timestamp(&self) -> u64 {
+        self.book.timestamp()
+    }
+
+    fn symbol(&self) -> &str {
+        self.book.symbol()
+    }
+
+    fn depth(&self, side: OrderSide) -> usize {
+        match side {
+            OrderSide::Bids => self.bids.len(),
+            OrderSide::Asks => self.asks.len(),
+        }
+    }
+
+    fn level(&self, side: OrderSide, index: usize) -> Option<LevelView> {
+        match side {
+            OrderSide::Bids => self.bids.get(index),
+            OrderSide::Asks => self.asks.get(index),
+        }
+    }
+}
+
+// --------------------------------------------------------------------- BOOK SLICE -- //
+// --------------------------------------------------------------------- ---------- -- //
+
+/// A borrowed, zero-copy view over the columns of a stored snapshot.
+///
+/// Useful to read books straight from the buffers they are stored in
+/// (e.g. the `L2Levels` of an `L2Orderbook`, or the columns of a loaded
+/// file) without building an owned book. `counts` are optional, when not
+/// available every level reports a count of 0.
+#[derive(Debug, Copy, Clone)]
+pub struct BookSlice<'a> {
+    pub timestamp: u64,
+    pub symbol: &'a str,
+    pub bid_prices: &'a [f64],
+    pub bid_volumes: &'a [f64],
+    pub bid_counts: Option<&'a [u32]>,
+    pub ask_prices: &'a [f64],
+    pub ask_volumes: &'a [f64],
+    pub ask_counts: Option<&'a [u32]>,
+}
+
+impl<'a> BookSlice<'a> {
+    /// Borrows the first `n_levels` of each side of an L2 snapshot.
+    pub fn from_l2(
+        timestamp: u64,
+        symbol: &'a str,
+        bids: &'a L2Levels,
+        asks: &'a L2Levels,
+        n_levels: usize,
+    ) -> Self {
+        let n_bids = n_levels.min(bids.len());
+        let n_asks = n_levels.min(asks.len());
+
+        BookSlice {
+            timestamp,
+            symbol,
+            bid_prices: &bids.prices[..n_bids],
+            bid_volumes: &bids.volumes[..n_bids],
+            bid_counts: Some(&bids.counts[..n_bids]),
+            ask_prices: &asks.prices[..n_asks],
+            ask_volumes: &asks.volumes[..n_asks],
+            ask_counts: Some(&asks.counts[..n_asks]),
+        }
+    }
+}
+
+impl BookView for BookSlice<'_> {
+    fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    fn symbol(&self) -> &str {
+        self.symbol
+    }
+
+    fn depth(&self, side: OrderSide) -> usize {
+        match side {
+            OrderSide::Bids => self.bid_prices.len().min(self.bid_volumes.len()),
+            OrderSide::Asks => self.ask_prices.len().min(self.ask_volumes.len()),
+        }
+    }
+
+    fn level(&self, side: OrderSide, index: usize) -> Option<LevelView> {
+        let (prices, volumes, counts) = match side {
+            OrderSide::Bids => (self.bid_prices, self.bid_volumes, self.bid_counts),
+            OrderSide::Asks => (self.ask_prices, self.ask_volumes, self.ask_counts),
+        };
+
+        Some(LevelView {
+            price: *prices.get(index)?,
+            volume: *volumes.get(index)?,
+            count: counts.and_then(|c| c.get(index).copied()).unwrap_or(0),
+        })
+    }
+}
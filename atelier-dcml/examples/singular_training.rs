@@ -1,5 +1,5 @@
 /// Conduct a Singular Training Process
-use atelier_data::{data, templates};
+use atelier_data::{data, orderbooks::Orderbook, templates};
 use std::{env, error::Error, path::Path};
 
 fn main() -> Result<(), Box<dyn Error>> {
@@ -22,6 +22,11 @@ fn main() -> Result<(), Box<dyn Error>> {
         .unwrap()
         .clone();
 
+    if let Err(e) = config.validate() {
+        eprintln!("{}", e);
+        return Ok(());
+    }
+
     let _exp_id = &config.experiments[0].id;
 
     // --- Load Computed Features
@@ -30,7 +35,8 @@ fn main() -> Result<(), Box<dyn Error>> {
         .join("datasets")
         .join("exp_00_ai_00_binance_ob.json");
 
-    let _v_orderbook = data::load_from_json(&data_file.to_str().unwrap().to_owned())?;
+    let _v_orderbook: Vec<Orderbook> =
+        data::load_from_json(&data_file.to_str().unwrap().to_owned())?;
 
     Ok(())
 }
@@ -0,0 +1,171 @@
+use crate::{features::FeatureSelector, targets::TargetSelector};
+use atelier_data::{
+    data::{windows::Aggregate, Dataset},
+    orders::OrderSide,
+    views::BookView,
+};
+
+// ------------------------------------------------------------------- FEATURE SPEC -- //
+// ------------------------------------------------------------------- ------------ -- //
+
+/// Features computed from each book, and the windows over them.
+///
+/// Rolling aggregates are added first and lags after them, so lags are
+/// taken of every column, the aggregates included.
+#[derive(Debug)]
+pub struct FeatureSpec {
+    selector: FeatureSelector,
+    depth: usize,
+    bps: f64,
+    lags: usize,
+    rolling: Option<(usize, Vec<Aggregate>)>,
+}
+
+impl FeatureSpec {
+    /// Spec of the named features, with a depth of 10 levels and a `bps` of
+    /// 1.0, see `bps`.
+    pub fn new(features_names: &[&str]) -> Result<Self, String> {
+        Ok(FeatureSpec::from_selector(FeatureSelector::new(
+            features_names,
+        )?))
+    }
+
+    pub fn from_selector(selector: FeatureSelector) -> Self {
+        FeatureSpec {
+            selector,
+            depth: 10,
+            bps: 1.0,
+            lags: 0,
+            rolling: None,
+        }
+    }
+
+    /// Levels used by depth based features, e.g. `vwap`.
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Band of price band features, e.g. `tav`, as a fraction of the best
+    /// prices: `compute_tav` takes the asks up to `ask * (1 + bps)` and the
+    /// bids down to `bid * (1 - bps)`, so 1.0 is a band of 100%.
+    pub fn bps(mut self, bps: f64) -> Self {
+        self.bps = bps;
+        self
+    }
+
+    /// Values of every feature at t-1..t-`lags`, see `Dataset::lags`.
+    pub fn lags(mut self, lags: usize) -> Self {
+        self.lags = lags;
+        self
+    }
+
+    /// Aggregates over the last `window` rows, see `Dataset::rolling`.
+    pub fn rolling(mut self, window: usize, aggregates: &[Aggregate]) -> Self {
+        self.rolling = Some((window, aggregates.to_vec()));
+        self
+    }
+
+    /// Rows of history each row of features uses.
+    pub fn lookback(&self) -> usize {
+        let window = self
+            .rolling
+            .as_ref()
+            .map_or(0, |(w, _)| w.saturating_sub(1));
+        window + self.lags
+    }
+}
+
+// ------------------------------------------------------------- DATASET FROM BOOKS -- //
+// ------------------------------------------------------------- ------------------ -- //
+
+/// Construction of a `Dataset` straight from a sequence of order books.
+pub trait FromOrderbooks: Sized {
+    /// Features of the book at t with the targets of the book at
+    /// t+`horizon`, each target computed over `horizon` books (e.g. the
+    /// sign of the midprice return from t to t+`horizon`).
+    ///
+    /// The first rows, without a full lookback, and the last `horizon`
+    /// ones are dropped. The index keeps the timestamps of the books the
+    /// features come from, and the columns the names of the features.
+    ///
+    /// # Parameters
+    ///
+    /// - `books`: Order books, every one of them with Bids and Asks.
+    /// - `feature_spec`: Features and windows over them.
+    /// - `target_spec`: Targets, without horizons of their own.
+    /// - `horizon`: Books ahead the targets are taken from.
+    fn from_orderbooks<B: BookView>(
+        books: &[B],
+        feature_spec: &FeatureSpec,
+        target_spec: &TargetSelector,
+        horizon: usize,
+    ) -> Result<Self, String>;
+}
+
+impl FromOrderbooks for Dataset {
+    fn from_orderbooks<B: BookView>(
+        books: &[B],
+        feature_spec: &FeatureSpec,
+        target_spec: &TargetSelector,
+        horizon: usize,
+    ) -> Result<Dataset, String> {
+        if horizon == 0 {
+            return Err("horizon must be greater than 0".to_string());
+        }
+        if books.len() <= feature_spec.lookback() + horizon {
+            return Err(format!(
+                "{} books are not enough for a lookback of {} and a horizon of {}",
+                books.len(),
+                feature_spec.lookback(),
+                horizon
+            ));
+        }
+        if let Some(book) = books
+            .iter()
+            .find(|b| b.depth(OrderSide::Bids) == 0 || b.depth(OrderSide::Asks) == 0)
+        {
+            return Err(format!(
+                "Orderbook at {} without Bids or Asks levels",
+                book.timestamp()
+            ));
+        }
+
+        let features = books
+            .iter()
+            .map(|book| {
+                feature_spec.selector.compute_values(
+                    book,
+                    feature_spec.depth,
+                    feature_spec.bps,
+                )
+            })
+            .collect();
+        let targets = target_spec
+            .compute_columns_over(books, horizon)
+            .map_err(|e| e.to_string())?;
+
+        let mut dataset = Dataset::new()
+            .index(books.iter().map(|book| book.timestamp()).collect())
+            .features(features)
+            .feature_names(
+                feature_spec
+                    .selector
+                    .features_names()
+                    .iter()
+                    .map(|name| name.to_string())
+                    .collect(),
+            )
+            .targets(targets)
+            .build()?;
+
+        if let Some((window, aggregates)) = &feature_spec.rolling {
+            dataset = dataset.rolling(*window, aggregates)?;
+        }
+        if feature_spec.lags > 0 {
+            dataset = dataset.lags(feature_spec.lags)?;
+        }
+
+        dataset.align_horizon(horizon)
+    }
+}
@@ -1,5 +1,9 @@
 /// Features Calculation
-use atelier_data::{data, orderbooks::Orderbook};
+use atelier_data::{
+    data,
+    orders::OrderSide,
+    views::{BookView, LevelView},
+};
 use std::error::Error;
 
 #[derive(Debug, Clone, Copy)]
@@ -30,9 +34,9 @@ impl OrderbookFeatures {
         }
     }
 
-    pub fn compute(
+    pub fn compute<B: BookView>(
         &self,
-        ob: &Orderbook,
+        ob: &B,
         depth: usize,
         bps: f64,
     ) -> Result<f64, Box<(dyn Error + 'static)>> {
@@ -107,7 +111,12 @@ impl FeatureSelector {
     }
 
     /// Compute all values
-    pub fn compute_values(&self, ob: &Orderbook, depth: usize, bps: f64) -> Vec<f64> {
+    pub fn compute_values<B: BookView>(
+        &self,
+        ob: &B,
+        depth: usize,
+        bps: f64,
+    ) -> Vec<f64> {
         self.selected_features
             .iter()
             .map(|feature| feature.compute(ob, depth, bps).unwrap())
@@ -120,8 +129,8 @@ impl FeatureSelector {
     }
 }
 
-pub fn compute_features(
-    orderbooks: &[Orderbook],
+pub fn compute_features<B: BookView>(
+    orderbooks: &[B],
     feature_names: &[&str],
     depth: usize,
     bps: f64,
@@ -143,29 +152,40 @@ pub fn compute_features(
 
 // --- Different Features Computations --- //
 
+/// Best bid and best ask levels, panics if either side is empty.
+fn top_of_book<B: BookView>(ob: &B) -> (LevelView, LevelView) {
+    let best_bid = ob.best_bid().expect("Orderbook without Bids levels");
+    let best_ask = ob.best_ask().expect("Orderbook without Asks levels");
+    (best_bid, best_ask)
+}
+
 /// Spread
-pub fn compute_spread(ob: &Orderbook) -> f64 {
-    let i_spread = &ob.asks[0].price - &ob.bids[0].price;
+pub fn compute_spread<B: BookView>(ob: &B) -> f64 {
+    let (best_bid, best_ask) = top_of_book(ob);
+    let i_spread = best_ask.price - best_bid.price;
     data::truncate_to_decimal(i_spread, 8)
 }
 
 /// Midprice
-pub fn compute_midprice(ob: &Orderbook) -> f64 {
-    let i_midprice = (&ob.asks[0].price + &ob.bids[0].price) / 2.0;
+pub fn compute_midprice<B: BookView>(ob: &B) -> f64 {
+    let (best_bid, best_ask) = top_of_book(ob);
+    let i_midprice = (best_ask.price + best_bid.price) / 2.0;
     data::truncate_to_decimal(i_midprice, 8)
 }
 
 /// Weighted Midprice
-pub fn compute_w_midprice(ob: &Orderbook) -> f64 {
-    let i_w_midprice = ((ob.bids[0].price * ob.bids[0].volume)
-        + (ob.asks[0].price * ob.asks[0].volume))
-        / (ob.asks[0].volume + ob.bids[0].volume);
+pub fn compute_w_midprice<B: BookView>(ob: &B) -> f64 {
+    let (best_bid, best_ask) = top_of_book(ob);
+    let i_w_midprice = ((best_bid.price * best_bid.volume)
+        + (best_ask.price * best_ask.volume))
+        / (best_ask.volume + best_bid.volume);
     data::truncate_to_decimal(i_w_midprice, 8)
 }
 
 /// Orderbook Volume Imbalance
-pub fn compute_imb(ob: &Orderbook) -> f64 {
-    let i_imb = &ob.asks[0].volume / (&ob.asks[0].volume + &ob.bids[0].volume);
+pub fn compute_imb<B: BookView>(ob: &B) -> f64 {
+    let (best_bid, best_ask) = top_of_book(ob);
+    let i_imb = best_ask.volume / (best_ask.volume + best_bid.volume);
     data::truncate_to_decimal(i_imb, 8)
 }
 
@@ -173,9 +193,9 @@ pub fn compute_imb(ob: &Orderbook) -> f64 {
 ///
 /// Takes the orderbook bids and asks, up to the specified level, and
 /// calculates the classic Volume-Weighted Average Price.
-pub fn compute_vwap(ob: &Orderbook, depth: usize) -> f64 {
-    let bid_levels = ob.bids.iter().take(depth);
-    let ask_levels = ob.asks.iter().take(depth);
+pub fn compute_vwap<B: BookView>(ob: &B, depth: usize) -> f64 {
+    let bid_levels = ob.levels(OrderSide::Bids).take(depth);
+    let ask_levels = ob.levels(OrderSide::Asks).take(depth);
     let all_levels = bid_levels.chain(ask_levels);
 
     let (sum_p_v, sum_v) = all_levels.fold((0.0, 0.0), |(acc_p_v, acc_v), level| {
@@ -193,26 +213,13 @@ pub fn compute_vwap(ob: &Orderbook, depth: usize) -> f64 {
 ///
 /// The total volume posted in the orderbook within X bps of the midprice
 ///
-pub fn compute_tav(ob: &Orderbook, bps: f64) -> f64 {
-    let best_bid = &ob.bids[0].price;
-    let best_ask = &ob.asks[0].price;
-    let upper_ask = best_ask * (1.0 + bps);
-    let lower_bid = best_bid * (1.0 - bps);
-
-    // find the closest bid leve to lower bid
-    let bid_volume: f64 = ob
-        .bids
-        .iter()
-        .filter(|level| level.price >= lower_bid)
-        .map(|level| level.volume)
-        .sum();
-
-    let ask_volume: f64 = ob
-        .asks
-        .iter()
-        .filter(|level| level.price <= upper_ask)
-        .map(|level| level.volume)
-        .sum();
+pub fn compute_tav<B: BookView>(ob: &B, bps: f64) -> f64 {
+    let (best_bid, best_ask) = top_of_book(ob);
+    let upper_ask = best_ask.price * (1.0 + bps);
+    let lower_bid = best_bid.price * (1.0 - bps);
+
+    let bid_volume = ob.volume_within(OrderSide::Bids, lower_bid);
+    let ask_volume = ob.volume_within(OrderSide::Asks, upper_ask);
 
     let i_tav = bid_volume + ask_volume;
     data::truncate_to_decimal(i_tav, 8)
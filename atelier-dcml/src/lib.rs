@@ -14,6 +14,9 @@ pub mod features;
 /// target engineering
 pub mod targets;
 
+/// Datasets built from Orderbook sequences
+pub mod datasets;
+
 /// Loss function engineering
 pub mod functions;
 
@@ -28,3 +31,6 @@ pub mod optimizers;
 
 /// Various metrics
 pub mod metrics;
+
+/// Data quality checks of order book histories
+pub mod quality;
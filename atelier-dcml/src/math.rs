@@ -1,35 +1,7 @@
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use tch::{Kind, Tensor};
 
-pub enum Transformation {
-    Standarize,
-    Scale,
-}
-
-/// Data transformation
-pub fn transform(data: &Tensor, operation: Transformation) -> Tensor {
-    // Compensation error for numerical stability
-    let epsilon = 1e-8;
-
-    // Match the selected operation
-    let transformed = match operation {
-        // new_x = (x - mean(x)) / std(x)
-        Transformation::Standarize => {
-            let xs_1 = data - data.mean(Kind::Float);
-            let xs_2 = data.std(true) + epsilon;
-
-            (xs_1 / xs_2).to_kind(Kind::Float)
-        }
-
-        // new_x = x / max(x)
-        Transformation::Scale => {
-            let max_data = data.max();
-            data / max_data
-        }
-    };
-    transformed
-}
-
 pub fn empty_matrix(num_agents: i64) -> Tensor {
     let val = 1.0 / num_agents as f64;
     Tensor::from_slice(&vec![val; (num_agents * num_agents) as usize])
@@ -37,7 +9,7 @@ pub fn empty_matrix(num_agents: i64) -> Tensor {
         .to_kind(Kind::Float)
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Stats {
     pub len: i32,
     pub min: f32,
@@ -7,14 +7,25 @@ use crate::{
     optimizers::Optimizer,
 };
 
-use atelier_data::data;
+use atelier_data::data::{self, loader::DataLoader};
 use serde::Deserialize;
 use std::{error::Error, fs};
 
-use tch::Kind;
+use tch::{Kind, Tensor};
 
+/// How the training data is fed at every step.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TrainType {
+    /// One step per epoch, with the whole dataset as a single tensor.
     Batch,
+    /// One step per batch of `batch_size` rows, shuffled within windows of
+    /// `shuffle_window` rows when given.
+    MiniBatch {
+        batch_size: usize,
+        shuffle_window: Option<usize>,
+    },
+    /// One step per row, in a fully shuffled order.
+    Stochastic,
 }
 
 #[derive(Debug, Deserialize)]
@@ -92,6 +103,7 @@ pub struct Singular {
     loss: functions::CrossEntropy,
     optimizer: optimizers::GradientDescent,
     metrics: metrics::Metrics,
+    train_type: TrainType,
 }
 
 impl Singular {
@@ -100,48 +112,69 @@ impl Singular {
     }
 
     pub fn train(&mut self, epochs: u32) -> Result<(), Box<dyn Error>> {
-        let (features, targets) = &self.data.clone().from_vec_to_tensor();
+        let (batch_size, shuffle_window) = match self.train_type {
+            TrainType::Batch => return self.train_batch(epochs),
+            TrainType::MiniBatch {
+                batch_size,
+                shuffle_window,
+            } => (batch_size, shuffle_window),
+            TrainType::Stochastic => (1, Some(self.data.len().max(1))),
+        };
+
+        let mut loader = DataLoader::new()
+            .dataset(self.data.clone())
+            .batch_size(batch_size)
+            .prefetch(2);
+
+        if let Some(window) = shuffle_window {
+            loader = loader.shuffle(window);
+        }
+
+        let loader = loader.build()?;
+        if loader.is_empty() {
+            return Err(format!(
+                "No batches to train on, the dataset has {} rows",
+                self.data.len()
+            )
+            .into());
+        }
 
         for epoch in 0..epochs {
-            // --- Forward Step --- //
-            let y_hat = self.model.forward(&features);
-
-            // --- Compute Loss --- //
-            let loss = self.loss.compute_loss(&y_hat, &targets);
-
-            let reg_param_c = 1.9;
-            let reg_param_lambda = 0.8;
-
-            let reg_loss = self
-                .loss
-                .regularize(
-                    &self.model.weights,
-                    &RegType::Elasticnet,
-                    vec![reg_param_c, reg_param_lambda],
-                )
-                .sum(Kind::Float);
-
-            let total_loss = &loss + &reg_loss;
-            total_loss.backward();
-
-            // --- Compute Gradients --- //
-            let c_w = self.model.weights.grad();
-            let c_b = self.model.bias.grad();
-
-            // --- Compute Step of Learning Algorithm
-            self.optimizer.step(
-                &mut self.model.weights,
-                &mut self.model.bias,
-                &c_w,
-                &c_b,
+            let mut epoch_loss = 0.0;
+            let mut y_hats = Vec::with_capacity(loader.len());
+            let mut targets = Vec::with_capacity(loader.len());
+
+            for (features, batch_targets) in loader.batches(epoch as u64) {
+                let (loss, y_hat) = self.step(&features, &batch_targets);
+                epoch_loss += loss.double_value(&[]);
+                y_hats.push(y_hat.detach());
+                targets.push(batch_targets);
+            }
+
+            // --- Get Metrics over every batch of the epoch --- //
+            let metrics = self
+                .metrics
+                .compute_all(&Tensor::cat(&y_hats, 0), &Tensor::cat(&targets, 0));
+
+            println!(
+                "\n--- epoch {:?} --- loss {:?} --- accuracy: {:?}",
+                epoch,
+                epoch_loss / loader.len().max(1) as f64,
+                metrics["accuracy"]
             );
+        }
+        Ok(())
+    }
+
+    /// Full batch training, the whole dataset as a single tensor.
+    fn train_batch(&mut self, epochs: u32) -> Result<(), Box<dyn Error>> {
+        let (features, targets) = &self.data.clone().from_vec_to_tensor();
 
-            // --- Reset gradient value on weights and bias
-            self.model.weights.zero_grad();
-            self.model.bias.zero_grad();
+        for epoch in 0..epochs {
+            let (loss, y_hat) = self.step(features, targets);
 
             // --- Get Metrics --- //
-            let metrics = self.metrics.compute_all(&y_hat, &targets);
+            let metrics = self.metrics.compute_all(&y_hat, targets);
 
             println!(
                 "\n--- epoch {:?} --- loss {:?} --- accuracy: {:?}",
@@ -151,6 +184,44 @@ impl Singular {
         Ok(())
     }
 
+    /// A single learning step, returns the loss and the predictions.
+    fn step(&mut self, features: &Tensor, targets: &Tensor) -> (Tensor, Tensor) {
+        // --- Forward Step --- //
+        let y_hat = self.model.forward(features);
+
+        // --- Compute Loss --- //
+        let loss = self.loss.compute_loss(&y_hat, targets);
+
+        let reg_param_c = 1.9;
+        let reg_param_lambda = 0.8;
+
+        let reg_loss = self
+            .loss
+            .regularize(
+                &self.model.weights,
+                &RegType::Elasticnet,
+                vec![reg_param_c, reg_param_lambda],
+            )
+            .sum(Kind::Float);
+
+        let total_loss = &loss + &reg_loss;
+        total_loss.backward();
+
+        // --- Compute Gradients --- //
+        let c_w = self.model.weights.grad();
+        let c_b = self.model.bias.grad();
+
+        // --- Compute Step of Learning Algorithm
+        self.optimizer
+            .step(&mut self.model.weights, &mut self.model.bias, &c_w, &c_b);
+
+        // --- Reset gradient value on weights and bias
+        self.model.weights.zero_grad();
+        self.model.bias.zero_grad();
+
+        (loss, y_hat)
+    }
+
     pub fn save_model(self, file_route: &str) {
         // --- Save a model's weight
         let _ = self.model.save_model(file_route);
@@ -168,6 +239,7 @@ pub struct SingularBuilder {
     loss: Option<functions::CrossEntropy>,
     optimizer: Option<optimizers::GradientDescent>,
     metrics: Option<metrics::Metrics>,
+    train_type: TrainType,
 }
 
 impl SingularBuilder {
@@ -178,6 +250,7 @@ impl SingularBuilder {
             loss: None,
             optimizer: None,
             metrics: None,
+            train_type: TrainType::Batch,
         }
     }
 
@@ -206,6 +279,12 @@ impl SingularBuilder {
         self
     }
 
+    /// How the data is fed to the model, `TrainType::Batch` by default.
+    pub fn train_type(mut self, train_type: TrainType) -> Self {
+        self.train_type = train_type;
+        self
+    }
+
     pub fn build(self) -> Result<Singular, &'static str> {
         let data = self.data.ok_or("Missing data")?;
         let model = self.model.ok_or("MIssing model")?;
@@ -219,6 +298,7 @@ impl SingularBuilder {
             loss,
             optimizer,
             metrics,
+            train_type: self.train_type,
         })
     }
 }
@@ -0,0 +1,332 @@
+use crate::math::Stats;
+use atelier_data::{
+    data::{
+        columnar::{OrderbookParquetReader, DEFAULT_ROW_GROUP_SIZE},
+        file_format, load_from_json,
+        ndjson::NdjsonReader,
+        snapshots::{self, SnapshotReader},
+    },
+    orderbooks::Orderbook,
+    orders::OrderSide,
+    views::BookView,
+};
+use serde::{Deserialize, Serialize};
+use std::{error::Error, fmt};
+
+// ----------------------------------------------------------------- QUALITY CHECKS -- //
+// ----------------------------------------------------------------- -------------- -- //
+
+/// Thresholds of the data quality checks of an order book history.
+///
+/// Outlier spreads and price jumps are found with the `Stats` of the whole
+/// history: a spread, or a midprice log-return, is an outlier when it is
+/// more than the given number of standard deviations away from the mean.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityChecks {
+    max_gap: Option<u64>,
+    gap_factor: f64,
+    spread_outlier: f64,
+    price_jump: f64,
+}
+
+impl QualityChecks {
+    pub fn new() -> QualityChecksBuilder {
+        QualityChecksBuilder::new()
+    }
+
+    /// Report over a sequence of books, in the order they were recorded.
+    pub fn report<B: BookView>(&self, books: &[B]) -> QualityReport {
+        let mut accumulator = QualityAccumulator::default();
+        for book in books {
+            accumulator.add(book);
+        }
+        accumulator.finish(self)
+    }
+
+    /// Report over a stream of books, e.g. an `NdjsonReader` or an
+    /// `OrderbookParquetReader`, without loading all of them in memory.
+    pub fn report_stream<I>(&self, books: I) -> Result<QualityReport, Box<dyn Error>>
+    where
+        I: IntoIterator<Item = Result<Orderbook, Box<dyn Error>>>,
+    {
+        let mut accumulator = QualityAccumulator::default();
+        for book in books {
+            accumulator.add(&book?);
+        }
+        Ok(accumulator.finish(self))
+    }
+
+    /// Report over a file of books, with the format given by its extension
+    /// as in `atelier_data::data::load_orderbooks` (see `file_format`). Every
+    /// format but `.json` is streamed.
+    pub fn report_file(&self, file_route: &str) -> Result<QualityReport, Box<dyn Error>> {
+        match file_format(file_route)? {
+            "json" => Ok(self.report(&load_from_json::<Orderbook>(file_route)?)),
+            "ndjson" | "jsonl" => self.report_stream(NdjsonReader::open(file_route)?),
+            "parquet" => self.report_stream(OrderbookParquetReader::open(
+                file_route,
+                None,
+                DEFAULT_ROW_GROUP_SIZE,
+            )?),
+            snapshots::EXTENSION => {
+                self.report_stream(SnapshotReader::open(file_route)?.iter())
+            }
+            _ => Err(format!("Unknown order books file format: {}", file_route).into()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct QualityChecksBuilder {
+    max_gap: Option<u64>,
+    gap_factor: f64,
+    spread_outlier: f64,
+    price_jump: f64,
+}
+
+impl QualityChecksBuilder {
+    pub fn new() -> Self {
+        QualityChecksBuilder {
+            max_gap: None,
+            gap_factor: 10.0,
+            spread_outlier: 5.0,
+            price_jump: 5.0,
+        }
+    }
+
+    /// Time between consecutive books above which there is a gap. When not
+    /// given, it is `gap_factor` times the median time between books.
+    pub fn max_gap(mut self, max_gap: u64) -> Self {
+        self.max_gap = Some(max_gap);
+        self
+    }
+
+    /// 10 by default.
+    pub fn gap_factor(mut self, gap_factor: f64) -> Self {
+        self.gap_factor = gap_factor;
+        self
+    }
+
+    /// Standard deviations from the mean spread, 5 by default.
+    pub fn spread_outlier(mut self, spread_outlier: f64) -> Self {
+        self.spread_outlier = spread_outlier;
+        self
+    }
+
+    /// Standard deviations from the mean midprice log-return, 5 by default.
+    pub fn price_jump(mut self, price_jump: f64) -> Self {
+        self.price_jump = price_jump;
+        self
+    }
+
+    pub fn build(self) -> Result<QualityChecks, &'static str> {
+        if self.max_gap == Some(0) {
+            return Err("max_gap must be greater than 0");
+        }
+        if self.gap_factor <= 0.0 {
+            return Err("gap_factor must be greater than 0");
+        }
+        if self.spread_outlier <= 0.0 || self.price_jump <= 0.0 {
+            return Err("outlier thresholds must be greater than 0");
+        }
+
+        Ok(QualityChecks {
+            max_gap: self.max_gap,
+            gap_factor: self.gap_factor,
+            spread_outlier: self.spread_outlier,
+            price_jump: self.price_jump,
+        })
+    }
+}
+
+// ------------------------------------------------------------ QUALITY ACCUMULATOR -- //
+// ------------------------------------------------------------ ------------------- -- //
+
+/// Counts and series gathered book by book, outliers are found once all of
+/// them are known.
+#[derive(Debug, Default)]
+struct QualityAccumulator {
+    report: QualityReport,
+    last_ts: Option<u64>,
+    last_mid: Option<f64>,
+    intervals: Vec<f32>,
+    spreads: Vec<f32>,
+    returns: Vec<f32>,
+}
+
+impl QualityAccumulator {
+    fn add<B: BookView>(&mut self, book: &B) {
+        let report = &mut self.report;
+        let ts = book.timestamp();
+
+        report.n_books += 1;
+        report.first_ts.get_or_insert(ts);
+        report.last_ts = Some(ts);
+
+        match self.last_ts {
+            Some(last) if ts == last => report.duplicate_timestamps += 1,
+            Some(last) if ts < last => report.unordered_timestamps += 1,
+            Some(last) => self.intervals.push((ts - last) as f32),
+            None => {}
+        }
+        self.last_ts = Some(ts);
+
+        if book.depth(OrderSide::Bids) == 0 {
+            report.empty_bids += 1;
+        }
+        if book.depth(OrderSide::Asks) == 0 {
+            report.empty_asks += 1;
+        }
+        report.invalid_volumes += book
+            .levels(OrderSide::Bids)
+            .chain(book.levels(OrderSide::Asks))
+            .filter(|level| level.volume <= 0.0)
+            .count();
+
+        if let (Some(bid), Some(ask)) = (book.best_bid(), book.best_ask()) {
+            if bid.price > ask.price {
+                report.crossed_books += 1;
+            } else if bid.price == ask.price {
+                report.locked_books += 1;
+            } else {
+                self.spreads.push((ask.price - bid.price) as f32);
+            }
+
+            let mid = (bid.price + ask.price) / 2.0;
+            if let Some(last_mid) = self.last_mid.filter(|m| *m > 0.0 && mid > 0.0) {
+                self.returns.push((mid / last_mid).ln() as f32);
+            }
+            self.last_mid = Some(mid);
+        }
+    }
+
+    fn finish(mut self, checks: &QualityChecks) -> QualityReport {
+        let interval_stats = Stats::new(&self.intervals);
+        let spread_stats = Stats::new(&self.spreads);
+        let return_stats = Stats::new(&self.returns);
+
+        let max_gap = checks.max_gap.map(|gap| gap as f32).or(interval_stats
+            .as_ref()
+            .map(|stats| stats.median * checks.gap_factor as f32));
+        if let Some(max_gap) = max_gap {
+            self.report.gaps = self.intervals.iter().filter(|i| **i > max_gap).count();
+        }
+
+        self.report.outlier_spreads =
+            count_outliers(&self.spreads, spread_stats.as_ref(), checks.spread_outlier);
+        self.report.price_jumps =
+            count_outliers(&self.returns, return_stats.as_ref(), checks.price_jump);
+
+        self.report.interval_stats = interval_stats;
+        self.report.spread_stats = spread_stats;
+        self.report.return_stats = return_stats;
+        self.report
+    }
+}
+
+/// Values more than `n_std` standard deviations away from the mean.
+fn count_outliers(values: &[f32], stats: Option<&Stats>, n_std: f64) -> usize {
+    let Some(stats) = stats else {
+        return 0;
+    };
+    let limit = stats.variance.sqrt() * n_std as f32;
+    values
+        .iter()
+        .filter(|v| (**v - stats.mean).abs() > limit)
+        .count()
+}
+
+// ----------------------------------------------------------------- QUALITY REPORT -- //
+// ----------------------------------------------------------------- -------------- -- //
+
+/// Data quality report of an order book history.
+///
+/// Locked books have the best bid equal to the best ask, crossed books a
+/// best bid above the best ask, both are left out of the spread stats.
+/// `invalid_volumes` counts levels with zero or negative volume, and
+/// `unordered_timestamps` books older than the previous one. Stats are
+/// None with less than 2 values.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QualityReport {
+    pub n_books: usize,
+    pub first_ts: Option<u64>,
+    pub last_ts: Option<u64>,
+    pub crossed_books: usize,
+    pub locked_books: usize,
+    pub empty_bids: usize,
+    pub empty_asks: usize,
+    pub invalid_volumes: usize,
+    pub duplicate_timestamps: usize,
+    pub unordered_timestamps: usize,
+    pub gaps: usize,
+    pub outlier_spreads: usize,
+    pub price_jumps: usize,
+    pub interval_stats: Option<Stats>,
+    pub spread_stats: Option<Stats>,
+    pub return_stats: Option<Stats>,
+}
+
+impl QualityReport {
+    /// Whether no check found an issue.
+    pub fn is_clean(&self) -> bool {
+        self.issues().iter().all(|(_, count)| *count == 0)
+    }
+
+    /// Name and count of each check.
+    pub fn issues(&self) -> Vec<(&'static str, usize)> {
+        vec![
+            ("crossed books", self.crossed_books),
+            ("locked books", self.locked_books),
+            ("empty bids", self.empty_bids),
+            ("empty asks", self.empty_asks),
+            ("zero or negative volumes", self.invalid_volumes),
+            ("duplicate timestamps", self.duplicate_timestamps),
+            ("unordered timestamps", self.unordered_timestamps),
+            ("time gaps", self.gaps),
+            ("outlier spreads", self.outlier_spreads),
+            ("price jumps", self.price_jumps),
+        ]
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+impl fmt::Display for QualityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Data quality report")?;
+        writeln!(f, "  books: {}", self.n_books)?;
+        if let (Some(first), Some(last)) = (self.first_ts, self.last_ts) {
+            writeln!(f, "  time range: {} to {}", first, last)?;
+        }
+
+        writeln!(f, "Issues")?;
+        for (name, count) in self.issues() {
+            writeln!(f, "  {}: {}", name, count)?;
+        }
+
+        writeln!(f, "Stats")?;
+        for (name, stats) in [
+            ("intervals", &self.interval_stats),
+            ("spreads", &self.spread_stats),
+            ("midprice returns", &self.return_stats),
+        ] {
+            match stats {
+                Some(s) => writeln!(
+                    f,
+                    "  {}: mean {}, std {}, min {}, median {}, max {}",
+                    name,
+                    s.mean,
+                    s.variance.sqrt(),
+                    s.min,
+                    s.median,
+                    s.max
+                )?,
+                None => writeln!(f, "  {}: not enough values", name)?,
+            }
+        }
+
+        Ok(())
+    }
+}
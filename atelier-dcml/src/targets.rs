@@ -1,4 +1,4 @@
-use atelier_data::orderbooks::Orderbook;
+use atelier_data::{data::Column, views::BookView};
 use std::error::Error;
 
 #[derive(Debug, Clone, Copy)]
@@ -19,12 +19,21 @@ impl OrderbookTargets {
         }
     }
 
-    pub fn compute(
+    pub fn compute<B: BookView>(
         &self,
-        ob: &Vec<Orderbook>,
+        ob: &[B],
+    ) -> Result<Vec<f64>, Box<(dyn Error + 'static)>> {
+        self.compute_over(ob, 1)
+    }
+
+    /// Computes the target over `horizon` books instead of one.
+    pub fn compute_over<B: BookView>(
+        &self,
+        ob: &[B],
+        horizon: usize,
     ) -> Result<Vec<f64>, Box<(dyn Error + 'static)>> {
         match self {
-            OrderbookTargets::ReturnSign => compute_return_sign(&ob),
+            OrderbookTargets::ReturnSign => compute_return_sign_over(ob, horizon),
         }
     }
 
@@ -47,6 +56,7 @@ impl OrderbookTargets {
 #[derive(Debug)]
 pub struct TargetSelector {
     selected_targets: Vec<OrderbookTargets>,
+    horizons: Option<Vec<usize>>,
 }
 
 impl TargetSelector {
@@ -67,6 +77,7 @@ impl TargetSelector {
 
         Ok(TargetSelector {
             selected_targets: targets,
+            horizons: None,
         })
     }
 
@@ -74,34 +85,95 @@ impl TargetSelector {
     pub fn from_targets(targets: Vec<OrderbookTargets>) -> Self {
         TargetSelector {
             selected_targets: targets,
+            horizons: None,
+        }
+    }
+
+    /// Computes every selected target over each one of the `horizons`, so
+    /// a single pass over the books produces labels for all of them. Names
+    /// get the horizon as suffix, e.g. `return_sign_5`.
+    pub fn horizons(mut self, horizons: &[usize]) -> Result<Self, String> {
+        if horizons.is_empty() || horizons.contains(&0) {
+            return Err(format!(
+                "horizons must be a non empty list of positive values, got {:?}",
+                horizons
+            ));
         }
+        self.horizons = Some(horizons.to_vec());
+        Ok(self)
     }
 
-    /// Compute all values
-    pub fn compute_values(
+    /// Compute all values, one vector per target (and horizon), in the
+    /// order of `target_names`.
+    pub fn compute_values<B: BookView>(
         &self,
-        ob: &Vec<Orderbook>,
-    ) -> Result<Vec<f64>, Box<dyn Error>> {
-        let results: Result<Vec<Vec<f64>>, _> = self
-            .selected_targets
+        ob: &[B],
+    ) -> Result<Vec<Vec<f64>>, Box<dyn Error>> {
+        let horizons = self.horizons.clone().unwrap_or(vec![1]);
+
+        self.selected_targets
             .iter()
-            .map(|target| target.compute(ob))
-            .collect();
+            .flat_map(|target| horizons.iter().map(|h| target.compute_over(ob, *h)))
+            .collect()
+    }
 
-        Ok(results?.into_iter().flatten().collect())
+    /// Compute all values as named columns, ready for a `Dataset`.
+    pub fn compute_columns<B: BookView>(
+        &self,
+        ob: &[B],
+    ) -> Result<Vec<Column>, Box<dyn Error>> {
+        Ok(self
+            .target_names()
+            .iter()
+            .zip(self.compute_values(ob)?)
+            .map(|(name, values)| Column::new(name, values))
+            .collect())
     }
 
-    /// Get all features names
-    pub fn target_names(&self) -> Vec<&'static str> {
-        self.selected_targets.iter().map(|f| f.name()).collect()
+    /// Compute every selected target over `horizon` books, as named
+    /// columns. Targets with horizons of their own are not accepted.
+    pub fn compute_columns_over<B: BookView>(
+        &self,
+        ob: &[B],
+        horizon: usize,
+    ) -> Result<Vec<Column>, Box<dyn Error>> {
+        if self.horizons.is_some() {
+            return Err("targets already have their own horizons".into());
+        }
+
+        self.selected_targets
+            .iter()
+            .map(|target| {
+                Ok(Column::new(
+                    target.name(),
+                    target.compute_over(ob, horizon)?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Get all targets names
+    pub fn target_names(&self) -> Vec<String> {
+        match &self.horizons {
+            None => self
+                .selected_targets
+                .iter()
+                .map(|f| f.name().to_string())
+                .collect(),
+            Some(horizons) => self
+                .selected_targets
+                .iter()
+                .flat_map(|f| horizons.iter().map(|h| format!("{}_{}", f.name(), h)))
+                .collect(),
+        }
     }
 }
 
-pub fn compute_targets(
-    orderbooks: &Vec<Orderbook>,
+pub fn compute_targets<B: BookView>(
+    orderbooks: &[B],
     targets_names: &[&str],
     output_format: TargetsOutput,
-) -> Result<Vec<f64>, Box<(dyn Error + 'static)>> {
+) -> Result<Vec<Vec<f64>>, Box<(dyn Error + 'static)>> {
     let selector = TargetSelector::new(targets_names)?;
 
     match output_format {
@@ -116,21 +188,38 @@ pub fn compute_targets(
 /// The directional price movement (midprice) from t to t+1 is represented
 /// as the sign of the corresponding return.
 ///
-pub fn compute_return_sign(
-    orderbooks: &Vec<Orderbook>,
+pub fn compute_return_sign<B: BookView>(
+    orderbooks: &[B],
+) -> Result<Vec<f64>, Box<dyn std::error::Error>> {
+    compute_return_sign_over(orderbooks, 1)
+}
+
+/// Return's Sign over a horizon
+///
+/// Same as `compute_return_sign`, comparing the midprice at t with the one
+/// at t-`horizon`. The first `horizon` values have no previous midprice to
+/// compare to and are 0.0.
+///
+pub fn compute_return_sign_over<B: BookView>(
+    orderbooks: &[B],
+    horizon: usize,
 ) -> Result<Vec<f64>, Box<dyn std::error::Error>> {
+    if horizon == 0 {
+        return Err("horizon must be greater than 0".into());
+    }
+
     // Compute midprices
     let mid_prices: Vec<f64> = orderbooks
         .iter()
-        .map(|x| (x.asks[0].price + x.bids[0].price) / 2.0)
-        .collect();
+        .map(|x| x.midprice().ok_or("Orderbook without Bids or Asks levels"))
+        .collect::<Result<_, _>>()?;
 
     // Compute up indicator: 1.0 if midprice increases, 0.0 otherwise
-    let mut up_indicator = Vec::with_capacity(mid_prices.len());
-    up_indicator.push(0.0); // First value, no previous to compare
+    // First values, no previous to compare
+    let mut up_indicator = vec![0.0; horizon.min(mid_prices.len())];
 
-    for i in 1..mid_prices.len() {
-        if mid_prices[i] > mid_prices[i - 1] {
+    for i in horizon..mid_prices.len() {
+        if mid_prices[i] > mid_prices[i - horizon] {
             up_indicator.push(1.0);
         } else {
             up_indicator.push(0.0);
@@ -43,6 +43,32 @@ pub async fn main() {
         .unwrap()
         .clone();
 
+    if let Err(e) = template.validate() {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+
+    // --- Experiments with sweeps, one output directory per run
+    if template
+        .experiments
+        .iter()
+        .any(|experiment| !experiment.sweeps.is_empty())
+    {
+        let output_dir = workspace_root.join(&args.output_dir);
+        match synthbooks::sweep_progressions(&template, &output_dir).await {
+            Ok(run_dirs) => {
+                for run_dir in run_dirs {
+                    println!("Output written to: {}", run_dir.display());
+                }
+            }
+            Err(e) => {
+                eprintln!("Sweep failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
     // --- Extract parameters from template
     let exp_id = &template.experiments[0].id;
     let n_progres = template.experiments[0].n_progressions as usize;
@@ -93,4 +119,12 @@ pub async fn main() {
     file.write_all(ob_json.as_bytes()).unwrap();
 
     println!("Output written to: {}", folder_route);
+
+    // --- Resolved template next to the output
+    let config_route = format!("{}.toml", folder_route.trim_end_matches(".json"));
+    let config_toml = template.resolved().to_toml().unwrap();
+    let mut file = File::create(&config_route).unwrap();
+    file.write_all(config_toml.as_bytes()).unwrap();
+
+    println!("Config written to: {}", config_route);
 }
@@ -21,6 +21,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let template = templates::Config::load_from_toml(template_file.to_str().unwrap())
         .unwrap()
         .clone();
+    template.validate()?;
 
     // --- Extract parameters from template
     let n_progres = template.experiments[0].n_progressions as usize;
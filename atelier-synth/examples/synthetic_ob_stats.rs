@@ -29,6 +29,11 @@ fn main() {
         .unwrap()
         .clone();
 
+    if let Err(e) = config.validate() {
+        eprintln!("{}", e);
+        return;
+    }
+
     let exchanges = config.exchanges.clone();
 
     // Number of progressions for all Orderbooks
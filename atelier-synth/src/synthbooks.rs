@@ -1,10 +1,14 @@
 use atelier_data::{
     orderbooks::Orderbook,
-    templates::{ModelConfig, Models, OrderbookConfig},
+    templates::{Config, ModelConfig, ModelParams, OrderbookConfig},
 };
 use atelier_generators::{brownian, probabilistic};
 use futures::future::join_all;
-use std::error::Error;
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
 
 /// Generates a randomized orderbook snapshot based on input parameters.
 ///
@@ -60,7 +64,7 @@ pub fn progress(
 ///
 /// # Arguments
 /// - `template_orderbook`: Initial configuration with all fields required
-/// - `template_model`: GBM parameters (μ, σ) required
+/// - `template_model`: Uniform (lower, upper) or GBM (μ, σ, dt) parameters
 /// - `n_progres`: Number of progressions to generate
 ///
 /// # Returns
@@ -69,7 +73,7 @@ pub fn progress(
 /// - Error if input validation fails or model becomes unstable
 ///
 /// # Panics
-/// - If any template field contains `None`
+/// - If any template field contains `None`, see `Config::validate`
 /// - If μ or σ lead to negative prices
 ///
 pub async fn progressions(
@@ -83,12 +87,8 @@ pub async fn progressions(
     let ini_ask = template_orderbook.ask_price.unwrap();
     let ini_price = (ini_bid + ini_ask) / 2.0;
 
-    let model_label: Models = template_model.label.unwrap();
-
-    let (r_1, r_2) = match model_label {
-        Models::Uniform => {
-            let lower = template_model.params_values.as_ref().unwrap()[0];
-            let upper = template_model.params_values.as_ref().unwrap()[1];
+    let (r_1, r_2) = match template_model.model_params()? {
+        ModelParams::Uniform { lower, upper } => {
             let n = n_progres;
 
             let r_1 = probabilistic::uniform_return(lower, upper, n);
@@ -97,11 +97,8 @@ pub async fn progressions(
             (r_1, r_2)
         }
 
-        Models::GBM => {
-            let dt = 0.01;
+        ModelParams::GBM { mu, sigma, dt } => {
             let n = n_progres;
-            let mu = template_model.params_values.as_ref().unwrap()[0];
-            let sigma = template_model.params_values.unwrap()[1];
 
             (
                 brownian::gbm_return(ini_bid, mu, sigma, dt, n).unwrap(),
@@ -178,3 +175,71 @@ pub async fn async_progressions(
 
     join_all(tasks).await
 }
+
+/// Runs every instance of the experiments of a config, each one in its own
+/// output directory.
+///
+/// Experiments with sweeps are expanded with `Config::expand`, and each
+/// instance runs `async_progressions` with the orderbook of each exchange
+/// paired with the model at the same position. The directory of an
+/// instance, named by its id, has a `<exchange id>_<exchange name>.json`
+/// file per exchange with its orderbooks, a `sweep.json` file with the
+/// swept values and a `config.toml` file with the config of the instance,
+/// see `Config::resolved`.
+///
+/// # Arguments
+/// - `config`: Template with the experiments, exchanges and models
+/// - `output_dir`: Directory where the instance directories are created
+///
+/// # Returns
+/// Directory of each instance, in the order of `Config::expand`
+///
+pub async fn sweep_progressions(
+    config: &Config,
+    output_dir: &Path,
+) -> Result<Vec<PathBuf>, Box<dyn Error + Send + Sync>> {
+    config.validate().map_err(|e| e.to_string())?;
+    let mut run_dirs = Vec::new();
+
+    for instance in config.expand()? {
+        let template = instance.config;
+        template
+            .validate()
+            .map_err(|e| format!("{}: {}", instance.id, e))?;
+
+        let n_progres = template.experiments[0].n_progressions as usize;
+        let orderbooks = template
+            .exchanges
+            .iter()
+            .map(|exchange| exchange.orderbook.clone().unwrap())
+            .collect();
+        let progressions =
+            async_progressions(orderbooks, template.models.clone(), n_progres).await;
+
+        let run_dir = output_dir.join(&instance.id);
+        fs::create_dir_all(&run_dir)?;
+
+        for (exchange, orderbooks) in template.exchanges.iter().zip(progressions) {
+            let data_file =
+                run_dir.join(format!("{}_{}.json", exchange.id, exchange.name));
+            let ob_json = serde_json::to_string(&orderbooks?)?;
+            fs::write(data_file, ob_json)?;
+        }
+
+        let values: serde_json::Map<String, serde_json::Value> = instance
+            .values
+            .iter()
+            .map(|(param, value)| Ok((param.clone(), serde_json::to_value(value)?)))
+            .collect::<Result<_, serde_json::Error>>()?;
+        let sweep = serde_json::json!({ "id": instance.id, "values": values });
+        fs::write(
+            run_dir.join("sweep.json"),
+            serde_json::to_string_pretty(&sweep)?,
+        )?;
+        fs::write(run_dir.join("config.toml"), template.resolved().to_toml()?)?;
+
+        run_dirs.push(run_dir);
+    }
+
+    Ok(run_dirs)
+}
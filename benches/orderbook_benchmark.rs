@@ -1,6 +1,6 @@
 //! Benchmark for Orderbook Methods
 
-use atelier_data::orderbooks::Orderbook;
+use atelier_data::{l2books::L2Orderbook, orderbooks::Orderbook};
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
@@ -60,5 +60,56 @@ fn create_orderbook(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, create_orderbook);
+fn create_l2_orderbook(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Orderbook Creation L3 vs L2");
+
+    let v_orders = [Some((5, 10)), Some((100, 200)), Some((1000, 1300))];
+    let levels = Some((10, 20));
+
+    let ref_bid_price = 100_000.00;
+    let ref_ask_price = 100_000.10;
+
+    for orders in v_orders.iter() {
+        let id = format!("l_{:?}_o_{:?}", levels, orders);
+
+        group.bench_with_input(
+            criterion::BenchmarkId::new("l3_random", &id),
+            orders,
+            |b, &orders| {
+                b.iter(|| {
+                    Orderbook::random(
+                        black_box(ref_bid_price),
+                        black_box(levels),
+                        black_box(orders),
+                        black_box(None),
+                        black_box(ref_ask_price),
+                        black_box(levels),
+                        black_box(orders),
+                    )
+                });
+            },
+        );
+
+        group.bench_with_input(
+            criterion::BenchmarkId::new("l2_random", &id),
+            orders,
+            |b, &orders| {
+                b.iter(|| {
+                    L2Orderbook::random(
+                        black_box(ref_bid_price),
+                        black_box(levels),
+                        black_box(orders),
+                        black_box(None),
+                        black_box(ref_ask_price),
+                        black_box(levels),
+                        black_box(orders),
+                    )
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, create_orderbook, create_l2_orderbook);
 criterion_main!(benches);
@@ -0,0 +1,78 @@
+//! Benchmark for the Shared Orderbook: reader throughput under write load
+
+use atelier_data::{l2books::L2Orderbook, shared::shared_book, views::BookView};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+fn read_under_write_load(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Shared Orderbook Reads");
+
+    let ini_ob = L2Orderbook::random(
+        100_000.00,
+        Some((10, 20)),
+        Some((5, 10)),
+        Some((0.1, 1.0)),
+        100_000.10,
+        Some((10, 20)),
+        Some((5, 10)),
+    );
+
+    // Amount of concurrent readers, besides the measured one
+    let v_background_readers = [0, 1, 3];
+
+    for n_readers in v_background_readers.iter() {
+        let (mut writer, reader) = shared_book(ini_ob.clone());
+        let stop = Arc::new(AtomicBool::new(false));
+
+        // -- Writer: keeps updating the top of the book and publishing
+        let writer_stop = Arc::clone(&stop);
+        let writer_handle = thread::spawn(move || {
+            let mut i: u64 = 0;
+            while !writer_stop.load(Ordering::Relaxed) {
+                i += 1;
+                writer.update(|ob| {
+                    ob.orderbook_ts = i;
+                    ob.bids.volumes[0] = (i % 100) as f64 * 0.01;
+                });
+            }
+        });
+
+        // -- Background readers: keep taking snapshots
+        let reader_handles: Vec<_> = (0..*n_readers)
+            .map(|_| {
+                let bg_reader = reader.clone();
+                let bg_stop = Arc::clone(&stop);
+                thread::spawn(move || {
+                    while !bg_stop.load(Ordering::Relaxed) {
+                        black_box(bg_reader.read(|ob| ob.midprice()));
+                    }
+                })
+            })
+            .collect();
+
+        group.bench_function(format!("read_midprice_bg_readers_{}", n_readers), |b| {
+            b.iter(|| black_box(reader.read(|ob| ob.midprice())))
+        });
+
+        group.bench_function(format!("snapshot_bg_readers_{}", n_readers), |b| {
+            b.iter(|| black_box(reader.snapshot()))
+        });
+
+        stop.store(true, Ordering::Relaxed);
+        writer_handle.join().unwrap();
+        for handle in reader_handles {
+            handle.join().unwrap();
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, read_under_write_load);
+criterion_main!(benches);
@@ -1,7 +1,10 @@
 //! Single Market Synthetic Data Generation
 
 use atelier_data::{data, templates};
-use atelier_dcml::{features, targets};
+use atelier_dcml::{
+    datasets::{self, FromOrderbooks},
+    targets,
+};
 use atelier_synth::synthbooks::progressions;
 use std::{env, path::Path};
 
@@ -23,6 +26,11 @@ pub async fn main() {
         .unwrap()
         .clone();
 
+    if let Err(e) = template.validate() {
+        eprintln!("{}", e);
+        return;
+    }
+
     // --- Extract parameters from template
     let exp_id = &template.experiments[0].id;
     let n_progres = template.experiments[0].n_progressions as usize;
@@ -44,42 +52,26 @@ pub async fn main() {
 
     // data::write_to_json(orderbook.as_ref().unwrap(), &folder_route_ob);
 
-    // --- Compute Features from Orderbook Synthetic Data
+    // --- Features at t and Target at t+1 from Orderbook Synthetic Data
     let selected_features = ["spread", "midprice", "w_midprice", "vwap", "imb", "tav"];
-    let depth: usize = 10;
-    let bps: f64 = 1.0;
-    let features_vec = features::compute_features(
-        &orderbook.as_ref().unwrap(),
-        &selected_features,
-        depth,
-        bps,
-        features::FeaturesOutput::Values,
-    );
-
-    // println!("features_vec: {:?}", features_vec);
+    let feature_spec = datasets::FeatureSpec::new(&selected_features)
+        .unwrap()
+        .depth(10)
+        .bps(1.0);
 
-    // -- Compute Target from Orderbook Synthetic Data
     let selected_target = ["return_sign"];
-    let target_vec = targets::compute_targets(
-        &orderbook.as_ref().unwrap(),
-        &selected_target,
-        targets::TargetsOutput::Values,
-    );
+    let target_spec = targets::TargetSelector::new(&selected_target).unwrap();
 
-    // println!("target_vec {:?}", target_vec);
-
-    // --- Merge Features and Target
-
-    let pre_dataset = data::Dataset::new()
-        .features(features_vec.unwrap().clone())
-        .target(target_vec.unwrap().clone())
-        .build()
-        .unwrap();
+    let dataset = data::Dataset::from_orderbooks(
+        orderbook.as_ref().unwrap(),
+        &feature_spec,
+        &target_spec,
+        1,
+    )
+    .unwrap();
 
     // println!("index: {:?}, features: {:?}, target: {:?}",
-    //      dataset.index[0], dataset.features[0], dataset.target[0]);
-
-    let dataset = pre_dataset.shift_features();
+    //      dataset.index[0], dataset.row(0), dataset.target().values[0]);
 
     // --- Features and Target file (csv)
     let file_name_ft = exp_id.to_owned() + "_data.csv";
@@ -5,7 +5,11 @@ use std::{error::Error, path::Path};
 use tch::Device;
 
 use atelier_data::{
-    data::{Dataset, Transformation},
+    data::{
+        scalers::{write_scaler, MinMaxScaler, Scaler},
+        splits::Split,
+        Dataset,
+    },
     templates,
 };
 
@@ -25,11 +29,16 @@ pub fn main() -> Result<(), Box<dyn Error + 'static>> {
     let template = templates::Config::load_from_toml(template_file.to_str().unwrap())
         .unwrap()
         .clone();
+    template.validate()?;
 
     // --- Extract parameters from template
     let _exp_id = &template.experiments[0].id;
     let _n_progres = template.experiments[0].n_progressions as usize;
-    let optimizer_model = template.models[1].params_values.clone().unwrap();
+    let templates::ModelParams::GD { learning_rate, .. } =
+        template.models[1].model_params()?
+    else {
+        return Err("models[1] must be a GD optimizer".into());
+    };
 
     // --- Data Layer --- //
 
@@ -46,12 +55,18 @@ pub fn main() -> Result<(), Box<dyn Error + 'static>> {
     let column_types = None;
     let target_column = Some(7);
 
-    let mut a_dataset =
+    let a_dataset =
         Dataset::from_csv(&data_file, header, column_types, target_column).unwrap();
 
-    a_dataset.transform(Transformation::Scale);
+    println!("dataset.features: {:?}", a_dataset.feature_names());
 
-    println!("dataset.features: {:?}", a_dataset.features[0]);
+    // Chronological split, the test rows are kept out of training
+    let a_split = Split::chronological(a_dataset.len(), 0.8, 0.0, 1)?;
+    let mut train_dataset = a_split.train_view(&a_dataset)?.to_dataset();
+
+    // Scaling statistics come from the training rows only
+    let mut a_scaler = MinMaxScaler::new();
+    a_scaler.fit_transform(&mut train_dataset)?;
 
     // --- Model Layer --- //
     let n_inputs = 6;
@@ -67,7 +82,7 @@ pub fn main() -> Result<(), Box<dyn Error + 'static>> {
 
     let a_optimizer = optimizers::GradientDescent::new()
         .id("opt_00".to_string())
-        .learning_rate(optimizer_model[0])
+        .learning_rate(learning_rate)
         .build()
         .unwrap();
 
@@ -91,7 +106,7 @@ pub fn main() -> Result<(), Box<dyn Error + 'static>> {
     // --- Trainer Environment (Singular) --- //
 
     let mut singular = processes::Singular::new()
-        .data(a_dataset)
+        .data(train_dataset)
         .model(a_model)
         .loss(a_loss)
         .optimizer(a_optimizer)
@@ -113,5 +128,16 @@ pub fn main() -> Result<(), Box<dyn Error + 'static>> {
 
     singular.save_model(&model_file);
 
+    // --- Keep the scaler along with the model, for inference
+    let scaler_file = workspace_root
+        .join("examples")
+        .join("case_a")
+        .to_str()
+        .unwrap()
+        .to_owned()
+        + "/singular_scaler.json";
+
+    write_scaler(&a_scaler, &scaler_file)?;
+
     Ok(())
 }
@@ -0,0 +1,136 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    use atelier_data::{
+        levels::Level, orderbooks::Orderbook, orders::OrderSide, trades::Trade,
+    };
+
+    // ---------------------------------------------------------------- TEST TRADES -- //
+
+    /// Trades every 10 units of time from 0, buys but for every third one,
+    /// with quantities 1, 2, 3, 1, 2, 3, ...
+    pub fn test_trades(n_trades: usize) -> Vec<Trade> {
+        (0..n_trades)
+            .map(|i| {
+                let side = if i % 3 == 2 {
+                    OrderSide::Asks
+                } else {
+                    OrderSide::Bids
+                };
+                Trade::new(
+                    i as u64,
+                    10 * i as u64,
+                    side,
+                    100.0 + i as f64,
+                    1.0 + (i % 3) as f64,
+                )
+            })
+            .collect()
+    }
+
+    // ------------------------------------------------------------ TEST ORDERBOOKS -- //
+
+    /// One level books with the given midprices, every 10 units of time.
+    pub fn test_orderbooks(midprices: &[f64]) -> Vec<Orderbook> {
+        midprices
+            .iter()
+            .enumerate()
+            .map(|(i, mid)| {
+                Orderbook::new(
+                    i as u32,
+                    10 * i as u64,
+                    "BTCUSDT".to_string(),
+                    vec![Level::new(0, OrderSide::Bids, mid - 0.5, 1.0, vec![])],
+                    vec![Level::new(0, OrderSide::Asks, mid + 0.5, 1.0, vec![])],
+                )
+            })
+            .collect()
+    }
+}
+
+mod tests {
+
+    // ----------------------------------------------------------------- TRADE BARS -- //
+
+    #[test]
+    fn test_trade_bars() {
+        use crate::test_utils::test_trades;
+        use atelier_data::data::bars::{bars_from_trades, BarKind};
+
+        let trades = test_trades(7);
+
+        // time bars of 25: trades at 0, 10, 20 | 30, 40 | 50, 60
+        let bars = bars_from_trades(&trades, BarKind::Time(25)).unwrap();
+        let counts: Vec<usize> = bars.iter().map(|b| b.n_trades).collect();
+        assert_eq!(counts, vec![3, 2, 2]);
+        assert_eq!((bars[0].open, bars[0].close), (100.0, 102.0));
+        assert_eq!((bars[0].high, bars[0].low), (102.0, 100.0));
+        // (100 * 1 + 101 * 2 + 102 * 3) / 6
+        assert!((bars[0].vwap - 608.0 / 6.0).abs() < 1e-12);
+        assert_eq!(bars[0].tick_imbalance, 1.0);
+        assert_eq!(bars[0].volume_imbalance, 0.0);
+
+        let bars = bars_from_trades(&trades, BarKind::Tick(3)).unwrap();
+        let counts: Vec<usize> = bars.iter().map(|b| b.n_trades).collect();
+        assert_eq!(counts, vec![3, 3, 1]);
+
+        // quantities 1, 2, 3, 1, 2, 3, 1
+        let bars = bars_from_trades(&trades, BarKind::Volume(4.0)).unwrap();
+        let volumes: Vec<f64> = bars.iter().map(|b| b.volume).collect();
+        assert_eq!(volumes, vec![6.0, 6.0, 1.0]);
+
+        let bars = bars_from_trades(&trades, BarKind::Dollar(250.0)).unwrap();
+        assert_eq!(bars[0].n_trades, 2);
+
+        // signs +1, +1 | -1, +1, +1, -1, +1
+        let bars = bars_from_trades(&trades, BarKind::TickImbalance(2.0)).unwrap();
+        let counts: Vec<usize> = bars.iter().map(|b| b.n_trades).collect();
+        assert_eq!(counts, vec![2, 5]);
+
+        assert!(bars_from_trades(&trades, BarKind::Tick(0)).is_err());
+    }
+
+    // -------------------------------------------------------------- MIDPRICE BARS -- //
+
+    #[test]
+    fn test_midprice_bars() {
+        use crate::test_utils::test_orderbooks;
+        use atelier_data::data::bars::{bars_from_midprice, BarKind};
+
+        let books = test_orderbooks(&[100.0, 101.0, 101.0, 100.0, 99.0, 99.0]);
+
+        let bars = bars_from_midprice(&books, BarKind::Tick(3)).unwrap();
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[0].volume, 0.0);
+        assert!((bars[0].vwap - 302.0 / 3.0).abs() < 1e-12);
+        // tick rule: 0, +1, +1 | -1, -1, -1
+        assert_eq!(bars[0].tick_imbalance, 2.0);
+        assert_eq!(bars[1].tick_imbalance, -3.0);
+
+        assert!(bars_from_midprice(&books, BarKind::Volume(1.0)).is_err());
+    }
+
+    // ----------------------------------------------------------------- TO DATASET -- //
+
+    #[test]
+    fn test_bars_to_dataset() {
+        use crate::test_utils::test_trades;
+        use atelier_data::data::bars::{
+            bars_from_trades, bars_to_dataset, BarKind, BAR_FEATURES,
+        };
+
+        let bars = bars_from_trades(&test_trades(9), BarKind::Tick(3)).unwrap();
+        let dataset = bars_to_dataset(&bars).unwrap();
+
+        assert_eq!(dataset.len(), 3);
+        assert_eq!(dataset.index, vec![20, 50, 80]);
+        assert_eq!(dataset.feature_names(), BAR_FEATURES.to_vec());
+        assert_eq!(dataset.target().name, "bar_return");
+        assert!((dataset.target().values[0] - 0.02).abs() < 1e-12);
+        assert_eq!(dataset.features[9].values, vec![20.0, 20.0, 20.0]);
+    }
+}
@@ -0,0 +1,99 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    use atelier_data::orderbooks::Orderbook;
+
+    // ------------------------------------------------------------ TEST ORDERBOOKS -- //
+
+    /// Random books, with timestamps 1_000, 1_010, 1_020, ...
+    pub fn test_orderbooks(n_books: usize) -> Vec<Orderbook> {
+        (0..n_books)
+            .map(|i| {
+                let mut ob = Orderbook::random(
+                    100_000.0,
+                    Some((2, 5)),
+                    Some((1, 3)),
+                    Some((0.1, 0.5)),
+                    100_001.0,
+                    Some((2, 5)),
+                    Some((1, 3)),
+                );
+                ob.orderbook_ts = 1_000 + 10 * i as u64;
+                ob
+            })
+            .collect()
+    }
+}
+
+mod tests {
+
+    // ----------------------------------------------------------------- ROUND TRIP -- //
+
+    #[test]
+    fn test_round_trip() {
+        use crate::test_utils::test_orderbooks;
+        use atelier_data::data::{
+            load_orderbooks,
+            snapshots::{write_to_snapshots, SnapshotReader, FORMAT_VERSION},
+        };
+
+        let orderbooks = test_orderbooks(50);
+        let file_route = std::env::temp_dir().join("atelier_test_books.atb");
+        let file_route = file_route.to_str().unwrap();
+
+        write_to_snapshots(&orderbooks, file_route).unwrap();
+
+        let reader = SnapshotReader::open(file_route).unwrap();
+        assert_eq!(reader.version(), FORMAT_VERSION);
+        assert_eq!(reader.len(), 50);
+        assert_eq!(reader.time_range(), Some((1_000, 1_490)));
+
+        // through the generic loader, by extension
+        assert_eq!(load_orderbooks(file_route).unwrap(), orderbooks);
+
+        // only ndjson files are read compressed
+        let error = load_orderbooks("atelier_test_books.atb.gz").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "compressed atb is not supported: atelier_test_books.atb.gz"
+        );
+    }
+
+    // --------------------------------------------------------------- SEEK & RANGE -- //
+
+    #[test]
+    fn test_seek_and_range() {
+        use crate::test_utils::test_orderbooks;
+        use atelier_data::{
+            data::snapshots::{SnapshotReader, SnapshotWriter},
+            orderbooks::Orderbook,
+        };
+
+        let orderbooks = test_orderbooks(50);
+        let file_route = std::env::temp_dir().join("atelier_test_range.atb");
+        let file_route = file_route.to_str().unwrap();
+
+        let mut writer = SnapshotWriter::create(file_route, 8, 3).unwrap();
+        orderbooks.iter().for_each(|ob| writer.write(ob).unwrap());
+        // out of time order
+        assert!(writer.write(&orderbooks[0]).is_err());
+        writer.finish().unwrap();
+
+        let mut reader = SnapshotReader::open(file_route).unwrap();
+        assert_eq!(reader.index().len(), 7);
+
+        let ranged: Vec<Orderbook> = reader
+            .range(1_095, 1_200)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(ranged, orderbooks[10..20].to_vec());
+
+        let sought = reader.seek(1_480).next().unwrap().unwrap();
+        assert_eq!(sought, orderbooks[48]);
+        assert!(reader.seek(2_000).next().is_none());
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    use atelier_data::l2books::{L2Levels, L2Orderbook};
+
+    // ---------------------------------------------------------- TEST L2 ORDERBOOK -- //
+
+    pub fn test_l2_orderbook() -> L2Orderbook {
+        let mut bids = L2Levels::new();
+        bids.push(100.4, 1.0, 1);
+        bids.push(100.2, 2.0, 2);
+        bids.push(99.8, 3.0, 3);
+
+        let mut asks = L2Levels::new();
+        asks.push(100.6, 1.5, 1);
+        asks.push(100.9, 2.5, 2);
+        asks.push(101.3, 3.5, 3);
+
+        L2Orderbook::new(1, 1_000, String::from("BTCUSDT"), bids, asks)
+    }
+}
+
+mod tests {
+
+    // -------------------------------------------------------------- DEPTH QUERIES -- //
+
+    #[test]
+    fn test_depth_queries() {
+        use crate::test_utils::test_l2_orderbook;
+        use atelier_data::{orders::OrderSide, views::BookView};
+
+        let ob = test_l2_orderbook();
+
+        assert_eq!(ob.levels(OrderSide::Bids).count(), 3);
+        assert_eq!(ob.levels(OrderSide::Asks).len(), 3);
+        assert_eq!(ob.midprice(), Some(100.5));
+        assert!((ob.spread().unwrap() - 0.2).abs() < 1e-9);
+        assert_eq!(ob.volume_depth(OrderSide::Bids, 2), 3.0);
+        assert_eq!(ob.volume_within(OrderSide::Asks, 101.0), 4.0);
+        assert_eq!(ob.depth_within(OrderSide::Bids, 100.0), 2);
+    }
+
+    // ------------------------------------------------------------ AGGREGATED VIEW -- //
+
+    #[test]
+    fn test_aggregated_view() {
+        use crate::test_utils::test_l2_orderbook;
+        use atelier_data::{
+            orders::OrderSide,
+            views::{AggregatedView, BookView},
+        };
+
+        let ob = test_l2_orderbook();
+        let agg = AggregatedView::new(&ob, 1.0).unwrap();
+
+        // bids 100.4 and 100.2 share the 100.0 bucket
+        assert_eq!(agg.depth(OrderSide::Bids), 2);
+        let top_bid = agg.best_bid().unwrap();
+        assert_eq!(
+            (top_bid.price, top_bid.volume, top_bid.count),
+            (100.0, 3.0, 3)
+        );
+
+        // asks 100.6 and 100.9 share the 101.0 bucket
+        let top_ask = agg.best_ask().unwrap();
+        assert_eq!(
+            (top_ask.price, top_ask.volume, top_ask.count),
+            (101.0, 4.0, 3)
+        );
+        assert_eq!(agg.level(OrderSide::Asks, 1).unwrap().price, 102.0);
+
+        assert!(AggregatedView::new(&ob, 0.0).is_err());
+    }
+
+    // ----------------------------------------------------------------- BOOK SLICE -- //
+
+    #[test]
+    fn test_book_slice() {
+        use crate::test_utils::test_l2_orderbook;
+        use atelier_data::{orders::OrderSide, views::BookView};
+        use atelier_dcml::features;
+
+        let ob = test_l2_orderbook();
+        let top = ob.slice(1);
+
+        assert_eq!(top.depth(OrderSide::Bids), 1);
+        assert_eq!(top.best_ask(), ob.best_ask());
+        assert_eq!(top.timestamp(), ob.timestamp());
+
+        // top of the book features do not depend on the levels below
+        assert_eq!(
+            features::compute_w_midprice(&top),
+            features::compute_w_midprice(&ob)
+        );
+    }
+}
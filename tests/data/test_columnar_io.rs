@@ -0,0 +1,228 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    use atelier_data::{
+        data::{Column, Dataset},
+        levels::Level,
+        orderbooks::Orderbook,
+        orders::OrderSide,
+    };
+
+    // --------------------------------------------------------------- TEST DATASET -- //
+
+    pub fn test_dataset(n_rows: usize) -> Dataset {
+        Dataset::new()
+            .index((0..n_rows as u64).map(|i| 1_000 + i * 100).collect())
+            .columns(vec![
+                Column::new("spread", (0..n_rows).map(|i| i as f64).collect()),
+                Column::new("midprice", (0..n_rows).map(|i| 100.0 + i as f64).collect()),
+                Column::new("vwap", (0..n_rows).map(|i| -(i as f64)).collect()),
+            ])
+            .targets(vec![
+                Column::new(
+                    "return_sign_1",
+                    (0..n_rows).map(|i| (i % 2) as f64).collect(),
+                ),
+                Column::new("return_sign_5", vec![1.0; n_rows]),
+            ])
+            .build()
+            .unwrap()
+    }
+
+    // ------------------------------------------------------------- TEST ORDERBOOKS -- //
+
+    /// Books with 3 bid levels and 2 ask levels, the last one with no asks.
+    pub fn test_orderbooks(n_books: usize) -> Vec<Orderbook> {
+        (0..n_books)
+            .map(|i| {
+                let mid = 100.0 + i as f64;
+                let bids = (0..3)
+                    .map(|l| {
+                        Level::new(
+                            l,
+                            OrderSide::Bids,
+                            mid - 1.0 - l as f64,
+                            10.0 + l as f64,
+                            vec![],
+                        )
+                    })
+                    .collect();
+                let asks = if i == n_books - 1 {
+                    vec![]
+                } else {
+                    (0..2)
+                        .map(|l| {
+                            Level::new(
+                                l,
+                                OrderSide::Asks,
+                                mid + 1.0 + l as f64,
+                                5.0 + l as f64,
+                                vec![],
+                            )
+                        })
+                        .collect()
+                };
+                Orderbook::new(7, 1_000 + i as u64, "BTCUSDT".to_string(), bids, asks)
+            })
+            .collect()
+    }
+}
+
+mod tests {
+
+    // --------------------------------------------------------- DATASET ROUND TRIP -- //
+
+    #[test]
+    fn test_dataset_round_trip() {
+        use crate::test_utils::test_dataset;
+        use atelier_data::data::{columnar::write_to_parquet, Dataset};
+
+        let mut dataset = test_dataset(10);
+        dataset.set_target("return_sign_5").unwrap();
+
+        let file_route = std::env::temp_dir().join("atelier_test_dataset.parquet");
+        let file_route = file_route.to_str().unwrap();
+
+        write_to_parquet(&dataset, file_route, 4).unwrap();
+        let loaded = Dataset::from_parquet(file_route, None).unwrap();
+
+        assert_eq!(loaded.index, dataset.index);
+        assert_eq!(loaded.features, dataset.features);
+        assert_eq!(loaded.targets, dataset.targets);
+        assert_eq!(loaded.target().name, "return_sign_5");
+    }
+
+    // ------------------------------------------------- DATASET PROJECTION & CHUNKS -- //
+
+    #[test]
+    fn test_dataset_projection_and_chunks() {
+        use crate::test_utils::test_dataset;
+        use atelier_data::data::{
+            columnar::{write_to_parquet, DatasetChunks},
+            Dataset,
+        };
+
+        let dataset = test_dataset(10);
+        let file_route = std::env::temp_dir().join("atelier_test_dataset_chunks.parquet");
+        let file_route = file_route.to_str().unwrap();
+        write_to_parquet(&dataset, file_route, 4).unwrap();
+
+        let projected =
+            Dataset::from_parquet(file_route, Some(&["vwap", "spread"])).unwrap();
+        assert_eq!(projected.feature_names(), vec!["spread", "vwap"]);
+        assert_eq!(
+            projected.target_names(),
+            vec!["return_sign_1", "return_sign_5"]
+        );
+        assert!(Dataset::from_parquet(file_route, Some(&["imbalance"])).is_err());
+
+        let chunks: Vec<Dataset> =
+            DatasetChunks::open(file_route, Some(&["midprice"]), 3)
+                .unwrap()
+                .collect::<Result<_, _>>()
+                .unwrap();
+
+        assert!(chunks.iter().all(|chunk| chunk.len() <= 3));
+        assert_eq!(chunks.iter().map(|chunk| chunk.len()).sum::<usize>(), 10);
+        assert_eq!(chunks[1].index[0], dataset.index[chunks[0].len()]);
+    }
+
+    // --------------------------------------------------------- ORDERBOOK LAYOUTS -- //
+
+    #[test]
+    fn test_orderbook_layouts() {
+        use crate::test_utils::test_orderbooks;
+        use atelier_data::data::columnar::{
+            load_orderbooks_from_parquet, write_orderbooks_to_parquet, BookLayout,
+            OrderbookParquetReader, OrderbookParquetWriter,
+        };
+
+        let orderbooks = test_orderbooks(5);
+
+        // one row per level, split across several row groups and batches
+        let file_route = std::env::temp_dir().join("atelier_test_levels.parquet");
+        let file_route = file_route.to_str().unwrap();
+
+        let mut writer =
+            OrderbookParquetWriter::create(file_route, BookLayout::Levels, 4).unwrap();
+        orderbooks.iter().for_each(|ob| writer.write(ob).unwrap());
+        writer.close().unwrap();
+
+        let loaded: Vec<_> = OrderbookParquetReader::open(file_route, None, 3)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(loaded, orderbooks);
+
+        let top = load_orderbooks_from_parquet(file_route, Some(1)).unwrap();
+        assert!(top
+            .iter()
+            .all(|ob| ob.bids.len() == 1 && ob.asks.len() <= 1));
+        assert_eq!(top[0].bids[0], orderbooks[0].bids[0]);
+
+        // top-N columns, missing levels are nulls
+        let file_route = std::env::temp_dir().join("atelier_test_wide.parquet");
+        let file_route = file_route.to_str().unwrap();
+
+        write_orderbooks_to_parquet(&orderbooks, file_route, BookLayout::Wide(2))
+            .unwrap();
+        let loaded = load_orderbooks_from_parquet(file_route, None).unwrap();
+
+        assert_eq!(loaded.len(), orderbooks.len());
+        assert_eq!(loaded[0].bids, orderbooks[0].bids[..2].to_vec());
+        assert_eq!(loaded[0].asks, orderbooks[0].asks);
+        assert!(loaded[4].asks.is_empty());
+    }
+
+    // ------------------------------------------------- LEVELS REPEATED SNAPSHOTS -- //
+
+    #[test]
+    fn test_levels_repeated_snapshots() {
+        use crate::test_utils::test_orderbooks;
+        use atelier_data::{
+            data::columnar::{
+                load_orderbooks_from_parquet, BookLayout, OrderbookParquetWriter,
+            },
+            orderbooks::Orderbook,
+        };
+
+        // same id and timestamp in every book, with empty books in between and last
+        let mut orderbooks: Vec<Orderbook> = test_orderbooks(4)
+            .into_iter()
+            .map(|mut ob| {
+                ob.orderbook_ts = 1_000;
+                ob
+            })
+            .collect();
+        orderbooks.insert(
+            2,
+            Orderbook::new(7, 1_000, "BTCUSDT".to_string(), vec![], vec![]),
+        );
+        orderbooks.push(Orderbook::new(
+            7,
+            1_000,
+            "BTCUSDT".to_string(),
+            vec![],
+            vec![],
+        ));
+
+        let file_route = std::env::temp_dir().join("atelier_test_repeated.parquet");
+        let file_route = file_route.to_str().unwrap();
+
+        let mut writer =
+            OrderbookParquetWriter::create(file_route, BookLayout::Levels, 3).unwrap();
+        orderbooks.iter().for_each(|ob| writer.write(ob).unwrap());
+        writer.close().unwrap();
+
+        let loaded = load_orderbooks_from_parquet(file_route, None).unwrap();
+        assert_eq!(loaded, orderbooks);
+
+        let top = load_orderbooks_from_parquet(file_route, Some(1)).unwrap();
+        assert_eq!(top.len(), orderbooks.len());
+        assert!(top[2].bids.is_empty() && top[2].asks.is_empty());
+    }
+}
@@ -0,0 +1,129 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    use atelier_data::templates::Config;
+    use std::path::{Path, PathBuf};
+
+    // ---------------------------------------------------------------- LOAD CONFIG -- //
+
+    /// Route of a file of the workspace.
+    pub fn workspace_file(route: &[&str]) -> PathBuf {
+        let workspace_root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        route
+            .iter()
+            .fold(workspace_root.to_path_buf(), |path, part| path.join(part))
+    }
+
+    /// Loads a config file of the workspace.
+    pub fn workspace_config(route: &[&str]) -> Config {
+        Config::load_from_toml(workspace_file(route).to_str().unwrap()).unwrap()
+    }
+}
+
+mod tests {
+
+    // ----------------------------------------------------------------- ROUND TRIP -- //
+
+    #[test]
+    fn test_resolved_round_trip() {
+        use crate::test_utils::workspace_config;
+        use atelier_data::templates::{Config, ModelParams};
+
+        for route in [
+            ["examples", "case_a", "config_a.toml"],
+            ["atelier-synth", "templates", "single_orderbook.toml"],
+            ["atelier-synth", "templates", "multi_orderbooks.toml"],
+            ["atelier-synth", "templates", "sweep_orderbooks.toml"],
+        ] {
+            let config = workspace_config(&route);
+            let resolved = config.resolved();
+            let contents = resolved.to_toml().unwrap();
+            let loaded: Config = toml::from_str(&contents).unwrap();
+
+            assert_eq!(loaded.experiments.len(), config.experiments.len());
+            assert_eq!(loaded.exchanges.len(), config.exchanges.len());
+            assert_eq!(loaded.experiments[0].sweeps, config.experiments[0].sweeps);
+            for (model, original) in loaded.models.iter().zip(&config.models) {
+                assert_eq!(model.id, original.id);
+                assert_eq!(model.model_params(), original.model_params());
+                assert!(model.params_values.is_none());
+            }
+        }
+
+        // Defaults are written, positional parameters by name
+        let config =
+            workspace_config(&["atelier-synth", "templates", "multi_orderbooks.toml"]);
+        let resolved = config.resolved();
+        assert_eq!(
+            resolved.models[0].params,
+            Some(ModelParams::GBM {
+                mu: 0.01,
+                sigma: 0.001,
+                dt: 0.01
+            })
+        );
+        let contents = resolved.to_toml().unwrap();
+        assert!(contents.contains("label = \"GBM\""));
+        assert!(contents.contains("dt = 0.01"));
+        assert!(!contents.contains("params_values"));
+
+        let json = serde_json::to_value(&resolved).unwrap();
+        assert_eq!(json["models"][0]["sigma"], 0.001);
+    }
+
+    // ---------------------------------------------------------------- JSON SCHEMA -- //
+
+    #[test]
+    fn test_json_schema() {
+        use crate::test_utils::workspace_file;
+        use atelier_data::templates::Config;
+
+        let schema = Config::json_schema();
+        let json: serde_json::Value = serde_json::from_str(&schema).unwrap();
+        let definitions = &json["definitions"];
+        for name in ["ExpConfig", "ExchangeConfig", "ModelConfig", "SweepConfig"] {
+            assert!(definitions.get(name).is_some(), "missing {}", name);
+        }
+        assert!(definitions["ModelConfig"]["properties"]
+            .get("learning_rate")
+            .is_some());
+
+        // The schema of the templates is kept up to date
+        let schema_file =
+            workspace_file(&["atelier-synth", "templates", "config.schema.json"]);
+        let contents = std::fs::read_to_string(schema_file).unwrap();
+        assert_eq!(
+            contents,
+            schema + "\n",
+            "run: cargo run -p atelier_data --example config_schema -- atelier-synth/templates/config.schema.json"
+        );
+
+        // Every template is valid, lowercase model labels included
+        let schema = jsonschema::JSONSchema::compile(&json).unwrap();
+        for name in [
+            "single_orderbook.toml",
+            "multi_orderbooks.toml",
+            "sweep_orderbooks.toml",
+        ] {
+            let route = workspace_file(&["atelier-synth", "templates", name]);
+            let contents = std::fs::read_to_string(route).unwrap();
+            let template: toml::Value = toml::from_str(&contents).unwrap();
+            let instance = serde_json::to_value(template).unwrap();
+
+            let errors: Vec<String> = match schema.validate(&instance) {
+                Ok(()) => Vec::new(),
+                Err(errors) => errors.map(|e| e.to_string()).collect(),
+            };
+            assert!(
+                errors.is_empty(),
+                "{} does not match the schema: {:?}",
+                name,
+                errors
+            );
+        }
+    }
+}
@@ -0,0 +1,151 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    use atelier_data::templates::Config;
+    use std::path::Path;
+
+    // ---------------------------------------------------------------- LOAD CONFIG -- //
+
+    /// Loads a config file of the workspace.
+    pub fn workspace_config(route: &[&str]) -> Config {
+        let workspace_root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let file_route = route
+            .iter()
+            .fold(workspace_root.to_path_buf(), |path, part| path.join(part));
+        Config::load_from_toml(file_route.to_str().unwrap()).unwrap()
+    }
+
+    /// Loads a config from a TOML string.
+    pub fn toml_config(name: &str, contents: &str) -> Config {
+        let file_route = std::env::temp_dir().join(name);
+        std::fs::write(&file_route, contents).unwrap();
+        Config::load_from_toml(file_route.to_str().unwrap()).unwrap()
+    }
+}
+
+mod tests {
+
+    // -------------------------------------------------------------- VALID CONFIGS -- //
+
+    #[test]
+    fn test_workspace_configs() {
+        use crate::test_utils::workspace_config;
+
+        let config = workspace_config(&["examples", "case_a", "config_a.toml"]);
+        assert_eq!(config.validate(), Ok(()));
+
+        let config =
+            workspace_config(&["atelier-synth", "templates", "single_orderbook.toml"]);
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    // ------------------------------------------------------------- INVALID CONFIG -- //
+
+    #[test]
+    fn test_every_issue_reported() {
+        use crate::test_utils::toml_config;
+
+        let config = toml_config(
+            "atelier_test_invalid_config.toml",
+            r#"
+            [[experiments]]
+            id = "exp_00"
+            n_progressions = 1
+
+            [[exchanges]]
+            id = "exc_00"
+            region = "region_a"
+            name = "exchange_a"
+            category = "cex"
+
+            [exchanges.orderbook]
+            bid_price = 100.0
+            bid_levels = [50, 10]
+            bid_orders = [5]
+            ticksize = [0.0, 1.0]
+            ask_price = 99.0
+            ask_levels = [10, 50]
+            ask_orders = [5, 15]
+
+            [[exchanges]]
+            id = "exc_01"
+            region = "region_a"
+            name = "exchange_b"
+            category = "cex"
+
+            [[models]]
+            id = "returns_00"
+            label = "Uniform"
+            description = "Uniform Distribution"
+            params_labels = ["lower", "upper"]
+            params_values = [0.005, -0.005]
+
+            [[models]]
+            id = "returns_01"
+            label = "GBM"
+            description = "Geometric Brownian Motion"
+            params_labels = ["mu"]
+            params_values = [0.01, -0.1]
+            "#,
+        );
+
+        let error = config.validate().unwrap_err();
+        let issues: Vec<String> = error.issues.iter().map(|i| i.to_string()).collect();
+        assert_eq!(
+            issues,
+            vec![
+                "experiments[0].n_progressions must be at least 2",
+                "exchanges[0].orderbook.ask_price must exceed bid_price",
+                "exchanges[0].orderbook.bid_levels must be [min, max] with min < max",
+                "exchanges[0].orderbook.bid_orders must be [min, max] with min < max",
+                "exchanges[0].orderbook.ticksize min must be greater than 0",
+                "exchanges[1].orderbook is missing",
+                "models[0].params_values[1] upper must exceed lower",
+                "models[1].params_labels must have one label per value of params_values",
+                "models[1].params_values[1] sigma must be greater than 0",
+            ]
+        );
+        assert!(error
+            .to_string()
+            .starts_with("Invalid configuration, 9 problem(s):"));
+    }
+
+    // ------------------------------------------------------------------- BUILDERS -- //
+
+    #[test]
+    fn test_builders() {
+        use atelier_data::templates::{ModelConfig, Models, OrderbookConfig};
+
+        // rands is optional
+        let orderbook = OrderbookConfig::builder()
+            .bid_price(100.0)
+            .bid_levels(vec![2, 5])
+            .bid_orders(vec![1, 3])
+            .ticksize(vec![0.1, 0.5])
+            .ask_price(101.0)
+            .ask_levels(vec![2, 5])
+            .ask_orders(vec![1, 3])
+            .build()
+            .unwrap();
+        assert_eq!(orderbook.validate(), Ok(()));
+        assert!(orderbook.rands.is_none());
+
+        let model = ModelConfig::builder()
+            .id("hawkes_00".to_string())
+            .label(Models::Hawkes)
+            .description("Hawkes process".to_string())
+            .params_labels(vec!["mu".into(), "alpha".into(), "beta".into()])
+            .params_values(vec![0.5, 0.8, 0.4])
+            .build()
+            .unwrap();
+        let error = model.validate().unwrap_err();
+        assert_eq!(
+            error.issues[0].to_string(),
+            "params_values[2] beta must exceed alpha"
+        );
+    }
+}
@@ -0,0 +1,106 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    use atelier_data::data::Dataset;
+
+    // --------------------------------------------------------------- TEST DATASET -- //
+
+    pub fn test_dataset(n_rows: usize) -> Dataset {
+        Dataset::new()
+            .features((0..n_rows).map(|i| vec![i as f64, -(i as f64)]).collect())
+            .target((0..n_rows).map(|i| (i % 2) as f64).collect())
+            .build()
+            .unwrap()
+    }
+}
+
+mod tests {
+
+    // ----------------------------------------------------------------- BATCH ROWS -- //
+
+    #[test]
+    fn test_batch_rows() {
+        use crate::test_utils::test_dataset;
+        use atelier_data::data::loader::DataLoader;
+
+        let loader = DataLoader::new()
+            .dataset(test_dataset(10))
+            .batch_size(4)
+            .build()
+            .unwrap();
+
+        assert_eq!(loader.len(), 3);
+        assert_eq!(
+            loader.batch_rows(0),
+            vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![8, 9]]
+        );
+
+        let loader = DataLoader::new()
+            .dataset(test_dataset(10))
+            .batch_size(4)
+            .drop_last(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(loader.len(), 2);
+        assert_eq!(loader.batch_rows(0).len(), 2);
+
+        assert!(DataLoader::new().dataset(test_dataset(10)).build().is_err());
+    }
+
+    // -------------------------------------------------------------------- SHUFFLE -- //
+
+    #[test]
+    fn test_shuffle_within_windows() {
+        use crate::test_utils::test_dataset;
+        use atelier_data::data::loader::DataLoader;
+
+        let loader = DataLoader::new()
+            .dataset(test_dataset(100))
+            .batch_size(10)
+            .shuffle(20)
+            .seed(7)
+            .build()
+            .unwrap();
+
+        let rows: Vec<usize> = loader.batch_rows(0).concat();
+
+        // every row once, and never out of its window
+        let mut sorted = rows.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..100).collect::<Vec<usize>>());
+        for (position, row) in rows.iter().enumerate() {
+            assert_eq!(position / 20, row / 20);
+        }
+
+        // reproducible by seed and epoch
+        assert_eq!(loader.batch_rows(0), loader.batch_rows(0));
+        assert_ne!(loader.batch_rows(0), loader.batch_rows(1));
+    }
+
+    // ------------------------------------------------------------------- PREFETCH -- //
+
+    #[test]
+    fn test_prefetched_batches() {
+        use crate::test_utils::test_dataset;
+        use atelier_data::data::loader::DataLoader;
+
+        let loader = DataLoader::new()
+            .dataset(test_dataset(50))
+            .batch_size(8)
+            .prefetch(2)
+            .build()
+            .unwrap();
+
+        assert_eq!(loader.batches(0).count(), loader.len());
+
+        // dropping the iterator early stops the background thread
+        let mut batches = loader.batches(1);
+        assert!(batches.next().is_some());
+        drop(batches);
+    }
+}
@@ -0,0 +1,131 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    use atelier_data::data::Dataset;
+
+    // --------------------------------------------------------------- TEST DATASET -- //
+
+    pub fn test_dataset() -> Dataset {
+        Dataset::new()
+            .index(vec![1_000, 1_100, 1_200, 1_300])
+            .features(vec![
+                vec![1.0, 10.0, 100.0],
+                vec![2.0, 20.0, 200.0],
+                vec![3.0, 30.0, 300.0],
+                vec![4.0, 40.0, 400.0],
+            ])
+            .feature_names(vec![
+                "spread".to_string(),
+                "midprice".to_string(),
+                "vwap".to_string(),
+            ])
+            .target(vec![0.0, 1.0, 0.0, 1.0])
+            .target_name("return_sign")
+            .build()
+            .unwrap()
+    }
+}
+
+mod tests {
+
+    // -------------------------------------------------------------- NAMED COLUMNS -- //
+
+    #[test]
+    fn test_named_columns() {
+        use crate::test_utils::test_dataset;
+
+        let dataset = test_dataset();
+
+        assert_eq!(dataset.feature_names(), vec!["spread", "midprice", "vwap"]);
+        assert_eq!(dataset.column_index("vwap"), Some(2));
+        assert_eq!(
+            dataset.column("midprice").unwrap().values,
+            vec![10.0, 20.0, 30.0, 40.0]
+        );
+        assert_eq!(dataset.row(1), vec![2.0, 20.0, 200.0]);
+        assert_eq!(
+            dataset.get_sample_by_index(1_200),
+            Some((vec![3.0, 30.0, 300.0], 0.0))
+        );
+
+        let selected = dataset.select(&["vwap", "spread"]).unwrap();
+        assert_eq!(selected.feature_names(), vec!["vwap", "spread"]);
+        assert_eq!(selected.row(0), vec![100.0, 1.0]);
+
+        let dropped = dataset.drop(&["midprice"]).unwrap();
+        assert_eq!(dropped.feature_names(), vec!["spread", "vwap"]);
+
+        assert!(dataset.select(&["imb"]).is_err());
+        assert!(dataset.drop(&["imb"]).is_err());
+    }
+
+    // ------------------------------------------------------------- SHIFT FEATURES -- //
+
+    #[test]
+    fn test_shift_features() {
+        use crate::test_utils::test_dataset;
+
+        let shifted = test_dataset().shift_features();
+
+        assert_eq!(shifted.len(), 3);
+        assert_eq!(shifted.index, vec![1_100, 1_200, 1_300]);
+        assert_eq!(shifted.row(0), vec![2.0, 20.0, 200.0]);
+        assert_eq!(shifted.target().values, vec![0.0, 1.0, 0.0]);
+        assert_eq!(shifted.target().name, "return_sign");
+    }
+
+    // ------------------------------------------------------------------ CSV ROUND -- //
+
+    #[test]
+    fn test_csv_round_trip() {
+        use crate::test_utils::test_dataset;
+        use atelier_data::data::{write_to_csv, Dataset};
+
+        let dataset = test_dataset();
+        let file_route = std::env::temp_dir().join("atelier_test_dataset.csv");
+        let file_route = file_route.to_str().unwrap();
+
+        write_to_csv(&dataset, file_route);
+        let loaded = Dataset::from_csv(file_route, true, None, None).unwrap();
+
+        assert_eq!(loaded.index, dataset.index);
+        assert_eq!(loaded.feature_names(), dataset.feature_names());
+        assert_eq!(loaded.target(), dataset.target());
+        assert_eq!(loaded.get_pairs(), dataset.get_pairs());
+    }
+
+    // -------------------------------------------------------------- MULTI TARGETS -- //
+
+    #[test]
+    fn test_multiple_targets() {
+        use atelier_data::data::{Column, Dataset};
+
+        let mut dataset = Dataset::new()
+            .index(vec![1_000, 1_100, 1_200])
+            .features(vec![vec![1.0], vec![2.0], vec![3.0]])
+            .feature_names(vec!["spread".to_string()])
+            .targets(vec![
+                Column::new("return_sign_1", vec![0.0, 1.0, 1.0]),
+                Column::new("return_sign_5", vec![1.0, 0.0, 1.0]),
+            ])
+            .build()
+            .unwrap();
+
+        assert_eq!(dataset.target_names(), vec!["return_sign_1", "return_sign_5"]);
+        assert_eq!(dataset.target().name, "return_sign_1");
+
+        dataset.set_target("return_sign_5").unwrap();
+        assert_eq!(dataset.target().values, vec![1.0, 0.0, 1.0]);
+        assert!(dataset.set_target("return_sign_10").is_err());
+
+        // every target follows the rows kept
+        let shifted = dataset.shift_features();
+        assert_eq!(shifted.target().name, "return_sign_5");
+        assert_eq!(shifted.targets[0].values, vec![0.0, 1.0]);
+        assert_eq!(shifted.targets[1].values, vec![1.0, 0.0]);
+    }
+}
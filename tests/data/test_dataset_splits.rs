@@ -0,0 +1,105 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    use atelier_data::data::Dataset;
+
+    // --------------------------------------------------------------- TEST DATASET -- //
+
+    pub fn test_dataset(n_rows: usize) -> Dataset {
+        Dataset::new()
+            .index((0..n_rows as u64).map(|i| 1_000 + i * 100).collect())
+            .features((0..n_rows).map(|i| vec![i as f64]).collect())
+            .target((0..n_rows).map(|i| (i % 2) as f64).collect())
+            .build()
+            .unwrap()
+    }
+}
+
+mod tests {
+
+    // -------------------------------------------------------------- CHRONOLOGICAL -- //
+
+    #[test]
+    fn test_chronological_split() {
+        use crate::test_utils::test_dataset;
+        use atelier_data::data::splits::Split;
+
+        let dataset = test_dataset(20);
+        let split = Split::chronological(dataset.len(), 0.6, 0.2, 1).unwrap();
+
+        assert_eq!(split.train, (0..12).collect::<Vec<usize>>());
+        assert_eq!(split.validation, (13..16).collect::<Vec<usize>>());
+        assert_eq!(split.test, (17..20).collect::<Vec<usize>>());
+
+        let test_data = split.test_view(&dataset).unwrap().to_dataset();
+        assert_eq!(test_data.index, vec![2_700, 2_800, 2_900]);
+        assert_eq!(test_data.row(0), vec![17.0]);
+
+        assert!(Split::chronological(dataset.len(), 0.8, 0.3, 0).is_err());
+        assert!(dataset.view(vec![20]).is_err());
+    }
+
+    // --------------------------------------------------------------- WALK FORWARD -- //
+
+    #[test]
+    fn test_walk_forward() {
+        use atelier_data::data::splits::{WalkForward, WindowType};
+
+        let expanding = WalkForward::new()
+            .n_folds(3)
+            .test_size(2)
+            .embargo(1)
+            .build()
+            .unwrap()
+            .folds(10)
+            .unwrap();
+
+        assert_eq!(expanding.len(), 3);
+        assert_eq!(expanding[0].train, (0..3).collect::<Vec<usize>>());
+        assert_eq!(expanding[0].test, vec![4, 5]);
+        assert_eq!(expanding[2].train, (0..7).collect::<Vec<usize>>());
+        assert_eq!(expanding[2].test, vec![8, 9]);
+
+        let rolling = WalkForward::new()
+            .n_folds(3)
+            .test_size(2)
+            .train_size(3)
+            .window(WindowType::Rolling)
+            .build()
+            .unwrap()
+            .folds(10)
+            .unwrap();
+
+        assert_eq!(rolling[0].train, vec![1, 2, 3]);
+        assert_eq!(rolling[2].train, vec![5, 6, 7]);
+        assert!(rolling.iter().all(|fold| fold.train.len() == 3));
+    }
+
+    // -------------------------------------------------------------- PURGED K-FOLD -- //
+
+    #[test]
+    fn test_purged_k_fold() {
+        use atelier_data::data::splits::PurgedKFold;
+
+        let folds = PurgedKFold::new()
+            .n_folds(3)
+            .horizon(1)
+            .embargo(1)
+            .build()
+            .unwrap()
+            .folds(9)
+            .unwrap();
+
+        assert_eq!(folds[1].test, vec![3, 4, 5]);
+        // row 2 overlaps the test labels, rows 6 (horizon) and 7 (embargo) too
+        assert_eq!(folds[1].train, vec![0, 1, 8]);
+
+        for fold in folds.iter() {
+            assert!(fold.train.iter().all(|i| !fold.test.contains(i)));
+        }
+    }
+}
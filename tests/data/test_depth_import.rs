@@ -0,0 +1,112 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    // ----------------------------------------------------------------- TEST FILES -- //
+
+    pub fn write_file(file_name: &str, contents: &str) -> String {
+        let file_route = std::env::temp_dir().join(file_name);
+        std::fs::write(&file_route, contents).unwrap();
+        file_route.to_str().unwrap().to_string()
+    }
+}
+
+mod tests {
+
+    // --------------------------------------------------------------- JSON CAPTURE -- //
+
+    #[test]
+    fn test_json_capture() {
+        use crate::test_utils::write_file;
+        use atelier_data::data::depth::{DepthImporter, SyncReport};
+
+        let snapshot = write_file(
+            "atelier_test_depth_snapshot.json",
+            r#"{"lastUpdateId":100,"bids":[["99.0","1.0"],["98.0","2.0"]],"asks":[["101.0","1.5"]]}
+"#,
+        );
+        let updates = write_file(
+            "atelier_test_depth_updates.json",
+            r#"{"result":null,"id":1}
+{"e":"depthUpdate","E":1000,"s":"BTCUSDT","U":90,"u":95,"b":[["99.0","5.0"]],"a":[]}
+{"e":"depthUpdate","E":1001,"s":"BTCUSDT","U":96,"u":102,"b":[["99.0","0.5"]],"a":[["102.0","3.0"]]}
+{"e":"depthUpdate","E":1002,"s":"ETHUSDT","U":1,"u":2,"b":[["9.0","1.0"]],"a":[]}
+{"stream":"btcusdt@depth","data":{"e":"depthUpdate","E":1003,"s":"BTCUSDT","U":103,"u":105,"b":[["98.0","0"]],"a":[]}}
+{"e":"depthUpdate","E":1004,"s":"BTCUSDT","U":107,"u":108,"b":[["97.0","1.0"]],"a":[]}
+{"e":"depthUpdate","E":1005,"s":"BTCUSDT","U":109,"u":110,"b":[["96.0","1.0"]],"a":[]}
+{"lastUpdateId":200,"bids":[["99.5","1.0"]],"asks":[["100.5","1.0"]]}
+{"e":"depthUpdate","E":1006,"s":"BTCUSDT","U":199,"u":201,"b":[],"a":[["100.5","2.0"]]}
+"#,
+        );
+
+        let importer = DepthImporter::new().symbol("btcusdt").build().unwrap();
+        let replay = importer.from_json(&[&snapshot, &updates]).unwrap();
+
+        assert_eq!(
+            replay.report,
+            SyncReport {
+                snapshots: 2,
+                applied: 3,
+                unsynced: 1,
+                stale: 1,
+                gaps: 1,
+            }
+        );
+        assert_eq!(replay.orderbooks.len(), 5);
+
+        // after the first applied update, in microseconds
+        let book = &replay.orderbooks[1];
+        assert_eq!(book.orderbook_ts, 1_001_000);
+        assert_eq!(book.bids[0].volume, 0.5);
+        assert_eq!(book.asks.len(), 2);
+
+        // the level with quantity 0 is removed
+        assert_eq!(replay.orderbooks[2].bids.len(), 1);
+
+        let last = replay.orderbooks.last().unwrap();
+        assert_eq!(last.bids[0].price, 99.5);
+        assert_eq!(last.asks[0].volume, 2.0);
+    }
+
+    // ---------------------------------------------------------------- CSV CAPTURE -- //
+
+    #[test]
+    fn test_csv_capture() {
+        use crate::test_utils::write_file;
+        use atelier_data::data::depth::DepthImporter;
+
+        let capture = write_file(
+            "atelier_test_depth_capture.csv",
+            "timestamp,symbol,side,price,amount,is_snapshot,first_update_id,last_update_id
+10,BTCUSDT,bid,99.0,1.0,true,,50
+10,BTCUSDT,bid,98.0,2.0,true,,50
+10,BTCUSDT,ask,101.0,1.0,true,,50
+11,BTCUSDT,ask,101.0,0,false,40,45
+12,BTCUSDT,ask,101.0,0,false,49,52
+12,BTCUSDT,ask,102.0,4.0,false,49,52
+12,ETHUSDT,ask,10.0,4.0,false,1,1
+13,BTCUSDT,bid,99.0,3.0,false,53,53
+",
+        );
+
+        let importer = DepthImporter::new()
+            .symbol("BTCUSDT")
+            .depth(1)
+            .build()
+            .unwrap();
+        let replay = importer.from_csv(&capture).unwrap();
+
+        assert_eq!(replay.report.snapshots, 1);
+        assert_eq!(replay.report.stale, 1);
+        assert_eq!(replay.report.applied, 2);
+        assert_eq!(replay.orderbooks.len(), 3);
+
+        assert_eq!(replay.orderbooks[0].bids.len(), 1);
+        assert_eq!(replay.orderbooks[1].orderbook_ts, 12);
+        assert_eq!(replay.orderbooks[1].asks[0].price, 102.0);
+        assert_eq!(replay.orderbooks[2].bids[0].volume, 3.0);
+    }
+}
@@ -0,0 +1,111 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    use atelier_data::orderbooks::Orderbook;
+
+    // ------------------------------------------------------------- TEST ORDERBOOK -- //
+
+    pub fn test_orderbook() -> Orderbook {
+        Orderbook::random(
+            100_000.0,
+            Some((5, 10)),
+            Some((20, 30)),
+            Some((0.1, 1.0)),
+            100_001.0,
+            Some((5, 10)),
+            Some((20, 30)),
+        )
+    }
+}
+
+mod tests {
+
+    // ------------------------------------------------------------- FROM ORDERBOOK -- //
+
+    #[test]
+    fn test_l2_from_orderbook() {
+        use crate::test_utils::test_orderbook;
+        use atelier_data::{l2books::L2Orderbook, orders::OrderSide, views::BookView};
+
+        let ob_data = test_orderbook();
+        let l2_data = L2Orderbook::from(&ob_data);
+
+        assert_eq!(l2_data.depth(OrderSide::Bids), ob_data.bids.len());
+        assert_eq!(l2_data.depth(OrderSide::Asks), ob_data.asks.len());
+
+        for side in [OrderSide::Bids, OrderSide::Asks] {
+            for i in 0..ob_data.depth(side) {
+                assert_eq!(l2_data.level(side, i), ob_data.level(side, i));
+            }
+        }
+
+        assert_eq!(
+            l2_data.best_bid().unwrap().count,
+            ob_data.bids[0].orders.len() as u32
+        );
+        assert!(l2_data.level(OrderSide::Asks, ob_data.asks.len()).is_none());
+    }
+
+    // -------------------------------------------------------- SAME FEATURE VALUES -- //
+
+    #[test]
+    fn test_l2_features() {
+        use crate::test_utils::test_orderbook;
+        use atelier_data::l2books::L2Orderbook;
+        use atelier_dcml::features;
+
+        let ob_data = test_orderbook();
+        let l2_data = L2Orderbook::from(&ob_data);
+
+        let names = features::OrderbookFeatures::list_features();
+        let ob_values = features::compute_features(
+            &[ob_data],
+            &names,
+            5,
+            0.0001,
+            features::FeaturesOutput::Values,
+        )
+        .unwrap();
+        let l2_values = features::compute_features(
+            &[l2_data],
+            &names,
+            5,
+            0.0001,
+            features::FeaturesOutput::Values,
+        )
+        .unwrap();
+
+        assert_eq!(ob_values, l2_values);
+    }
+
+    // ------------------------------------------------------------------ RANDOM L2 -- //
+
+    #[test]
+    fn test_l2_random() {
+        use atelier_data::{l2books::L2Orderbook, orders::OrderSide, views::BookView};
+
+        let l2_data = L2Orderbook::random(
+            100_000.0,
+            Some((5, 10)),
+            Some((20, 30)),
+            Some((0.1, 1.0)),
+            100_001.0,
+            Some((5, 10)),
+            Some((20, 30)),
+        );
+
+        let n_bids = l2_data.depth(OrderSide::Bids);
+        assert!((5..10).contains(&n_bids));
+        assert_eq!(l2_data.best_bid().unwrap().price, 100_000.0);
+        assert_eq!(l2_data.best_ask().unwrap().price, 100_001.0);
+
+        // prices move away from the top of the book on each side
+        assert!(l2_data.bids.prices.windows(2).all(|w| w[0] >= w[1]));
+        assert!(l2_data.asks.prices.windows(2).all(|w| w[0] <= w[1]));
+        assert!(l2_data.bids.counts.iter().all(|c| (20..30).contains(c)));
+    }
+}
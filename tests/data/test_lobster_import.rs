@@ -0,0 +1,110 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    // ----------------------------------------------------------------- TEST FILES -- //
+
+    /// Pair of LOBSTER files with 2 levels, the books start with 100 shares
+    /// at 10.01 (ask) and 200 shares at 9.99 (bid) placed before the first
+    /// message.
+    pub fn test_files(name: &str, last_bid_size: u32) -> (String, String) {
+        let messages = "\
+34200.000000001,1,11,50,100000,1
+34200.5,1,12,30,100200,-1
+34201.25,4,11,20,100000,1
+34201.3,2,99,40,99900,1
+34202,3,12,30,100200,-1
+34202.000001,5,0,15,100100,1
+";
+        let orderbook = format!(
+            "\
+100100,100,100000,50,9999999999,0,99900,200
+100100,100,100000,50,100200,30,99900,200
+100100,100,100000,30,100200,30,99900,200
+100100,100,100000,30,100200,30,99900,160
+100100,100,100000,30,9999999999,0,99900,160
+100100,100,100000,30,9999999999,0,99900,{}
+",
+            last_bid_size
+        );
+
+        let dir = std::env::temp_dir();
+        let message_route = dir.join(format!("{}_message_2.csv", name));
+        let orderbook_route = dir.join(format!("{}_orderbook_2.csv", name));
+        std::fs::write(&message_route, messages).unwrap();
+        std::fs::write(&orderbook_route, orderbook).unwrap();
+
+        (
+            message_route.to_str().unwrap().to_string(),
+            orderbook_route.to_str().unwrap().to_string(),
+        )
+    }
+}
+
+mod tests {
+
+    // ------------------------------------------------------------------- MESSAGES -- //
+
+    #[test]
+    fn test_read_messages() {
+        use crate::test_utils::test_files;
+        use atelier_data::{
+            data::lobster::LobsterImporter, events::EventKind, orders::OrderSide,
+        };
+
+        let (message_route, _) = test_files("atelier_lobster_messages", 160);
+        let importer = LobsterImporter::new()
+            .symbol("AAPL")
+            .levels(2)
+            .start_ts(1_000_000_000_000)
+            .build()
+            .unwrap();
+
+        let events = importer.read_messages(&message_route).unwrap();
+
+        assert_eq!(events.len(), 6);
+        assert_eq!(events[0].event_ts, 1_000_000_000_000 + 34_200_000_000_001);
+        assert_eq!(events[0].kind, EventKind::Submit);
+        assert_eq!(events[0].price, 10.0);
+        assert_eq!(events[1].side, OrderSide::Asks);
+        assert_eq!(events[2].kind, EventKind::Execute);
+        assert_eq!(events[5].kind, EventKind::ExecuteHidden);
+    }
+
+    // --------------------------------------------------------------------- REPLAY -- //
+
+    #[test]
+    fn test_replay() {
+        use crate::test_utils::test_files;
+        use atelier_data::data::lobster::LobsterImporter;
+
+        let importer = LobsterImporter::new()
+            .symbol("AAPL")
+            .levels(2)
+            .build()
+            .unwrap();
+
+        let (message_route, orderbook_route) = test_files("atelier_lobster_ok", 160);
+        let replay = importer.replay(&message_route, &orderbook_route).unwrap();
+
+        assert!(replay.report.is_consistent());
+        assert_eq!(replay.report.rows_checked, 5);
+        assert_eq!(replay.orderbooks.len(), 6);
+
+        let last = replay.orderbooks.last().unwrap();
+        assert_eq!(last.bids[0].price, 10.0);
+        assert_eq!(last.bids[0].volume, 30.0);
+        assert_eq!(last.bids[1].volume, 160.0);
+        assert_eq!(last.asks.len(), 1);
+        assert_eq!(last.asks[0].price, 10.01);
+
+        // a wrong orderbook row is reported
+        let (message_route, orderbook_route) = test_files("atelier_lobster_ko", 150);
+        let replay = importer.replay(&message_route, &orderbook_route).unwrap();
+
+        assert_eq!(replay.report.mismatched_rows, vec![6]);
+    }
+}
@@ -0,0 +1,84 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    // ------------------------------------------------------------------ TEST FILE -- //
+
+    pub fn test_csv_file(name: &str) -> String {
+        let content = "\
+index,spread,vwap,imb,target
+100,1.0,10.0,0.5,1
+200,,20.0,NaN,0
+300,3.0,abc,0.7,
+400,4.0,40.0,,1
+500,5.0,,0.9,0
+";
+        let file_route = std::env::temp_dir().join(name);
+        std::fs::write(&file_route, content).unwrap();
+        file_route.to_str().unwrap().to_string()
+    }
+}
+
+mod tests {
+
+    // ------------------------------------------------------------- DEFAULT POLICY -- //
+
+    #[test]
+    fn test_missing_default_error() {
+        use crate::test_utils::test_csv_file;
+        use atelier_data::data::Dataset;
+
+        let file_route = test_csv_file("atelier_test_missing_error.csv");
+        let result = Dataset::from_csv(&file_route, true, None, None);
+
+        assert!(result.unwrap_err().to_string().contains("spread at row 2"));
+    }
+
+    // ------------------------------------------------------------ COLUMN POLICIES -- //
+
+    #[test]
+    fn test_missing_policies() {
+        use crate::test_utils::test_csv_file;
+        use atelier_data::data::{
+            missing::{MissingPolicy, MissingValues},
+            Dataset,
+        };
+
+        let file_route = test_csv_file("atelier_test_missing_policies.csv");
+        let missing = MissingValues::new()
+            .default_policy(MissingPolicy::ForwardFill)
+            .column("target", MissingPolicy::DropRow)
+            .column("vwap", MissingPolicy::Mean)
+            .column("imb", MissingPolicy::Constant(0.0));
+
+        let (dataset, report) =
+            Dataset::from_csv_with(&file_route, true, None, None, &missing).unwrap();
+
+        // row 300 is dropped for its missing target
+        assert_eq!(dataset.index, vec![100, 200, 400, 500]);
+        assert_eq!(
+            dataset.column("spread").unwrap().values,
+            vec![1.0, 1.0, 4.0, 5.0]
+        );
+        assert_eq!(
+            dataset.column("vwap").unwrap().values,
+            vec![10.0, 20.0, 40.0, 70.0 / 3.0]
+        );
+        assert_eq!(
+            dataset.column("imb").unwrap().values,
+            vec![0.5, 0.0, 0.0, 0.9]
+        );
+        assert_eq!(dataset.target().values, vec![1.0, 0.0, 1.0, 0.0]);
+
+        assert_eq!(report.rows_read, 5);
+        assert_eq!(report.rows_dropped, 1);
+        assert_eq!(report.cells(&MissingPolicy::DropRow), 1);
+        assert_eq!(report.cells(&MissingPolicy::ForwardFill), 1);
+        assert_eq!(report.cells(&MissingPolicy::Mean), 1);
+        assert_eq!(report.cells(&MissingPolicy::Constant(0.0)), 2);
+        assert!(!report.is_clean());
+    }
+}
@@ -0,0 +1,213 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    use atelier_data::templates::Config;
+    use std::error::Error;
+
+    // ---------------------------------------------------------------- LOAD CONFIG -- //
+
+    /// Loads a config with the given `[[models]]` tables.
+    pub fn models_config(name: &str, models: &str) -> Result<Config, Box<dyn Error>> {
+        let file_route = std::env::temp_dir().join(name);
+        let contents = format!(
+            r#"
+            [[experiments]]
+            id = "exp_00"
+            n_progressions = 10
+
+            [[exchanges]]
+            id = "exc_00"
+            region = "region_a"
+            name = "exchange_a"
+            category = "cex"
+
+            {}
+            "#,
+            models
+        );
+        std::fs::write(&file_route, contents)?;
+        Config::load_from_toml(file_route.to_str().unwrap())
+    }
+}
+
+mod tests {
+
+    // ----------------------------------------------------------- NAMED AND LEGACY -- //
+
+    #[test]
+    fn test_named_and_legacy_params() {
+        use crate::test_utils::models_config;
+        use atelier_data::templates::{ModelParams, Models};
+
+        let config = models_config(
+            "atelier_test_model_params.toml",
+            r#"
+            [[models]]
+            id = "named"
+            label = "GBM"
+            mu = 0.01
+            sigma = 0.2
+
+            [[models]]
+            id = "positional"
+            label = "gbm"
+            params_values = [0.01, 0.2, 0.5]
+
+            [[models]]
+            id = "by_label"
+            label = "Hawkes"
+            params_labels = ["beta", "mu", "alpha"]
+            params_values = [1.5, 0.5, 1]
+
+            [[models]]
+            id = "optimizer"
+            label = "GD"
+            learning_rate = 1
+            "#,
+        )
+        .unwrap();
+
+        let params: Vec<ModelParams> = config
+            .models
+            .iter()
+            .map(|model| model.model_params().unwrap())
+            .collect();
+        assert_eq!(
+            params,
+            vec![
+                ModelParams::GBM {
+                    mu: 0.01,
+                    sigma: 0.2,
+                    dt: 0.01
+                },
+                ModelParams::GBM {
+                    mu: 0.01,
+                    sigma: 0.2,
+                    dt: 0.5
+                },
+                ModelParams::Hawkes {
+                    mu: 0.5,
+                    alpha: 1.0,
+                    beta: 1.5
+                },
+                ModelParams::GD {
+                    learning_rate: 1.0,
+                    epsilon: None
+                },
+            ]
+        );
+        assert_eq!(config.models[1].label, Some(Models::GBM));
+        assert!(config.models[0].params_values.is_none());
+    }
+
+    // ------------------------------------------------------------- INVALID PARAMS -- //
+
+    #[test]
+    fn test_invalid_named_params() {
+        use crate::test_utils::models_config;
+
+        // Unknown and missing names fail when loading
+        let error = models_config(
+            "atelier_test_model_params_unknown.toml",
+            r#"
+            [[models]]
+            id = "returns_00"
+            label = "Uniform"
+            lower = -0.1
+            uper = 0.1
+            "#,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("unknown field `uper`"));
+
+        let error = models_config(
+            "atelier_test_model_params_missing.toml",
+            r#"
+            [[models]]
+            id = "returns_00"
+            label = "GBM"
+            mu = 0.1
+            "#,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("missing field `sigma`"));
+
+        // Out of range values are reported by validate, with their names
+        let config = models_config(
+            "atelier_test_model_params_invalid.toml",
+            r#"
+            [[models]]
+            id = "returns_00"
+            label = "GBM"
+            mu = 0.1
+            sigma = -0.2
+            dt = 0
+
+            [[models]]
+            id = "returns_01"
+            label = "Uniform"
+            params_values = [0.1]
+            "#,
+        )
+        .unwrap();
+        let error = config.validate().unwrap_err();
+        let issues: Vec<String> = error.issues.iter().map(|i| i.to_string()).collect();
+        assert_eq!(
+            issues,
+            vec![
+                "exchanges[0].orderbook is missing",
+                "models[0].sigma must be greater than 0",
+                "models[0].dt must be greater than 0",
+                "models[1].params_values must be [lower, upper]",
+            ]
+        );
+    }
+
+    // -------------------------------------------------------------------- BUILDER -- //
+
+    #[test]
+    fn test_builder_with_params() {
+        use atelier_data::templates::{ModelConfig, ModelParams, Models};
+
+        let params = ModelParams::Uniform {
+            lower: -0.01,
+            upper: 0.01,
+        };
+        let model = ModelConfig::builder()
+            .id("returns_00".to_string())
+            .description("Uniform Distribution".to_string())
+            .params(params)
+            .build()
+            .unwrap();
+        assert_eq!(model.label, Some(Models::Uniform));
+        assert_eq!(model.model_params(), Ok(params));
+        assert_eq!(model.validate(), Ok(()));
+
+        let mismatch = ModelConfig::builder()
+            .id("returns_00".to_string())
+            .label(Models::GBM)
+            .description("Uniform Distribution".to_string())
+            .params(params)
+            .build();
+        assert!(mismatch.is_err());
+
+        // Params by name and by position can not be loaded, nor built
+        let both = ModelConfig::builder()
+            .id("returns_00".to_string())
+            .description("Uniform Distribution".to_string())
+            .params(params)
+            .params_values(vec![-0.01, 0.01])
+            .build();
+        assert!(both.is_err());
+
+        // Built models are loaded back as they were written
+        let contents = toml::to_string(&model).unwrap();
+        let loaded: ModelConfig = toml::from_str(&contents).unwrap();
+        assert_eq!(loaded.params, Some(params));
+        assert!(loaded.params_values.is_none());
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    use atelier_data::orderbooks::Orderbook;
+
+    // ------------------------------------------------------------ TEST ORDERBOOKS -- //
+
+    pub fn test_orderbooks(n_books: usize) -> Vec<Orderbook> {
+        (0..n_books)
+            .map(|_| {
+                Orderbook::random(
+                    100_000.0,
+                    Some((2, 5)),
+                    Some((1, 3)),
+                    Some((0.1, 0.5)),
+                    100_001.0,
+                    Some((2, 5)),
+                    Some((1, 3)),
+                )
+            })
+            .collect()
+    }
+}
+
+mod tests {
+
+    // ---------------------------------------------------------------- COMPRESSION -- //
+
+    #[test]
+    fn test_round_trip_by_extension() {
+        use crate::test_utils::test_orderbooks;
+        use atelier_data::{
+            data::ndjson::{load_from_ndjson, write_to_ndjson, Compression},
+            orderbooks::Orderbook,
+        };
+
+        let orderbooks = test_orderbooks(20);
+
+        for (file_name, compression) in [
+            ("atelier_test_books.ndjson", Compression::None),
+            ("atelier_test_books.ndjson.gz", Compression::Gzip),
+            ("atelier_test_books.ndjson.zst", Compression::Zstd),
+        ] {
+            let file_route = std::env::temp_dir().join(file_name);
+            let file_route = file_route.to_str().unwrap();
+            assert_eq!(Compression::from_route(file_route), compression);
+
+            write_to_ndjson(&orderbooks, file_route).unwrap();
+            let loaded: Vec<Orderbook> = load_from_ndjson(file_route).unwrap();
+
+            assert_eq!(loaded, orderbooks);
+        }
+    }
+
+    // ------------------------------------------------------------------ STREAMING -- //
+
+    #[test]
+    fn test_streaming_reader() {
+        use crate::test_utils::test_orderbooks;
+        use atelier_data::{
+            data::ndjson::{NdjsonReader, NdjsonWriter},
+            orderbooks::Orderbook,
+        };
+
+        let orderbooks = test_orderbooks(5);
+        let file_route = std::env::temp_dir().join("atelier_test_stream.ndjson.gz");
+        let file_route = file_route.to_str().unwrap();
+
+        let mut writer = NdjsonWriter::create(file_route).unwrap();
+        orderbooks.iter().for_each(|ob| writer.write(ob).unwrap());
+        writer.finish().unwrap();
+
+        let mut reader = NdjsonReader::<Orderbook>::open(file_route).unwrap();
+        assert_eq!(reader.next().unwrap().unwrap(), orderbooks[0]);
+        assert_eq!(reader.count(), 4);
+
+        // errors tell the line
+        let file_route = std::env::temp_dir().join("atelier_test_invalid.ndjson");
+        let file_route = file_route.to_str().unwrap();
+        std::fs::write(file_route, "\n{\"not\": \"a book\"}\n").unwrap();
+
+        let error = NdjsonReader::<Orderbook>::open(file_route)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap_err();
+        assert!(error.to_string().contains("line 2"));
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    use atelier_data::{levels::Level, orderbooks::Orderbook, orders::OrderSide};
+
+    // ------------------------------------------------------------ TEST ORDERBOOKS -- //
+
+    /// One level books at the given timestamps, with ids from 1.
+    pub fn test_orderbooks(timestamps: &[u64]) -> Vec<Orderbook> {
+        timestamps
+            .iter()
+            .enumerate()
+            .map(|(i, ts)| {
+                let mid = 100.0 + i as f64;
+                Orderbook::new(
+                    i as u32 + 1,
+                    *ts,
+                    "BTCUSDT".to_string(),
+                    vec![Level::new(0, OrderSide::Bids, mid - 0.5, 1.0, vec![])],
+                    vec![Level::new(0, OrderSide::Asks, mid + 0.5, 1.0, vec![])],
+                )
+            })
+            .collect()
+    }
+}
+
+mod tests {
+
+    // ------------------------------------------------------------------ RESAMPLER -- //
+
+    #[test]
+    fn test_regular_grid() {
+        use crate::test_utils::test_orderbooks;
+        use atelier_data::data::resample::Resampler;
+
+        let books = test_orderbooks(&[1_020, 1_030, 1_250, 1_260]);
+
+        let resampler = Resampler::new().interval(100).build().unwrap();
+        let resampled = resampler.resample(&books).unwrap();
+        let grid: Vec<(u64, u32)> = resampled
+            .iter()
+            .map(|ob| (ob.orderbook_ts, ob.orderbook_id))
+            .collect();
+        assert_eq!(grid, vec![(1_100, 2), (1_200, 2)]);
+        assert_eq!(resampled[0].bids, books[1].bids);
+
+        // explicit bounds, stale books are not carried forward
+        let resampler = Resampler::new()
+            .interval(100)
+            .start(1_000)
+            .end(1_300)
+            .max_staleness(100)
+            .build()
+            .unwrap();
+        let indices = resampler.resample_indices(&books).unwrap();
+        assert_eq!(
+            indices,
+            vec![
+                (1_000, None),
+                (1_100, Some(1)),
+                (1_200, None),
+                (1_300, Some(3))
+            ]
+        );
+
+        assert!(Resampler::new().interval(0).build().is_err());
+        assert!(Resampler::new().build().is_err());
+    }
+
+    // ------------------------------------------------------------------ ASOF JOIN -- //
+
+    #[test]
+    fn test_asof_join() {
+        use crate::test_utils::test_orderbooks;
+        use atelier_data::data::resample::{asof_indices, asof_join};
+
+        let books = test_orderbooks(&[100, 200, 300, 400]);
+        let predictions = vec![(150_u64, 0.1), (200, 0.2), (390, 0.3)];
+
+        let joined = asof_join(&books, &predictions, |p| p.0, None).unwrap();
+        let values: Vec<Option<f64>> = joined.iter().map(|p| p.map(|p| p.1)).collect();
+        assert_eq!(values, vec![None, Some(0.2), Some(0.2), Some(0.3)]);
+
+        let joined = asof_join(&books, &predictions, |p| p.0, Some(50)).unwrap();
+        let values: Vec<Option<f64>> = joined.iter().map(|p| p.map(|p| p.1)).collect();
+        assert_eq!(values, vec![None, Some(0.2), None, Some(0.3)]);
+
+        assert!(asof_indices(&[2, 1], &[1, 2], None).is_err());
+    }
+}
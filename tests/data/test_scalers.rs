@@ -0,0 +1,109 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    use atelier_data::data::Dataset;
+
+    // --------------------------------------------------------------- TEST DATASET -- //
+
+    pub fn test_dataset() -> Dataset {
+        Dataset::new()
+            .features(vec![
+                vec![1.0, 100.0],
+                vec![2.0, 300.0],
+                vec![3.0, 200.0],
+                vec![4.0, 500.0],
+                vec![5.0, 400.0],
+            ])
+            .feature_names(vec!["spread".to_string(), "vwap".to_string()])
+            .target(vec![0.0, 1.0, 0.0, 1.0, 0.0])
+            .build()
+            .unwrap()
+    }
+}
+
+mod tests {
+
+    // ------------------------------------------------------------ FIT / TRANSFORM -- //
+
+    #[test]
+    fn test_fit_transform() {
+        use crate::test_utils::test_dataset;
+        use atelier_data::data::scalers::{
+            MinMaxScaler, QuantileScaler, RobustScaler, Scaler, StandardScaler,
+        };
+
+        let mut dataset = test_dataset();
+        let mut standard = StandardScaler::new();
+        standard.fit_transform(&mut dataset).unwrap();
+        assert_eq!(standard.means, vec![3.0, 300.0]);
+        assert!(
+            dataset
+                .column("spread")
+                .unwrap()
+                .values
+                .iter()
+                .sum::<f64>()
+                .abs()
+                < 1e-9
+        );
+
+        standard.inverse_transform(&mut dataset).unwrap();
+        assert!((dataset.row(1)[1] - 300.0).abs() < 1e-9);
+
+        let mut min_max = MinMaxScaler::new();
+        min_max.fit_transform(&mut dataset).unwrap();
+        assert_eq!(
+            dataset.column("vwap").unwrap().values,
+            vec![0.0, 0.5, 0.25, 1.0, 0.75]
+        );
+
+        let mut robust = RobustScaler::new();
+        robust.fit(&test_dataset()).unwrap();
+        assert_eq!(robust.medians, vec![3.0, 300.0]);
+        assert_eq!(robust.iqrs, vec![2.0, 200.0]);
+
+        let mut quantile = QuantileScaler::new(5).unwrap();
+        let mut dataset = test_dataset();
+        quantile.fit_transform(&mut dataset).unwrap();
+        assert_eq!(
+            dataset.column("spread").unwrap().values,
+            vec![0.0, 0.25, 0.5, 0.75, 1.0]
+        );
+        assert_eq!(quantile.unscale(1, 0.5), 300.0);
+        assert!(QuantileScaler::new(1).is_err());
+    }
+
+    // --------------------------------------------------------- APPLY FITTED STATE -- //
+
+    #[test]
+    fn test_fitted_state() {
+        use crate::test_utils::test_dataset;
+        use atelier_data::data::scalers::{
+            load_scaler, write_scaler, Scaler, StandardScaler,
+        };
+
+        let train = test_dataset();
+        let mut scaler = StandardScaler::new();
+        assert!(scaler.transform(&mut test_dataset()).is_err());
+        scaler.fit(&train).unwrap();
+
+        // saved statistics are the ones used at inference time
+        let file_route = std::env::temp_dir().join("atelier_test_scaler.json");
+        let file_route = file_route.to_str().unwrap();
+        write_scaler(&scaler, file_route).unwrap();
+        let loaded: StandardScaler = load_scaler(file_route).unwrap();
+        assert_eq!(loaded, scaler);
+
+        // columns are matched by name, not by position
+        let mut inference = train.select(&["vwap", "spread"]).unwrap();
+        loaded.transform(&mut inference).unwrap();
+        assert_eq!(inference.row(2), vec![-100.0 / 141.4213562373095, 0.0]);
+
+        let mut missing = train.drop(&["vwap"]).unwrap();
+        assert!(loaded.transform(&mut missing).is_err());
+    }
+}
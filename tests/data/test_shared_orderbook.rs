@@ -0,0 +1,96 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    use atelier_data::l2books::L2Orderbook;
+
+    // ---------------------------------------------------------- TEST L2 ORDERBOOK -- //
+
+    pub fn test_l2_orderbook() -> L2Orderbook {
+        L2Orderbook::random(
+            100_000.0,
+            Some((5, 10)),
+            Some((5, 10)),
+            Some((0.1, 1.0)),
+            100_001.0,
+            Some((5, 10)),
+            Some((5, 10)),
+        )
+    }
+}
+
+mod tests {
+
+    // ------------------------------------------------------------- PUBLISH / READ -- //
+
+    #[test]
+    fn test_shared_publish() {
+        use crate::test_utils::test_l2_orderbook;
+        use atelier_data::{shared::shared_book, views::BookView};
+
+        let (mut writer, reader) = shared_book(test_l2_orderbook());
+        let before = reader.snapshot();
+
+        // staged changes are not visible until published
+        writer.staging().orderbook_ts = 42;
+        assert_eq!(reader.sequence(), 0);
+        assert_ne!(reader.read(|ob| ob.timestamp()), 42);
+
+        assert_eq!(writer.publish(), 1);
+        assert_eq!(reader.read(|ob| ob.timestamp()), 42);
+
+        // an old snapshot is kept unchanged
+        assert_eq!(before.sequence, 0);
+        assert_ne!(before.timestamp(), 42);
+    }
+
+    // ------------------------------------------------------- CONSISTENT SNAPSHOTS -- //
+
+    #[test]
+    fn test_shared_consistent_reads() {
+        use crate::test_utils::test_l2_orderbook;
+        use atelier_data::shared::shared_book;
+        use std::thread;
+
+        let mut ini_ob = test_l2_orderbook();
+        ini_ob.orderbook_ts = 0;
+        ini_ob.bids.volumes[0] = 0.0;
+        ini_ob.asks.volumes[0] = 0.0;
+
+        let (mut writer, reader) = shared_book(ini_ob);
+        let n_updates = 2_000;
+
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let i_reader = reader.clone();
+                thread::spawn(move || {
+                    let mut last_sequence = 0;
+                    while last_sequence < n_updates {
+                        let snapshot = i_reader.snapshot();
+                        // every field comes from the same published state
+                        assert_eq!(snapshot.orderbook_ts, snapshot.sequence);
+                        assert_eq!(snapshot.bids.volumes[0], snapshot.asks.volumes[0]);
+                        assert!(snapshot.sequence >= last_sequence);
+                        last_sequence = snapshot.sequence;
+                    }
+                })
+            })
+            .collect();
+
+        for i in 1..=n_updates {
+            writer.update(|ob| {
+                ob.orderbook_ts = i;
+                ob.bids.volumes[0] = i as f64;
+                ob.asks.volumes[0] = i as f64;
+            });
+        }
+
+        for handle in readers {
+            handle.join().unwrap();
+        }
+        assert_eq!(writer.sequence(), n_updates);
+    }
+}
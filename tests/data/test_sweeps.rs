@@ -0,0 +1,167 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    use atelier_data::templates::Config;
+    use std::path::Path;
+
+    // ---------------------------------------------------------------- LOAD CONFIG -- //
+
+    /// Loads the sweep template of atelier-synth.
+    pub fn sweep_config() -> Config {
+        let workspace_root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let file_route = workspace_root
+            .join("atelier-synth")
+            .join("templates")
+            .join("sweep_orderbooks.toml");
+        Config::load_from_toml(file_route.to_str().unwrap()).unwrap()
+    }
+}
+
+mod tests {
+
+    // ------------------------------------------------------------------ EXPANSION -- //
+
+    #[test]
+    fn test_sweep_expansion() {
+        use crate::test_utils::sweep_config;
+        use atelier_data::templates::ModelParams;
+
+        let config = sweep_config();
+        assert_eq!(config.validate(), Ok(()));
+
+        // 3 sigmas, 2 zipped depths and 2 tick sizes
+        let instances = config.expand().unwrap();
+        assert_eq!(instances.len(), 12);
+        assert_eq!(instances[0].id, "vol_grid_00");
+        assert_eq!(instances[11].id, "vol_grid_11");
+
+        // The last sweep changes fastest, zipped sweeps move together
+        let instance = &instances[3];
+        let orderbook = instance.config.exchanges[0].orderbook.as_ref().unwrap();
+        assert_eq!(orderbook.ticksize, Some(vec![0.5, 1.0]));
+        assert_eq!(orderbook.bid_levels, Some(vec![10, 20]));
+        assert_eq!(orderbook.ask_levels, Some(vec![10, 20]));
+        assert_eq!(instance.values.len(), 4);
+        assert_eq!(instance.values[0].0, "models.mod_00.sigma");
+
+        let sigmas: Vec<f64> = instances
+            .iter()
+            .step_by(4)
+            .map(|instance| match instance.config.models[0].model_params() {
+                Ok(ModelParams::GBM { sigma, .. }) => sigma,
+                _ => panic!("not a GBM"),
+            })
+            .collect();
+        assert_eq!(sigmas.len(), 3);
+        assert!((sigmas[0] - 0.0001).abs() < 1e-12);
+        assert!((sigmas[1] - 0.001).abs() < 1e-12);
+        assert!((sigmas[2] - 0.01).abs() < 1e-12);
+
+        // Instances have a single experiment, without sweeps
+        let experiments = &instances[5].config.experiments;
+        assert_eq!(experiments.len(), 1);
+        assert_eq!(experiments[0].id, "vol_grid_05");
+        assert!(experiments[0].sweeps.is_empty());
+    }
+
+    // --------------------------------------------------------------------- RANGES -- //
+
+    #[test]
+    fn test_sweep_ranges() {
+        use atelier_data::templates::sweeps::{SweepLogRange, SweepRange};
+
+        let range = SweepRange {
+            start: 0.1,
+            stop: 0.3,
+            step: 0.1,
+        };
+        assert_eq!(range.values().unwrap().len(), 3);
+
+        let log_range = SweepLogRange {
+            start: 1.0,
+            stop: 100.0,
+            num: 3,
+        };
+        let values = log_range.values();
+        assert!((values[1] - 10.0).abs() < 1e-9);
+        assert!((values[2] - 100.0).abs() < 1e-9);
+    }
+
+    // ------------------------------------------------------ EXPAND WITHOUT CHECKS -- //
+
+    #[test]
+    fn test_expand_zero_step() {
+        use crate::test_utils::sweep_config;
+        use atelier_data::templates::sweeps::SweepRange;
+
+        // Expanding does not rely on validate to stop at a bad range
+        let mut config = sweep_config();
+        let sweep = &mut config.experiments[0].sweeps[0];
+        sweep.log_range = None;
+        sweep.range = Some(SweepRange {
+            start: 0.1,
+            stop: 0.2,
+            step: 0.0,
+        });
+        assert_eq!(
+            config.expand().unwrap_err(),
+            "sweep of models.mod_00.sigma range step must be greater than 0"
+        );
+    }
+
+    // ------------------------------------------------------------- INVALID SWEEPS -- //
+
+    #[test]
+    fn test_invalid_sweeps() {
+        use crate::test_utils::sweep_config;
+        use atelier_data::templates::sweeps::{SweepConfig, SweepRange};
+
+        let range = SweepRange {
+            start: 0.1,
+            stop: 0.2,
+            step: 0.0,
+        };
+
+        // Values are checked first
+        let mut config = sweep_config();
+        let sweeps = &mut config.experiments[0].sweeps;
+        sweeps[0].log_range = None;
+        sweeps[0].range = Some(range);
+        sweeps[3].range = Some(range);
+        let error = config.validate().unwrap_err();
+        let issues: Vec<String> = error.issues.iter().map(|i| i.to_string()).collect();
+        assert_eq!(
+            issues,
+            vec![
+                "experiments[0].sweeps[0].range step must be greater than 0",
+                "experiments[0].sweeps[3] must have one of values, range or log_range",
+            ]
+        );
+
+        // Then zips and parameters, when expanding
+        let mut config = sweep_config();
+        config.experiments[0].sweeps[2].values =
+            Some(vec![toml::Value::from(vec![5, 10])]);
+        assert_eq!(
+            config.validate().unwrap_err().issues[0].to_string(),
+            "experiments[0].sweeps sweeps zipped as depth must have the same number of values"
+        );
+
+        let mut config = sweep_config();
+        config.experiments[0].sweeps.push(SweepConfig {
+            param: "models.mod_00.theta".to_string(),
+            values: Some(vec![toml::Value::from(1.0)]),
+            range: None,
+            log_range: None,
+            zip: None,
+        });
+        assert_eq!(
+            config.validate().unwrap_err().issues[0].to_string(),
+            "experiments[0].sweeps models.mod_00.theta: GBM has no parameter theta"
+        );
+    }
+}
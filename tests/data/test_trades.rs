@@ -0,0 +1,172 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    use atelier_data::{
+        levels::Level, orderbooks::Orderbook, orders::OrderSide, trades::Trade,
+    };
+
+    // ---------------------------------------------------------------- TEST TRADES -- //
+
+    /// Alternating buys and sells, every 10 microseconds from 1_005, the even
+    /// ones with maker and taker order ids.
+    pub fn test_trades(n_trades: usize) -> Vec<Trade> {
+        (0..n_trades)
+            .map(|i| {
+                let side = if i % 2 == 0 {
+                    OrderSide::Bids
+                } else {
+                    OrderSide::Asks
+                };
+                let trade = Trade::new(
+                    i as u64 + 1,
+                    1_005 + 10 * i as u64,
+                    side,
+                    100.0 + i as f64 * 0.1,
+                    1.0 + i as f64,
+                );
+                if i % 2 == 0 {
+                    trade.with_order_ids(10 + i as u64, 20 + i as u64)
+                } else {
+                    trade
+                }
+            })
+            .collect()
+    }
+
+    // ------------------------------------------------------------ TEST ORDERBOOKS -- //
+
+    /// One level books every 20 microseconds from 1_000.
+    pub fn test_orderbooks(n_books: usize) -> Vec<Orderbook> {
+        (0..n_books)
+            .map(|i| {
+                Orderbook::new(
+                    i as u32 + 1,
+                    1_000 + 20 * i as u64,
+                    "BTCUSDT".to_string(),
+                    vec![Level::new(0, OrderSide::Bids, 99.0, 1.0, vec![])],
+                    vec![Level::new(0, OrderSide::Asks, 101.0, 1.0, vec![])],
+                )
+            })
+            .collect()
+    }
+}
+
+mod tests {
+
+    // ----------------------------------------------------------------- ROUND TRIP -- //
+
+    #[test]
+    fn test_round_trip() {
+        use crate::test_utils::test_trades;
+        use atelier_data::data::{
+            columnar::write_trades_to_parquet,
+            ndjson::write_to_ndjson,
+            trades::{load_trades, write_trades_to_csv},
+            write_to_json,
+        };
+
+        let trades = test_trades(7);
+        let dir = std::env::temp_dir();
+        let route = |name: &str| dir.join(name).to_str().unwrap().to_string();
+
+        let csv_route = route("atelier_test_trades.csv");
+        write_trades_to_csv(&trades, &csv_route).unwrap();
+        assert_eq!(load_trades(&csv_route).unwrap(), trades);
+
+        let json_route = route("atelier_test_trades.json");
+        write_to_json(&trades, &json_route);
+        assert_eq!(load_trades(&json_route).unwrap(), trades);
+
+        let ndjson_route = route("atelier_test_trades.ndjson.gz");
+        write_to_ndjson(&trades, &ndjson_route).unwrap();
+        assert_eq!(load_trades(&ndjson_route).unwrap(), trades);
+
+        let parquet_route = route("atelier_test_trades.parquet");
+        write_trades_to_parquet(&trades, &parquet_route, 3).unwrap();
+        assert_eq!(load_trades(&parquet_route).unwrap(), trades);
+
+        let error = load_trades("atelier_test_trades.csv.gz").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "compressed csv is not supported: atelier_test_trades.csv.gz"
+        );
+    }
+
+    // ----------------------------------------------------------------- VENDOR CSV -- //
+
+    #[test]
+    fn test_vendor_csv() {
+        use atelier_data::{data::trades::load_trades_from_csv, orders::OrderSide};
+
+        let file_route = std::env::temp_dir().join("atelier_test_tape.csv");
+        let file_route = file_route.to_str().unwrap();
+        std::fs::write(
+            file_route,
+            "trade_ts, trade_id, price, quantity, side\n\
+             1000, 7, 100.5, 0.25, Asks\n\
+             1001, 8, 100.6, 1.5, Bids\n",
+        )
+        .unwrap();
+
+        let trades = load_trades_from_csv(file_route).unwrap();
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].side, OrderSide::Asks);
+        assert_eq!(trades[1].trade_id, 8);
+        assert_eq!(trades[1].maker_order_id, None);
+
+        std::fs::write(
+            file_route,
+            "trade_ts,trade_id,price,quantity,side\n1,2,x,1,Bids\n",
+        )
+        .unwrap();
+        let error = load_trades_from_csv(file_route).unwrap_err().to_string();
+        assert!(error.contains("row 1"));
+    }
+
+    // ---------------------------------------------------------------------- JOINS -- //
+
+    #[test]
+    fn test_book_joins() {
+        use crate::test_utils::{test_orderbooks, test_trades};
+        use atelier_data::trades::{books_asof, trade_flows, trades_per_book};
+
+        // books at 1_000, 1_020, 1_040, trades at 1_005, 1_015, .., 1_065
+        let books = test_orderbooks(3);
+        let trades = test_trades(7);
+
+        let groups = trades_per_book(&books, &trades).unwrap();
+        let sizes: Vec<usize> = groups.iter().map(|g| g.len()).collect();
+        assert_eq!(sizes, vec![0, 2, 2]);
+
+        let flows = trade_flows(&books, &trades).unwrap();
+        assert_eq!(flows[0].vwap(), None);
+        // buy of 1.0 at 100.0, sell of 2.0 at 100.1
+        assert_eq!(flows[1].buy_volume, 1.0);
+        assert_eq!(flows[1].sell_volume, 2.0);
+        assert!((flows[1].vwap().unwrap() - 300.2 / 3.0).abs() < 1e-12);
+        assert!((flows[1].imbalance() + 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(flows[2].last_price, Some(100.3));
+
+        let asof = books_asof(&books, &trades, None).unwrap();
+        assert_eq!(
+            asof,
+            vec![
+                Some(0),
+                Some(0),
+                Some(1),
+                Some(1),
+                Some(2),
+                Some(2),
+                Some(2)
+            ]
+        );
+
+        let mut unsorted = trades.clone();
+        unsorted.swap(0, 1);
+        assert!(trades_per_book(&books, &unsorted).is_err());
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    use atelier_data::{levels::Level, orderbooks::Orderbook, orders::OrderSide};
+
+    // ------------------------------------------------------------ TEST ORDERBOOKS -- //
+
+    /// Books with 3 bid levels, and 2 ask levels but for the last one.
+    pub fn test_orderbooks(n_books: usize) -> Vec<Orderbook> {
+        (0..n_books)
+            .map(|i| {
+                let mid = 100.0 + i as f64;
+                let bids = (0..3)
+                    .map(|l| {
+                        Level::new(l, OrderSide::Bids, mid - 1.0 - l as f64, 1.5, vec![])
+                    })
+                    .collect();
+                let n_asks = if i == n_books - 1 { 1 } else { 2 };
+                let asks = (0..n_asks)
+                    .map(|l| {
+                        Level::new(l, OrderSide::Asks, mid + 1.0 + l as f64, 2.5, vec![])
+                    })
+                    .collect();
+                Orderbook::new(
+                    i as u32 + 1,
+                    1_000 + i as u64,
+                    "BTCUSDT".to_string(),
+                    bids,
+                    asks,
+                )
+            })
+            .collect()
+    }
+}
+
+mod tests {
+
+    // ----------------------------------------------------------------- ROUND TRIP -- //
+
+    #[test]
+    fn test_round_trip() {
+        use crate::test_utils::test_orderbooks;
+        use atelier_data::data::wide_csv::{
+            load_from_wide_csv, write_to_wide_csv, WideCsvFormat,
+        };
+
+        let orderbooks = test_orderbooks(4);
+        let format = WideCsvFormat::new(3);
+        let file_route = std::env::temp_dir().join("atelier_test_wide.csv");
+        let file_route = file_route.to_str().unwrap();
+
+        write_to_wide_csv(&orderbooks, file_route, &format).unwrap();
+        let header = std::fs::read_to_string(file_route).unwrap();
+        assert!(header.starts_with("timestamp,bid_px_1,bid_px_2,bid_px_3,bid_sz_1"));
+
+        let loaded = load_from_wide_csv(file_route, &format, "BTCUSDT").unwrap();
+        assert_eq!(loaded, orderbooks);
+
+        // reading less levels than written
+        let top =
+            load_from_wide_csv(file_route, &WideCsvFormat::new(1), "BTCUSDT").unwrap();
+        assert!(top
+            .iter()
+            .all(|ob| ob.bids.len() == 1 && ob.asks.len() == 1));
+    }
+
+    // --------------------------------------------------------------- CUSTOM NAMES -- //
+
+    #[test]
+    fn test_custom_names() {
+        use atelier_data::data::wide_csv::{load_from_wide_csv, WideCsvFormat};
+
+        let file_route = std::env::temp_dir().join("atelier_test_vendor.csv");
+        let file_route = file_route.to_str().unwrap();
+        std::fs::write(
+            file_route,
+            "ts,ask1,askqty1,bid1,bidqty1,ask2,askqty2,bid2,bidqty2\n\
+             10,101.0,1.0,99.0,2.0,102.0,3.0,,\n",
+        )
+        .unwrap();
+
+        let format = WideCsvFormat::new(5)
+            .timestamp("ts")
+            .prefixes("bid", "bidqty", "ask", "askqty")
+            .separator("");
+        let loaded = load_from_wide_csv(file_route, &format, "ETHUSDT").unwrap();
+
+        assert_eq!(loaded[0].orderbook_ts, 10);
+        assert_eq!(loaded[0].bids.len(), 1);
+        assert_eq!(loaded[0].asks[1].price, 102.0);
+        assert_eq!(loaded[0].asks[1].volume, 3.0);
+
+        let missing = WideCsvFormat::new(2).timestamp("ts");
+        assert!(load_from_wide_csv(file_route, &missing, "ETHUSDT").is_err());
+    }
+}
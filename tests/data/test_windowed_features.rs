@@ -0,0 +1,96 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    use atelier_data::data::Dataset;
+
+    // --------------------------------------------------------------- TEST DATASET -- //
+
+    pub fn test_dataset() -> Dataset {
+        Dataset::new()
+            .index(vec![100, 200, 300, 400, 500, 600])
+            .features(vec![
+                vec![1.0],
+                vec![2.0],
+                vec![4.0],
+                vec![8.0],
+                vec![16.0],
+                vec![32.0],
+            ])
+            .feature_names(vec!["spread".to_string()])
+            .target(vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0])
+            .build()
+            .unwrap()
+    }
+}
+
+mod tests {
+
+    // ----------------------------------------------------------------------- LAGS -- //
+
+    #[test]
+    fn test_lags() {
+        use crate::test_utils::test_dataset;
+
+        let lagged = test_dataset().lags(2).unwrap();
+
+        assert_eq!(
+            lagged.feature_names(),
+            vec!["spread", "spread_lag_1", "spread_lag_2"]
+        );
+        assert_eq!(lagged.index, vec![300, 400, 500, 600]);
+        assert_eq!(lagged.row(0), vec![4.0, 2.0, 1.0]);
+        assert_eq!(lagged.target().values, vec![2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(lagged.lookback, 2);
+
+        assert!(test_dataset().lags(6).is_err());
+    }
+
+    // -------------------------------------------------------------------- ROLLING -- //
+
+    #[test]
+    fn test_rolling() {
+        use crate::test_utils::test_dataset;
+        use atelier_data::data::windows::Aggregate;
+
+        let aggregates = [
+            Aggregate::Mean,
+            Aggregate::Min,
+            Aggregate::Max,
+            Aggregate::Std,
+            Aggregate::Ewma(0.5),
+        ];
+        let rolled = test_dataset().rolling(2, &aggregates).unwrap();
+
+        assert_eq!(rolled.len(), 5);
+        assert_eq!(rolled.lookback, 1);
+        assert_eq!(rolled.column("spread_mean_2").unwrap().values[0], 1.5);
+        assert_eq!(rolled.column("spread_min_2").unwrap().values[4], 16.0);
+        assert_eq!(rolled.column("spread_max_2").unwrap().values[4], 32.0);
+        assert_eq!(rolled.column("spread_std_2").unwrap().values[1], 1.0);
+        assert_eq!(rolled.column("spread_ewma_2").unwrap().values[0], 1.5);
+
+        assert!(test_dataset().rolling(2, &[Aggregate::Ewma(1.5)]).is_err());
+    }
+
+    // -------------------------------------------------------------------- HORIZON -- //
+
+    #[test]
+    fn test_align_horizon() {
+        use crate::test_utils::test_dataset;
+
+        let dataset = test_dataset().lags(1).unwrap().align_horizon(2).unwrap();
+
+        // features at t (index 200) paired with the target at t+2
+        assert_eq!(dataset.index, vec![200, 300, 400]);
+        assert_eq!(dataset.row(0), vec![2.0, 1.0]);
+        assert_eq!(dataset.target().values, vec![3.0, 4.0, 5.0]);
+        assert_eq!((dataset.lookback, dataset.horizon), (1, 2));
+
+        assert!(dataset.to_sequences(4).is_err());
+        assert!(dataset.to_sequences(2).is_ok());
+    }
+}
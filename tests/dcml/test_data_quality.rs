@@ -0,0 +1,128 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    use atelier_data::{levels::Level, orderbooks::Orderbook, orders::OrderSide};
+
+    // ------------------------------------------------------------ TEST ORDERBOOKS -- //
+
+    fn book(id: u32, ts: u64, bid: f64, ask: Option<f64>, bid_volume: f64) -> Orderbook {
+        let asks = match ask {
+            Some(ask) => vec![Level::new(0, OrderSide::Asks, ask, 1.0, vec![])],
+            None => vec![],
+        };
+        Orderbook::new(
+            id,
+            ts,
+            "BTCUSDT".to_string(),
+            vec![Level::new(0, OrderSide::Bids, bid, bid_volume, vec![])],
+            asks,
+        )
+    }
+
+    /// Books every 100 with a midprice of 100 and a spread of 1.
+    pub fn clean_orderbooks(n_books: usize) -> Vec<Orderbook> {
+        (0..n_books)
+            .map(|i| book(i as u32, 100 * i as u64, 99.5, Some(100.5), 1.0))
+            .collect()
+    }
+
+    /// 50 clean books but for one of each issue, the midprice jumps to 150
+    /// from the 30th book on.
+    pub fn dirty_orderbooks() -> Vec<Orderbook> {
+        let mut books = clean_orderbooks(50);
+
+        books[5] = book(5, 500, 100.5, Some(99.5), 1.0);
+        books[6] = book(6, 600, 100.0, Some(100.0), 1.0);
+        books[7] = book(7, 700, 99.5, None, 1.0);
+        books[8] = book(8, 800, 99.5, Some(100.5), 0.0);
+        books[9].orderbook_ts = 800;
+        books[12] = book(12, 1_200, 90.0, Some(110.0), 1.0);
+        for (i, ob) in books.iter_mut().enumerate() {
+            if i >= 10 {
+                ob.orderbook_ts += 5_000;
+            }
+            if i >= 30 {
+                *ob = book(i as u32, ob.orderbook_ts, 149.5, Some(150.5), 1.0);
+            }
+        }
+
+        books
+    }
+}
+
+mod tests {
+
+    // --------------------------------------------------------------------- CHECKS -- //
+
+    #[test]
+    fn test_quality_checks() {
+        use crate::test_utils::{clean_orderbooks, dirty_orderbooks};
+        use atelier_dcml::quality::QualityChecks;
+
+        let checks = QualityChecks::new().build().unwrap();
+
+        let report = checks.report(&clean_orderbooks(20));
+        assert!(report.is_clean());
+        assert_eq!(report.n_books, 20);
+        assert_eq!(report.spread_stats.unwrap().mean, 1.0);
+
+        let report = checks.report(&dirty_orderbooks());
+        assert!(!report.is_clean());
+        assert_eq!(report.crossed_books, 1);
+        assert_eq!(report.locked_books, 1);
+        assert_eq!(report.empty_bids, 0);
+        assert_eq!(report.empty_asks, 1);
+        assert_eq!(report.invalid_volumes, 1);
+        assert_eq!(report.duplicate_timestamps, 1);
+        assert_eq!(report.unordered_timestamps, 0);
+        assert_eq!(report.gaps, 1);
+        assert_eq!(report.outlier_spreads, 1);
+        assert_eq!(report.price_jumps, 1);
+
+        // an explicit gap limit below the regular interval
+        let checks = QualityChecks::new().max_gap(50).build().unwrap();
+        assert_eq!(checks.report(&dirty_orderbooks()).gaps, 48);
+
+        assert!(QualityChecks::new().price_jump(0.0).build().is_err());
+    }
+
+    // ---------------------------------------------------------- STREAM AND RENDER -- //
+
+    #[test]
+    fn test_stream_and_render() {
+        use crate::test_utils::dirty_orderbooks;
+        use atelier_data::data::ndjson::write_to_ndjson;
+        use atelier_dcml::quality::{QualityChecks, QualityReport};
+
+        let books = dirty_orderbooks();
+        let file_route = std::env::temp_dir().join("atelier_test_quality.ndjson.zst");
+        let file_route = file_route.to_str().unwrap();
+        write_to_ndjson(&books, file_route).unwrap();
+
+        let checks = QualityChecks::new().build().unwrap();
+        let report = checks.report_file(file_route).unwrap();
+        assert_eq!(report, checks.report(&books));
+
+        // only ndjson files are read compressed
+        let error = checks
+            .report_file("atelier_test_quality.json.gz")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "compressed json is not supported: atelier_test_quality.json.gz"
+        );
+
+        let json = report.to_json().unwrap();
+        let parsed: QualityReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, report);
+
+        let text = report.to_string();
+        assert!(text.contains("crossed books: 1"));
+        assert!(text.contains("time gaps: 1"));
+        assert!(text.contains("books: 50"));
+    }
+}
@@ -0,0 +1,112 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod test_utils {
+
+    use atelier_data::{levels::Level, orderbooks::Orderbook, orders::OrderSide};
+
+    pub const MIDPRICES: [f64; 8] =
+        [100.0, 101.0, 100.0, 102.0, 103.0, 101.0, 104.0, 104.0];
+
+    // ------------------------------------------------------------ TEST ORDERBOOKS -- //
+
+    /// One level books with the `MIDPRICES`, a spread of 1 and timestamps
+    /// 10, 20, 30, ...
+    pub fn test_orderbooks() -> Vec<Orderbook> {
+        MIDPRICES
+            .iter()
+            .enumerate()
+            .map(|(i, mid)| {
+                Orderbook::new(
+                    i as u32,
+                    10 * (i as u64 + 1),
+                    "BTCUSDT".to_string(),
+                    vec![Level::new(0, OrderSide::Bids, mid - 0.5, 2.0, vec![])],
+                    vec![Level::new(0, OrderSide::Asks, mid + 0.5, 1.0, vec![])],
+                )
+            })
+            .collect()
+    }
+}
+
+mod tests {
+
+    // ------------------------------------------------------------------ ALIGNMENT -- //
+
+    #[test]
+    fn test_features_and_horizon() {
+        use crate::test_utils::{test_orderbooks, MIDPRICES};
+        use atelier_data::data::Dataset;
+        use atelier_dcml::{
+            datasets::{FeatureSpec, FromOrderbooks},
+            targets::TargetSelector,
+        };
+
+        let books = test_orderbooks();
+        let features = FeatureSpec::new(&["spread", "midprice"]).unwrap();
+        let targets = TargetSelector::new(&["return_sign"]).unwrap();
+
+        let dataset = Dataset::from_orderbooks(&books, &features, &targets, 2).unwrap();
+
+        assert_eq!(dataset.len(), 6);
+        assert_eq!(dataset.index, vec![10, 20, 30, 40, 50, 60]);
+        assert_eq!(dataset.feature_names(), vec!["spread", "midprice"]);
+        assert_eq!(dataset.features[1].values, MIDPRICES[..6].to_vec());
+        assert_eq!(dataset.target().name, "return_sign");
+        assert_eq!(dataset.horizon, 2);
+
+        // the target of row t is the sign of the return from t to t+2
+        let expected: Vec<f64> = (0..6)
+            .map(|t| (MIDPRICES[t + 2] > MIDPRICES[t]) as u8 as f64)
+            .collect();
+        assert_eq!(dataset.target().values, expected);
+    }
+
+    // ------------------------------------------------------------------- LOOKBACK -- //
+
+    #[test]
+    fn test_lookback() {
+        use crate::test_utils::{test_orderbooks, MIDPRICES};
+        use atelier_data::data::{windows::Aggregate, Dataset};
+        use atelier_dcml::{
+            datasets::{FeatureSpec, FromOrderbooks},
+            targets::TargetSelector,
+        };
+
+        let books = test_orderbooks();
+        let features = FeatureSpec::new(&["midprice"])
+            .unwrap()
+            .rolling(3, &[Aggregate::Mean])
+            .lags(1);
+        let targets = TargetSelector::new(&["return_sign"]).unwrap();
+        assert_eq!(features.lookback(), 3);
+
+        let dataset = Dataset::from_orderbooks(&books, &features, &targets, 1).unwrap();
+
+        // 8 books, 3 without lookback and 1 without horizon
+        assert_eq!(dataset.len(), 4);
+        assert_eq!(dataset.index, vec![40, 50, 60, 70]);
+        assert_eq!((dataset.lookback, dataset.horizon), (3, 1));
+        assert_eq!(
+            dataset.feature_names(),
+            vec![
+                "midprice",
+                "midprice_mean_3",
+                "midprice_lag_1",
+                "midprice_mean_3_lag_1"
+            ]
+        );
+        assert_eq!(dataset.features[0].values, MIDPRICES[3..7].to_vec());
+        assert_eq!(dataset.target().values, vec![1.0, 0.0, 1.0, 0.0]);
+
+        // not enough books, or targets with horizons of their own
+        assert!(Dataset::from_orderbooks(&books[..4], &features, &targets, 1).is_err());
+        let horizons = TargetSelector::new(&["return_sign"])
+            .unwrap()
+            .horizons(&[1, 2])
+            .unwrap();
+        assert!(Dataset::from_orderbooks(&books, &features, &horizons, 1).is_err());
+    }
+}
@@ -27,6 +27,7 @@ mod tests {
         let template = templates::Config::load_from_toml(template_file.to_str().unwrap())
             .unwrap()
             .clone();
+        template.validate().unwrap();
 
         println!("model: {:?}", template.models[0].clone());
 
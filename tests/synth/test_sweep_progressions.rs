@@ -0,0 +1,60 @@
+#[cfg(test)]
+
+// -- ----------------------------------------------------------------- TESTS UTILS -- //
+// -- ----------------------------------------------------------------- ----------- -- //
+
+mod tests {
+
+    // --------------------------------------------------------- SWEEP PROGRESSIONS -- //
+
+    #[tokio::test]
+    async fn test_sweep_progressions() {
+        use atelier_data::{orderbooks::Orderbook, templates};
+        use atelier_synth::synthbooks::sweep_progressions;
+        use std::{env, fs, path::Path};
+
+        // --- Template file (toml)
+        let workspace_root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let template_file = workspace_root
+            .join("atelier-synth")
+            .join("templates")
+            .join("sweep_orderbooks.toml");
+        let mut template =
+            templates::Config::load_from_toml(template_file.to_str().unwrap()).unwrap();
+        template.experiments[0].n_progressions = 10;
+
+        // --- One directory per run
+        let output_dir = env::temp_dir().join("atelier_test_sweep_progressions");
+        let _ = fs::remove_dir_all(&output_dir);
+        let run_dirs = sweep_progressions(&template, &output_dir).await.unwrap();
+        assert_eq!(run_dirs.len(), 12);
+        assert_eq!(run_dirs[7], output_dir.join("vol_grid_07"));
+
+        let contents =
+            fs::read_to_string(run_dirs[7].join("ai_00_binance.json")).unwrap();
+        let orderbooks: Vec<Orderbook> = serde_json::from_str(&contents).unwrap();
+        assert_eq!(orderbooks.len(), 9);
+
+        let contents = fs::read_to_string(run_dirs[7].join("sweep.json")).unwrap();
+        let sweep: serde_json::Value = serde_json::from_str(&contents).unwrap();
+        assert_eq!(sweep["id"], "vol_grid_07");
+        assert_eq!(
+            sweep["values"]["exchanges.ai_00.orderbook.ticksize"],
+            serde_json::json!([0.5, 1.0])
+        );
+
+        // --- Resolved config of the run
+        let config_file = run_dirs[7].join("config.toml");
+        let config =
+            templates::Config::load_from_toml(config_file.to_str().unwrap()).unwrap();
+        assert_eq!(config.validate(), Ok(()));
+        assert_eq!(config.experiments[0].id, "vol_grid_07");
+        assert!(config.experiments[0].sweeps.is_empty());
+        assert_eq!(
+            config.exchanges[0].orderbook.as_ref().unwrap().ticksize,
+            Some(vec![0.5, 1.0])
+        );
+
+        fs::remove_dir_all(&output_dir).unwrap();
+    }
+}